@@ -0,0 +1,235 @@
+use crate::effect_size::{EffectSize, EffectSizeKind};
+use crate::test::AlternativeHypothesis;
+use statrs::distribution::{ContinuousCDF, Normal};
+use statrs::{Result, StatsError};
+
+use super::resample::{jackknife_influence, jackknife_standard_error};
+use super::{Bootstrap, BootstrapInterval};
+
+impl Bootstrap {
+    /// Returns the confidence interval of the statistic, constructed with the given `method`
+    /// (Efron & Tibshirani, 1993; DiCiccio & Efron, 1996).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no replicate of the statistic was a number, for bootstrap-t intervals
+    /// if the replicates were not studentized, and for BCa intervals if all replicates lie on
+    /// one side of the estimate.
+    ///
+    /// # References
+    ///
+    /// DiCiccio, T. J., & Efron, B. (1996). Bootstrap confidence intervals. Statistical Science,
+    ///     11(3), 189–228. <https://doi.org/10.1214/ss/1032280214>
+    pub fn interval(&self, method: BootstrapInterval) -> Result<(f64, f64)> {
+        let alpha = 1.0 - self.confidence_level;
+        let (lower, upper) = (alpha / 2.0, 1.0 - alpha / 2.0);
+        let mut sorted = self.replicates.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        if sorted.is_empty() {
+            return Err(StatsError::BadParams);
+        }
+
+        match method {
+            BootstrapInterval::Percentile => {
+                Ok((quantile(&sorted, lower), quantile(&sorted, upper)))
+            }
+            BootstrapInterval::Basic => Ok((
+                2.0 * self.estimate - quantile(&sorted, upper),
+                2.0 * self.estimate - quantile(&sorted, lower),
+            )),
+            BootstrapInterval::BCa => {
+                let normal = Normal::new(0.0, 1.0).unwrap();
+                let below = sorted.iter().filter(|r| **r < self.estimate).count();
+                let bias = normal.inverse_cdf(below as f64 / sorted.len() as f64);
+                if !bias.is_finite() {
+                    return Err(StatsError::BadParams);
+                }
+
+                let influence = jackknife_influence(&self.jackknife);
+                let squares = influence.iter().map(|u| u * u).sum::<f64>();
+                let acceleration = if squares > 0.0 {
+                    influence.iter().map(|u| u.powi(3)).sum::<f64>() / (6.0 * squares.powf(1.5))
+                } else {
+                    0.0
+                };
+
+                let adjust = |p: f64| {
+                    let z = bias + normal.inverse_cdf(p);
+                    normal.cdf(bias + z / (1.0 - acceleration * z))
+                };
+                Ok((
+                    quantile(&sorted, adjust(lower)),
+                    quantile(&sorted, adjust(upper)),
+                ))
+            }
+            BootstrapInterval::Studentized => {
+                let mut pivots = self.pivots().ok_or(StatsError::BadParams)?;
+                if pivots.is_empty() {
+                    return Err(StatsError::BadParams);
+                }
+                pivots.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                let standard_error = jackknife_standard_error(&self.jackknife);
+                Ok((
+                    self.estimate - quantile(&pivots, upper) * standard_error,
+                    self.estimate - quantile(&pivots, lower) * standard_error,
+                ))
+            }
+        }
+    }
+
+    /// Returns the p-value of the bootstrap test of the null hypothesis that the statistic
+    /// equals `null`, comparing the deviation of the estimate from `null` to the deviations of
+    /// the replicates from the estimate (Hall & Wilson, 1991). The deviations are studentized
+    /// if the replicates were.
+    ///
+    /// # References
+    ///
+    /// Hall, P., & Wilson, S. R. (1991). Two Guidelines for Bootstrap Hypothesis Testing.
+    ///     Biometrics, 47(2), 757–762. <https://doi.org/10.2307/2532163>
+    pub fn p_value(&self, null: f64, alternative: AlternativeHypothesis) -> f64 {
+        let (observed, deviations) = match self.pivots() {
+            Some(pivots) => (
+                (self.estimate - null) / jackknife_standard_error(&self.jackknife),
+                pivots,
+            ),
+            None => (
+                self.estimate - null,
+                self.replicates.iter().map(|r| r - self.estimate).collect(),
+            ),
+        };
+
+        let extreme = deviations
+            .iter()
+            .filter(|deviation| match alternative {
+                AlternativeHypothesis::Greater => **deviation >= observed,
+                AlternativeHypothesis::Less => **deviation <= observed,
+                AlternativeHypothesis::Different => deviation.abs() >= observed.abs(),
+            })
+            .count();
+        (extreme + 1) as f64 / (deviations.len() + 1) as f64
+    }
+
+    /// Returns the estimate of the statistic as an effect size of the given `kind`, with a
+    /// confidence interval constructed with the given `method`.
+    pub fn effect_size(
+        &self,
+        kind: EffectSizeKind,
+        method: BootstrapInterval,
+    ) -> Result<EffectSize> {
+        Ok(EffectSize::new(
+            kind,
+            self.estimate,
+            self.interval(method)?,
+            self.confidence_level,
+        ))
+    }
+
+    /// Returns the studentized deviations of the replicates from the estimate, if the
+    /// replicates were studentized.
+    fn pivots(&self) -> Option<Vec<f64>> {
+        let standard_errors = self.standard_errors.as_ref()?;
+        Some(
+            self.replicates
+                .iter()
+                .zip(standard_errors)
+                .map(|(replicate, standard_error)| (replicate - self.estimate) / standard_error)
+                .filter(|pivot| pivot.is_finite())
+                .collect(),
+        )
+    }
+}
+
+/// Returns the quantile `p` of the `sorted` values, interpolating linearly between them.
+fn quantile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let h = (sorted.len() - 1) as f64 * p;
+    let low = h.floor() as usize;
+    let high = h.ceil() as usize;
+    sorted[low] + (h - low as f64) * (sorted[high] - sorted[low])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::BootstrapOptions;
+    use crate::statistics::StatisticsExt;
+    use statrs::statistics::Statistics;
+
+    #[test]
+    fn one_sample() {
+        let x = vec![
+            134.0, 146.0, 104.0, 119.0, 124.0, 161.0, 107.0, 83.0, 113.0, 129.0, 97.0, 123.0,
+        ];
+        let options = BootstrapOptions::default().with_studentized(true);
+        let bootstrap = Bootstrap::one_sample(&x, |x| x.mean(), options).unwrap();
+        assert_eq!(
+            bootstrap.interval(BootstrapInterval::Percentile).unwrap(),
+            (108.66666666666669, 131.5)
+        );
+        assert_eq!(
+            bootstrap.interval(BootstrapInterval::Basic).unwrap(),
+            (108.5, 131.33333333333331)
+        );
+        assert_eq!(
+            bootstrap.interval(BootstrapInterval::BCa).unwrap(),
+            (108.75, 131.5)
+        );
+        assert_eq!(
+            bootstrap.interval(BootstrapInterval::Studentized).unwrap(),
+            (107.26386219500101, 134.8011488730767)
+        );
+        assert_eq!(
+            bootstrap.p_value(100.0, AlternativeHypothesis::Different),
+            0.0079960019990005
+        );
+    }
+
+    #[test]
+    fn undefined_statistic() {
+        let options = BootstrapOptions::default().with_studentized(true);
+        let bootstrap = Bootstrap::one_sample(&[1.0, 2.0, 3.0], |_| f64::NAN, options).unwrap();
+        for method in [
+            BootstrapInterval::Percentile,
+            BootstrapInterval::Basic,
+            BootstrapInterval::BCa,
+            BootstrapInterval::Studentized,
+        ] {
+            assert!(bootstrap.interval(method).is_err());
+        }
+    }
+
+    #[test]
+    fn two_sample() {
+        let x = vec![
+            134.0, 146.0, 104.0, 119.0, 124.0, 161.0, 107.0, 83.0, 113.0, 129.0, 97.0, 123.0,
+        ];
+        let y = vec![70.0, 118.0, 101.0, 85.0, 107.0, 132.0, 94.0];
+        let difference = |x: &[f64], y: &[f64]| x.mean() - y.mean();
+        let bootstrap =
+            Bootstrap::two_sample(&x, &y, difference, BootstrapOptions::default()).unwrap();
+        assert_eq!(
+            bootstrap.interval(BootstrapInterval::BCa).unwrap(),
+            (1.6538665780218977, 37.72590831762516)
+        );
+        assert_eq!(
+            bootstrap.p_value(0.0, AlternativeHypothesis::Greater),
+            0.019490254872563718
+        );
+        assert!(bootstrap.interval(BootstrapInterval::Studentized).is_err());
+
+        let cohens_d = |x: &[f64], y: &[f64]| (x.mean() - y.mean()) / x.pooled_std_dev(y);
+        let bootstrap =
+            Bootstrap::two_sample(&x, &y, cohens_d, BootstrapOptions::default()).unwrap();
+        let effect_size = bootstrap
+            .effect_size(EffectSizeKind::CohensD, BootstrapInterval::Percentile)
+            .unwrap();
+        assert_eq!(effect_size.kind(), EffectSizeKind::CohensD);
+        assert_eq!(effect_size.estimate(), 0.8995574392432595);
+        assert_eq!(
+            effect_size.confidence_interval(),
+            (0.05845534453632483, 2.072912157405956)
+        );
+    }
+}
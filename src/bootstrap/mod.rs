@@ -0,0 +1,162 @@
+//! Defines bootstrap confidence intervals and hypothesis tests for custom statistics.
+
+use crate::effect_size::CONFIDENCE_LEVEL;
+
+mod interval;
+mod resample;
+
+/// Default number of bootstrap resamples.
+static RESAMPLES: usize = 2000;
+/// Default seed of the bootstrap resampling, so that results are reproducible.
+static SEED: u64 = 0x5EED;
+
+/// Options of the bootstrap.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BootstrapOptions {
+    resamples: usize,
+    seed: u64,
+    confidence_level: f64,
+    studentized: bool,
+}
+
+impl Default for BootstrapOptions {
+    fn default() -> BootstrapOptions {
+        BootstrapOptions {
+            resamples: RESAMPLES,
+            seed: SEED,
+            confidence_level: CONFIDENCE_LEVEL,
+            studentized: false,
+        }
+    }
+}
+
+impl BootstrapOptions {
+    /// Sets the number of bootstrap resamples.
+    pub fn with_resamples(self, resamples: usize) -> BootstrapOptions {
+        BootstrapOptions { resamples, ..self }
+    }
+
+    /// Sets the seed of the bootstrap resampling.
+    pub fn with_seed(self, seed: u64) -> BootstrapOptions {
+        BootstrapOptions { seed, ..self }
+    }
+
+    /// Sets the confidence level of the intervals.
+    pub fn with_confidence_level(self, confidence_level: f64) -> BootstrapOptions {
+        BootstrapOptions {
+            confidence_level,
+            ..self
+        }
+    }
+
+    /// Estimates the standard error of every replicate with the jackknife, which is needed for
+    /// bootstrap-t intervals and studentized tests but multiplies the number of evaluations of
+    /// the statistic by the sample size.
+    pub fn with_studentized(self, studentized: bool) -> BootstrapOptions {
+        BootstrapOptions {
+            studentized,
+            ..self
+        }
+    }
+
+    /// Returns the number of bootstrap resamples.
+    pub fn resamples(&self) -> usize {
+        self.resamples
+    }
+
+    /// Returns the seed of the bootstrap resampling.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the confidence level of the intervals.
+    pub fn confidence_level(&self) -> f64 {
+        self.confidence_level
+    }
+
+    /// Returns whether the replicates are studentized.
+    pub fn studentized(&self) -> bool {
+        self.studentized
+    }
+}
+
+/// Method of constructing a bootstrap confidence interval.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BootstrapInterval {
+    /// Quantiles of the bootstrap replicates.
+    Percentile,
+    /// Quantiles of the replicates reflected around the estimate, also known as the pivotal
+    /// interval.
+    Basic,
+    /// Bias-corrected and accelerated percentile interval.
+    BCa,
+    /// Bootstrap-t interval from the quantiles of the studentized replicates.
+    Studentized,
+}
+
+/// Implements the [bootstrap](https://en.wikipedia.org/wiki/Bootstrapping_(statistics)) of a
+/// custom statistic (Efron & Tibshirani, 1993).
+///
+/// # References
+///
+/// Efron, B., & Tibshirani, R. J. (1993). An Introduction to the Bootstrap. Chapman and Hall/CRC.
+///     <https://doi.org/10.1201/9780429246593>
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bootstrap {
+    estimate: f64,
+    replicates: Vec<f64>,
+    jackknife: Vec<(usize, f64)>,
+    standard_errors: Option<Vec<f64>>,
+    confidence_level: f64,
+}
+
+impl Bootstrap {
+    /// Returns the estimate of the statistic on the original samples.
+    pub fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    /// Returns the bootstrap replicates of the statistic, without those that are `NaN`.
+    pub fn replicates(&self) -> &[f64] {
+        &self.replicates
+    }
+
+    /// Returns the bootstrap estimate of the standard error of the statistic.
+    pub fn standard_error(&self) -> f64 {
+        let n = self.replicates.len() as f64;
+        let mean = self.replicates.iter().sum::<f64>() / n;
+        let squares = self
+            .replicates
+            .iter()
+            .map(|r| (r - mean).powi(2))
+            .sum::<f64>();
+        (squares / (n - 1.0)).sqrt()
+    }
+
+    /// Returns the bootstrap estimate of the bias of the statistic.
+    pub fn bias(&self) -> f64 {
+        self.replicates.iter().sum::<f64>() / self.replicates.len() as f64 - self.estimate
+    }
+
+    /// Returns the confidence level of the intervals.
+    pub fn confidence_level(&self) -> f64 {
+        self.confidence_level
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use statrs::statistics::Statistics;
+
+    #[test]
+    fn standard_error() {
+        let x = vec![8.0, 6.0, 5.5, 11.0, 8.5, 5.0, 6.0, 6.0];
+        let bootstrap =
+            Bootstrap::one_sample(&x, |x| x.mean(), BootstrapOptions::default()).unwrap();
+        assert_eq!(bootstrap.estimate(), 7.0);
+        assert_eq!(bootstrap.replicates().len(), 2000);
+        assert_eq!(bootstrap.standard_error(), 0.6527786143361837);
+        assert_eq!(bootstrap.bias(), -0.003874999999999851);
+    }
+}
@@ -0,0 +1,262 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use statrs::{Result, StatsError};
+
+use super::{Bootstrap, BootstrapOptions};
+
+/// How the samples are resampled.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Design {
+    /// Every sample is resampled separately.
+    Independent,
+    /// The samples are resampled together, keeping the observations in pairs.
+    Paired,
+}
+
+impl Bootstrap {
+    /// Bootstrap the `statistic` of the sample `x`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::Statistics;
+    /// use stattest::bootstrap::{Bootstrap, BootstrapInterval, BootstrapOptions};
+    ///
+    /// let x = vec![8.0, 6.0, 5.5, 11.0, 8.5, 5.0, 6.0, 6.0];
+    /// let bootstrap = Bootstrap::one_sample(&x, |x| x.mean(), BootstrapOptions::default()).unwrap();
+    /// let interval = bootstrap.interval(BootstrapInterval::BCa);
+    /// assert!(interval.is_ok());
+    /// ```
+    pub fn one_sample<F>(x: &[f64], statistic: F, options: BootstrapOptions) -> Result<Bootstrap>
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        Bootstrap::new(
+            &[x],
+            Design::Independent,
+            |samples| statistic(samples[0]),
+            options,
+        )
+    }
+
+    /// Bootstrap the `statistic` of the independent samples `x` and `y`, resampling each of
+    /// them separately.
+    pub fn two_sample<F>(
+        x: &[f64],
+        y: &[f64],
+        statistic: F,
+        options: BootstrapOptions,
+    ) -> Result<Bootstrap>
+    where
+        F: Fn(&[f64], &[f64]) -> f64,
+    {
+        Bootstrap::new(
+            &[x, y],
+            Design::Independent,
+            |samples| statistic(samples[0], samples[1]),
+            options,
+        )
+    }
+
+    /// Bootstrap the `statistic` of the paired samples `x` and `y`, resampling the pairs.
+    pub fn paired<F>(
+        x: &[f64],
+        y: &[f64],
+        statistic: F,
+        options: BootstrapOptions,
+    ) -> Result<Bootstrap>
+    where
+        F: Fn(&[f64], &[f64]) -> f64,
+    {
+        if x.len() != y.len() {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+        Bootstrap::new(
+            &[x, y],
+            Design::Paired,
+            |samples| statistic(samples[0], samples[1]),
+            options,
+        )
+    }
+
+    fn new<F>(
+        samples: &[&[f64]],
+        design: Design,
+        statistic: F,
+        options: BootstrapOptions,
+    ) -> Result<Bootstrap>
+    where
+        F: Fn(&[&[f64]]) -> f64,
+    {
+        if !(0.0..1.0).contains(&options.confidence_level)
+            || options.resamples == 0
+            || samples.iter().any(|sample| sample.is_empty())
+        {
+            return Err(StatsError::BadParams);
+        }
+
+        let mut rng = StdRng::seed_from_u64(options.seed);
+        let mut resamples: Vec<Vec<f64>> = samples.iter().map(|sample| sample.to_vec()).collect();
+        let mut replicates = Vec::with_capacity(options.resamples);
+        let mut standard_errors = Vec::with_capacity(options.resamples);
+
+        for _ in 0..options.resamples {
+            match design {
+                Design::Independent => {
+                    for (sample, resample) in samples.iter().zip(resamples.iter_mut()) {
+                        for value in resample.iter_mut() {
+                            *value = sample[rng.gen_range(0..sample.len())];
+                        }
+                    }
+                }
+                Design::Paired => {
+                    for i in 0..samples[0].len() {
+                        let index = rng.gen_range(0..samples[0].len());
+                        for (sample, resample) in samples.iter().zip(resamples.iter_mut()) {
+                            resample[i] = sample[index];
+                        }
+                    }
+                }
+            }
+
+            let slices: Vec<&[f64]> = resamples
+                .iter()
+                .map(|resample| resample.as_slice())
+                .collect();
+            let replicate = statistic(&slices);
+            if replicate.is_nan() {
+                continue;
+            }
+            replicates.push(replicate);
+            if options.studentized {
+                let jackknife = jackknife(&slices, design, &statistic);
+                standard_errors.push(jackknife_standard_error(&jackknife));
+            }
+        }
+
+        Ok(Bootstrap {
+            estimate: statistic(samples),
+            replicates,
+            jackknife: jackknife(samples, design, &statistic),
+            standard_errors: if options.studentized {
+                Some(standard_errors)
+            } else {
+                None
+            },
+            confidence_level: options.confidence_level,
+        })
+    }
+}
+
+/// Returns the leave-one-out estimates of the `statistic`, each with the sample that the
+/// observation was left out of. Paired observations are left out together.
+fn jackknife<F>(samples: &[&[f64]], design: Design, statistic: &F) -> Vec<(usize, f64)>
+where
+    F: Fn(&[&[f64]]) -> f64,
+{
+    let leave_out = |sample: &[f64], i: usize| -> Vec<f64> {
+        sample[..i]
+            .iter()
+            .chain(&sample[i + 1..])
+            .copied()
+            .collect()
+    };
+
+    match design {
+        Design::Independent => samples
+            .iter()
+            .enumerate()
+            .flat_map(|(group, sample)| {
+                (0..sample.len()).map(move |i| {
+                    let left_out = leave_out(sample, i);
+                    let mut slices = samples.to_vec();
+                    slices[group] = &left_out;
+                    (group, statistic(&slices))
+                })
+            })
+            .collect(),
+        Design::Paired => (0..samples[0].len())
+            .map(|i| {
+                let left_out: Vec<Vec<f64>> =
+                    samples.iter().map(|sample| leave_out(sample, i)).collect();
+                let slices: Vec<&[f64]> = left_out.iter().map(|sample| sample.as_slice()).collect();
+                (0, statistic(&slices))
+            })
+            .collect(),
+    }
+}
+
+/// Returns the influence of every observation estimated with the `jackknife`, as the
+/// deviation from the mean of the leave-one-out estimates of its sample, scaled by the size
+/// of the sample minus one.
+pub(super) fn jackknife_influence(jackknife: &[(usize, f64)]) -> Vec<f64> {
+    let groups = jackknife
+        .iter()
+        .map(|(group, _)| group + 1)
+        .max()
+        .unwrap_or(0);
+    let mut sums = vec![(0.0, 0.0); groups];
+    for (group, value) in jackknife {
+        sums[*group].0 += value;
+        sums[*group].1 += 1.0;
+    }
+    jackknife
+        .iter()
+        .map(|(group, value)| {
+            let (sum, n) = sums[*group];
+            (n - 1.0) * (sum / n - value)
+        })
+        .collect()
+}
+
+/// Returns the jackknife estimate of the standard error from the leave-one-out estimates.
+pub(super) fn jackknife_standard_error(jackknife: &[(usize, f64)]) -> f64 {
+    let groups = jackknife
+        .iter()
+        .map(|(group, _)| group + 1)
+        .max()
+        .unwrap_or(0);
+    let mut sizes = vec![0.0; groups];
+    for (group, _) in jackknife {
+        sizes[*group] += 1.0;
+    }
+    // The influence is (n - 1) times the deviation, and the variance (n - 1) / n times the sum
+    // of the squared deviations.
+    jackknife_influence(jackknife)
+        .iter()
+        .zip(jackknife)
+        .map(|(influence, (group, _))| {
+            let n = sizes[*group];
+            influence * influence / (n * (n - 1.0))
+        })
+        .sum::<f64>()
+        .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use statrs::statistics::Statistics;
+
+    #[test]
+    fn jackknife_standard_error() {
+        // The jackknife standard error of the mean is the usual standard error.
+        let x = vec![8.0, 6.0, 5.5, 11.0, 8.5, 5.0, 6.0, 6.0];
+        let jackknife = jackknife(&[&x], Design::Independent, &|samples: &[&[f64]]| {
+            samples[0].mean()
+        });
+        let standard_error = super::jackknife_standard_error(&jackknife);
+        assert!((standard_error - (&x).std_dev() / 8_f64.sqrt()).abs() < 1E-12);
+    }
+
+    #[test]
+    fn paired() {
+        let x = vec![8.0, 6.0, 5.5, 11.0, 8.5, 5.0, 6.0, 6.0];
+        let y = vec![8.5, 9.0, 6.5, 10.5, 9.0, 7.0, 6.5, 7.0];
+        let difference = |x: &[f64], y: &[f64]| x.mean() - y.mean();
+        let bootstrap = Bootstrap::paired(&x, &y, difference, BootstrapOptions::default()).unwrap();
+        assert_eq!(bootstrap.estimate(), -1.0);
+        assert_eq!(bootstrap.standard_error(), 0.35334847717679724);
+        assert!(Bootstrap::paired(&x, &y[1..], difference, BootstrapOptions::default()).is_err());
+    }
+}
@@ -1,7 +1,20 @@
 //! Defines interfaces for creating and approximating statistical distributions.
 
+pub use self::noncentral_chi_squared::*;
+pub use self::noncentral_fisher_snedecor::*;
+pub use self::noncentral_students_t::*;
 pub use self::shapiro_wilk::*;
 pub use self::signed_rank::*;
+pub use self::studentized_range::*;
 
+pub(crate) use self::quantile::*;
+pub(crate) use self::roots::*;
+
+mod noncentral_chi_squared;
+mod noncentral_fisher_snedecor;
+mod noncentral_students_t;
+mod quantile;
+mod roots;
 mod shapiro_wilk;
 mod signed_rank;
+mod studentized_range;
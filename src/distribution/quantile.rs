@@ -0,0 +1,35 @@
+//! Accurate quantile functions for distributions of which [statrs] only provides an
+//! approximate `inverse_cdf` by bisection.
+
+use statrs::function::beta::inv_beta_reg;
+
+/// Returns the quantile of the beta distribution with shape parameters `a` and `b`.
+pub(crate) fn beta_inverse_cdf(a: f64, b: f64, p: f64) -> f64 {
+    if p <= 0.0 {
+        0.0
+    } else if p >= 1.0 {
+        1.0
+    } else {
+        inv_beta_reg(a, b, p)
+    }
+}
+
+/// Returns the quantile of the F-distribution with `freedom_1` and `freedom_2` degrees of freedom.
+pub(crate) fn fisher_snedecor_inverse_cdf(freedom_1: f64, freedom_2: f64, p: f64) -> f64 {
+    let x = beta_inverse_cdf(freedom_1 / 2.0, freedom_2 / 2.0, p);
+    freedom_2 * x / (freedom_1 * (1.0 - x))
+}
+
+#[cfg(test)]
+mod tests {
+    use statrs::distribution::{ContinuousCDF, FisherSnedecor};
+
+    #[test]
+    fn fisher_snedecor() {
+        let distribution = FisherSnedecor::new(11.0, 6.0).unwrap();
+        for p in [1E-6, 0.025, 0.5, 0.975] {
+            let x = super::fisher_snedecor_inverse_cdf(11.0, 6.0, p);
+            assert!((distribution.cdf(x) - p).abs() < 1E-12);
+        }
+    }
+}
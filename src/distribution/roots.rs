@@ -0,0 +1,146 @@
+use std::f64;
+
+static TOLERANCE: f64 = 1E-12;
+static MAX_ITERATIONS: usize = 200;
+
+/// Find the value `x` for which the non-decreasing function `f` equals `target`, given
+/// the bounds of its domain. Infinite bounds are replaced by a bracket around `start`
+/// that is widened until it contains the solution.
+pub(crate) fn invert<F>(f: F, target: f64, min: f64, max: f64, start: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    let g = |x: f64| f(x) - target;
+
+    let mut step = 1.0_f64.max(start.abs());
+    let mut low = if min.is_finite() { min } else { start - step };
+    while !min.is_finite() && g(low) > 0.0 {
+        step *= 2.0;
+        low = start - step;
+        if !low.is_finite() {
+            return min;
+        }
+    }
+
+    step = 1.0_f64.max(start.abs());
+    let mut high = if max.is_finite() {
+        max
+    } else {
+        start.max(low) + step
+    };
+    while !max.is_finite() && g(high) < 0.0 {
+        step *= 2.0;
+        high = start.max(low) + step;
+        if !high.is_finite() {
+            return max;
+        }
+    }
+
+    brent(g, low, high)
+}
+
+/// Find a root of `f` in the bracket `[a, b]` with Brent's method (Brent, 1973).
+///
+/// # References
+///
+/// Brent, R. P. (1973). Algorithms for Minimization without Derivatives.
+///     Prentice-Hall. Chapter 4.
+pub(crate) fn brent<F>(f: F, a: f64, b: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    let (mut a, mut b) = (a, b);
+    let (mut fa, mut fb) = (f(a), f(b));
+
+    if fa == 0.0 {
+        return a;
+    } else if fb == 0.0 {
+        return b;
+    } else if fa.signum() == fb.signum() {
+        // No sign change; return the end closest to a root.
+        return if fa.abs() < fb.abs() { a } else { b };
+    }
+
+    let (mut c, mut fc) = (a, fa);
+    let mut d = b - a;
+    let mut e = d;
+
+    for _ in 0..MAX_ITERATIONS {
+        if fb.signum() == fc.signum() {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+
+        let tolerance = 2.0 * f64::EPSILON * b.abs() + 0.5 * TOLERANCE;
+        let m = 0.5 * (c - b);
+        if m.abs() <= tolerance || fb == 0.0 {
+            break;
+        }
+
+        if e.abs() >= tolerance && fa.abs() > fb.abs() {
+            // Attempt inverse quadratic interpolation or the secant method.
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0 * m * s, 1.0 - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2.0 * m * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            } else {
+                p = -p;
+            }
+            if 2.0 * p < (3.0 * m * q - (tolerance * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = m;
+                e = m;
+            }
+        } else {
+            d = m;
+            e = m;
+        }
+
+        a = b;
+        fa = fb;
+        b += if d.abs() > tolerance {
+            d
+        } else {
+            tolerance.copysign(m)
+        };
+        fb = f(b);
+    }
+
+    b
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn brent() {
+        let root = super::brent(|x| x * x - 2.0, 0.0, 2.0);
+        assert!((root - 2_f64.sqrt()).abs() < 1E-12);
+    }
+
+    #[test]
+    fn invert() {
+        let x = super::invert(|x| x.powi(3), 27.0, f64::NEG_INFINITY, f64::INFINITY, 0.0);
+        assert!((x - 3.0).abs() < 1E-10);
+    }
+}
@@ -0,0 +1,208 @@
+use crate::distribution::{invert, NoncentralChiSquared};
+use crate::statistics::ContingencyTable;
+use statrs::distribution::ContinuousCDF;
+use statrs::{Result, StatsError};
+
+use super::rank::fisher_interval;
+use super::{EffectSize, EffectSizeKind};
+
+impl EffectSize {
+    /// Calculate Cramér's V of the association between the rows and columns of the `table`,
+    /// with a confidence interval at `confidence_level` based on the noncentral chi-squared
+    /// distribution (Smithson, 2003).
+    ///
+    /// # References
+    ///
+    /// Smithson, M. (2003). Noncentral Chi-Square and F Distributions. In Confidence Intervals
+    ///     (pp. 39–55). SAGE Publications. <https://doi.org/10.4135/9781412983761.n4>
+    pub fn cramers_v(table: &ContingencyTable, confidence_level: f64) -> Result<EffectSize> {
+        let scale = (table.rows().min(table.columns()) - 1) as f64;
+        let df = ((table.rows() - 1) * (table.columns() - 1)) as f64;
+        EffectSize::from_chi_squared(
+            EffectSizeKind::CramersV,
+            pearson_chi_squared(table)?,
+            df,
+            table.total() as f64 * scale,
+            confidence_level,
+        )
+    }
+
+    /// Calculate the phi coefficient of the association between the rows and columns of the
+    /// 2×2 `table`, which is positive when the counts concentrate on the main diagonal, with a
+    /// confidence interval at `confidence_level` from the Fisher z-transformation with the
+    /// standard error 1/√(n − 3) of a Pearson correlation (Fisher, 1921). Unlike the interval of
+    /// Cramér's V, it can include zero and effects of the opposite sign.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the table is not 2×2, if a row or column has no counts, or if the
+    /// table has fewer than four counts.
+    ///
+    /// # References
+    ///
+    /// Fisher, R. A. (1921). On the "probable error" of a coefficient of correlation deduced
+    ///     from a small sample. Metron, 1, 3–32.
+    pub fn phi(table: &ContingencyTable, confidence_level: f64) -> Result<EffectSize> {
+        if table.rows() != 2 || table.columns() != 2 || table.total() < 4 {
+            return Err(StatsError::BadParams);
+        }
+
+        let (a, b) = (table.get(0, 0) as f64, table.get(0, 1) as f64);
+        let (c, d) = (table.get(1, 0) as f64, table.get(1, 1) as f64);
+        let margins = (a + b) * (c + d) * (a + c) * (b + d);
+        if margins == 0.0 {
+            return Err(StatsError::BadParams);
+        }
+
+        let estimate = (a * d - b * c) / margins.sqrt();
+        let standard_error = (table.total() as f64 - 3.0).sqrt().recip();
+        Ok(EffectSize::new(
+            EffectSizeKind::Phi,
+            estimate,
+            fisher_interval(estimate, standard_error, confidence_level)?,
+            confidence_level,
+        ))
+    }
+
+    /// Calculate Cohen's w of the deviation of `observed` counts from the given `expected`
+    /// counts, with a confidence interval at `confidence_level` based on the noncentral
+    /// chi-squared distribution.
+    pub fn cohens_w(
+        observed: &[usize],
+        expected: &[f64],
+        confidence_level: f64,
+    ) -> Result<EffectSize> {
+        if observed.len() != expected.len() {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+        if observed.len() < 2 {
+            return Err(StatsError::BadParams);
+        }
+
+        let chi_squared = observed
+            .iter()
+            .zip(expected)
+            .map(|(o, e)| (*o as f64 - e).powi(2) / e)
+            .sum();
+        EffectSize::from_chi_squared(
+            EffectSizeKind::CohensW,
+            chi_squared,
+            (observed.len() - 1) as f64,
+            observed.iter().sum::<usize>() as f64,
+            confidence_level,
+        )
+    }
+
+    /// Returns the effect size `sqrt(chi_squared / scale)`, with the confidence interval of
+    /// the noncentrality parameter on the same scale.
+    pub(crate) fn from_chi_squared(
+        kind: EffectSizeKind,
+        chi_squared: f64,
+        df: f64,
+        scale: f64,
+        confidence_level: f64,
+    ) -> Result<EffectSize> {
+        if scale <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        let (lower, upper) = noncentral_chi_squared_interval(chi_squared, df, confidence_level)?;
+
+        Ok(EffectSize::new(
+            kind,
+            (chi_squared / scale).sqrt(),
+            ((lower / scale).sqrt(), (upper / scale).sqrt()),
+            confidence_level,
+        ))
+    }
+}
+
+/// Returns Pearson's chi-squared statistic of independence of the rows and columns of the
+/// `table`.
+fn pearson_chi_squared(table: &ContingencyTable) -> Result<f64> {
+    let expected = table.expected();
+    if expected.iter().flatten().any(|e| *e <= 0.0) {
+        return Err(StatsError::BadParams);
+    }
+    Ok(table
+        .counts()
+        .iter()
+        .flatten()
+        .zip(expected.iter().flatten())
+        .map(|(o, e)| (*o as f64 - e).powi(2) / e)
+        .sum())
+}
+
+/// Returns the confidence interval at `confidence_level` of the noncentrality parameter of a
+/// chi-squared distribution with `df` degrees of freedom, given an observed statistic
+/// `chi_squared`.
+fn noncentral_chi_squared_interval(
+    chi_squared: f64,
+    df: f64,
+    confidence_level: f64,
+) -> Result<(f64, f64)> {
+    if !(0.0..1.0).contains(&confidence_level) || df.is_nan() || df <= 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    if !chi_squared.is_finite() || chi_squared < 0.0 {
+        return Err(StatsError::BadParams);
+    }
+
+    let alpha = 1.0 - confidence_level;
+    let survival = |ncp: f64| NoncentralChiSquared::new(df, ncp).unwrap().sf(chi_squared);
+    let bound = |p: f64| {
+        if survival(0.0) >= p {
+            0.0
+        } else {
+            invert(survival, p, 0.0, f64::INFINITY, chi_squared)
+        }
+    };
+
+    Ok((bound(alpha / 2.0), bound(1.0 - alpha / 2.0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cramers_v() {
+        let table = ContingencyTable::new(vec![vec![20, 15, 5], vec![10, 25, 25]]).unwrap();
+        let v = EffectSize::cramers_v(&table, 0.95).unwrap();
+        assert_eq!(v.kind(), EffectSizeKind::CramersV);
+        assert_eq!(v.estimate(), 0.39747466725706065);
+        assert_eq!(
+            v.confidence_interval(),
+            (0.1829979391323701, 0.5830688548127424)
+        );
+    }
+
+    #[test]
+    fn phi() {
+        let table = ContingencyTable::new(vec![vec![3, 12], vec![9, 4]]).unwrap();
+        let phi = EffectSize::phi(&table, 0.95).unwrap();
+        assert_eq!(phi.estimate(), -0.49613893835683387);
+        assert_eq!(
+            phi.confidence_interval(),
+            (-0.7334548479153562, -0.15101448816642435)
+        );
+
+        // A small positive association has an interval that reaches below zero.
+        let table = ContingencyTable::new(vec![vec![12, 9], vec![8, 11]]).unwrap();
+        let phi = EffectSize::phi(&table, 0.95).unwrap();
+        assert_eq!(phi.estimate(), 0.15018785229652767);
+        assert_eq!(
+            phi.confidence_interval(),
+            (-0.1692393459573966, 0.44106214410517475)
+        );
+        assert!(EffectSize::phi(&table, 1.5).is_err());
+        let table = ContingencyTable::new(vec![vec![0, 0], vec![9, 4]]).unwrap();
+        assert!(EffectSize::phi(&table, 0.95).is_err());
+    }
+
+    #[test]
+    fn cohens_w() {
+        let w = EffectSize::cohens_w(&[30, 20, 50], &[25.0, 25.0, 50.0], 0.95).unwrap();
+        assert_eq!(w.estimate(), 0.1414213562373095);
+        assert_eq!(w.confidence_interval(), (0.0, 0.3131783937065309));
+    }
+}
@@ -0,0 +1,246 @@
+use statrs::distribution::{ContinuousCDF, Normal};
+use statrs::{Result, StatsError};
+use std::f64::consts::{PI, SQRT_2};
+
+use super::standardized::hedges_correction;
+use super::{EffectSize, EffectSizeKind};
+
+impl EffectSize {
+    /// Convert the effect size, including its confidence interval, to another `kind` of
+    /// effect size (Borenstein et al., 2009; Ruscio, 2008).
+    ///
+    /// Conversions go through Cohen's d and assume normally distributed outcomes with equal
+    /// variances. Where the formulas depend on the sizes of the groups, the sample sizes of the
+    /// effect size are used, and groups of equal size are assumed if those are not known.
+    /// Glass's delta, variance ratios, proportions, risk differences, risk ratios, rates, trends in
+    /// proportions and effect sizes of categorical data cannot be converted. Neither can effect
+    /// sizes of paired samples, like those of [`EffectSize::cohens_d_paired`] and
+    /// [`EffectSize::rank_biserial_paired`], because the formulas are those of two independent
+    /// groups.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the kinds cannot be converted, if the effect size is paired, or if
+    /// the estimate or a bound of its interval is NaN, infinite or outside the range of the
+    /// kind, such as a probability of superiority above one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::effect_size::{EffectSize, EffectSizeKind};
+    ///
+    /// let d = EffectSize::new(EffectSizeKind::CohensD, 0.5, (0.1, 0.9), 0.95);
+    /// let r = d.convert(EffectSizeKind::PointBiserial);
+    /// assert!(r.is_ok());
+    /// ```
+    ///
+    /// # References
+    ///
+    /// Borenstein, M., Hedges, L. V., Higgins, J. P. T., & Rothstein, H. R. (2009). Converting
+    ///     Among Effect Sizes. In Introduction to Meta-Analysis (pp. 45–49). John Wiley & Sons.
+    ///     <https://doi.org/10.1002/9780470743386.ch7>
+    ///
+    /// Ruscio, J. (2008). A probability-based measure of effect size: Robustness to base rates
+    ///     and other factors. Psychological Methods, 13(1), 19–30.
+    ///     <https://doi.org/10.1037/1082-989X.13.1.19>
+    pub fn convert(&self, kind: EffectSizeKind) -> Result<EffectSize> {
+        if kind == self.kind {
+            return Ok(*self);
+        }
+        if self.paired {
+            return Err(StatsError::BadParams);
+        }
+
+        let convert = |value: f64, kind: EffectSizeKind| {
+            self.value_to_d(value)
+                .and_then(|d| self.d_to_value(d, kind))
+        };
+
+        let estimate = convert(self.estimate, kind)?;
+        let (lower, upper) = self.confidence_interval;
+        let confidence_interval = if kind == EffectSizeKind::EtaSquared {
+            // Squaring is not monotone, so convert the interval of the correlation instead.
+            let lower = convert(lower, EffectSizeKind::PointBiserial)?;
+            let upper = convert(upper, EffectSizeKind::PointBiserial)?;
+            let squares = (lower.powi(2), upper.powi(2));
+            if lower <= 0.0 && upper >= 0.0 {
+                (0.0, squares.0.max(squares.1))
+            } else {
+                (squares.0.min(squares.1), squares.0.max(squares.1))
+            }
+        } else {
+            let (lower, upper) = (convert(lower, kind)?, convert(upper, kind)?);
+            (lower.min(upper), lower.max(upper))
+        };
+
+        Ok(EffectSize {
+            kind,
+            estimate,
+            confidence_interval,
+            ..*self
+        })
+    }
+
+    /// Returns the factor `(n_x + n_y)^2 / (n_x * n_y)` relating Cohen's d to the point-biserial
+    /// correlation, which is 4 for groups of equal size.
+    fn group_size_factor(&self) -> f64 {
+        match self.sample_sizes {
+            Some((n_x, n_y)) => {
+                let (n_x, n_y) = (n_x as f64, n_y as f64);
+                (n_x + n_y).powi(2) / (n_x * n_y)
+            }
+            None => 4.0,
+        }
+    }
+
+    fn hedges_correction(&self) -> Result<f64> {
+        match self.sample_sizes {
+            Some((n_x, n_y)) if n_x + n_y > 3 => Ok(hedges_correction((n_x + n_y - 2) as f64)),
+            _ => Err(StatsError::BadParams),
+        }
+    }
+
+    fn value_to_d(&self, value: f64) -> Result<f64> {
+        let range = match self.kind {
+            EffectSizeKind::PointBiserial
+            | EffectSizeKind::CliffsDelta
+            | EffectSizeKind::RankBiserial => -1.0..=1.0,
+            EffectSizeKind::EtaSquared
+            | EffectSizeKind::ProbabilityOfSuperiority
+            | EffectSizeKind::VarghaDelaneyA => 0.0..=1.0,
+            EffectSizeKind::OddsRatio => 0.0..=f64::MAX,
+            _ => f64::MIN..=f64::MAX,
+        };
+        if !range.contains(&value) {
+            return Err(StatsError::BadParams);
+        }
+
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        match self.kind {
+            EffectSizeKind::CohensD => Ok(value),
+            EffectSizeKind::HedgesG => Ok(value / self.hedges_correction()?),
+            EffectSizeKind::PointBiserial => {
+                Ok(self.group_size_factor().sqrt() * value / (1.0 - value * value).sqrt())
+            }
+            EffectSizeKind::EtaSquared => {
+                let r = value.sqrt();
+                Ok(self.group_size_factor().sqrt() * r / (1.0 - value).sqrt())
+            }
+            EffectSizeKind::LogOddsRatio => Ok(value * 3_f64.sqrt() / PI),
+            EffectSizeKind::OddsRatio => Ok(value.ln() * 3_f64.sqrt() / PI),
+            EffectSizeKind::ProbabilityOfSuperiority | EffectSizeKind::VarghaDelaneyA => {
+                Ok(SQRT_2 * normal.inverse_cdf(value))
+            }
+            EffectSizeKind::CliffsDelta | EffectSizeKind::RankBiserial => {
+                Ok(SQRT_2 * normal.inverse_cdf((value + 1.0) / 2.0))
+            }
+            EffectSizeKind::GlassDelta
+            | EffectSizeKind::VarianceRatio
+            | EffectSizeKind::CramersV
+            | EffectSizeKind::Phi
+            | EffectSizeKind::CohensW
+            | EffectSizeKind::Proportion
+            | EffectSizeKind::RiskDifference
+            | EffectSizeKind::RiskRatio
+            | EffectSizeKind::Rate
+            | EffectSizeKind::RateRatio
+            | EffectSizeKind::ProportionTrend => Err(StatsError::BadParams),
+        }
+    }
+
+    fn d_to_value(&self, d: f64, kind: EffectSizeKind) -> Result<f64> {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        match kind {
+            EffectSizeKind::CohensD => Ok(d),
+            EffectSizeKind::HedgesG => Ok(d * self.hedges_correction()?),
+            EffectSizeKind::PointBiserial => Ok(d / (d * d + self.group_size_factor()).sqrt()),
+            EffectSizeKind::EtaSquared => Ok(d * d / (d * d + self.group_size_factor())),
+            EffectSizeKind::LogOddsRatio => Ok(d * PI / 3_f64.sqrt()),
+            EffectSizeKind::OddsRatio => Ok((d * PI / 3_f64.sqrt()).exp()),
+            EffectSizeKind::ProbabilityOfSuperiority | EffectSizeKind::VarghaDelaneyA => {
+                Ok(normal.cdf(d / SQRT_2))
+            }
+            EffectSizeKind::CliffsDelta | EffectSizeKind::RankBiserial => {
+                Ok(2.0 * normal.cdf(d / SQRT_2) - 1.0)
+            }
+            EffectSizeKind::GlassDelta
+            | EffectSizeKind::VarianceRatio
+            | EffectSizeKind::CramersV
+            | EffectSizeKind::Phi
+            | EffectSizeKind::CohensW
+            | EffectSizeKind::Proportion
+            | EffectSizeKind::RiskDifference
+            | EffectSizeKind::RiskRatio
+            | EffectSizeKind::Rate
+            | EffectSizeKind::RateRatio
+            | EffectSizeKind::ProportionTrend => Err(StatsError::BadParams),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cohens_d() {
+        let d = EffectSize::new(EffectSizeKind::CohensD, 0.5, (-0.1, 1.1), 0.95);
+        let r = d.convert(EffectSizeKind::PointBiserial).unwrap();
+        assert_eq!(r.estimate(), 0.24253562503633297);
+        assert_eq!(
+            r.confidence_interval(),
+            (-0.04993761694389223, 0.48191874977215593)
+        );
+        let eta_squared = d.convert(EffectSizeKind::EtaSquared).unwrap();
+        assert_eq!(eta_squared.estimate(), 0.058823529411764705);
+        assert_eq!(
+            eta_squared.confidence_interval(),
+            (0.0, 0.23224568138195784)
+        );
+        let log_odds_ratio = d.convert(EffectSizeKind::LogOddsRatio).unwrap();
+        assert_eq!(log_odds_ratio.estimate(), 0.9068996821171089);
+        let auc = d.convert(EffectSizeKind::ProbabilityOfSuperiority).unwrap();
+        assert_eq!(auc.estimate(), 0.6381631950841185);
+    }
+
+    #[test]
+    fn sample_sizes() {
+        let d = EffectSize::new(EffectSizeKind::CohensD, 0.5, (-0.1, 1.1), 0.95)
+            .with_sample_sizes(10, 30);
+        let r = d.convert(EffectSizeKind::PointBiserial).unwrap();
+        assert_eq!(r.estimate(), 0.2116036847575795);
+        let g = d.convert(EffectSizeKind::HedgesG).unwrap();
+        assert_eq!(g.estimate(), 0.4900552010654494);
+    }
+
+    #[test]
+    fn round_trip() {
+        let auc = EffectSize::new(
+            EffectSizeKind::ProbabilityOfSuperiority,
+            0.7,
+            (0.6, 0.8),
+            0.95,
+        );
+        let d = auc.convert(EffectSizeKind::CohensD).unwrap();
+        let back = d.convert(EffectSizeKind::ProbabilityOfSuperiority).unwrap();
+        assert!((back.estimate() - 0.7).abs() < 1E-12);
+        assert!(auc.convert(EffectSizeKind::VarianceRatio).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        let a = EffectSize::new(EffectSizeKind::VarghaDelaneyA, 1.2, (0.9, 1.3), 0.95);
+        assert!(a.convert(EffectSizeKind::CohensD).is_err());
+        let delta = EffectSize::new(EffectSizeKind::CliffsDelta, f64::NAN, (-0.5, 0.5), 0.95);
+        assert!(delta.convert(EffectSizeKind::CohensD).is_err());
+        let d = EffectSize::new(EffectSizeKind::CohensD, 0.5, (0.1, f64::INFINITY), 0.95);
+        assert!(d.convert(EffectSizeKind::PointBiserial).is_err());
+
+        let x = vec![8.0, 6.0, 5.5, 11.0, 8.5, 5.0, 6.0, 6.0];
+        let y = vec![8.5, 9.0, 6.5, 10.5, 9.0, 7.0, 6.5, 7.0];
+        let d = EffectSize::cohens_d_paired(&x, &y, 0.95).unwrap();
+        assert!(d.convert(EffectSizeKind::PointBiserial).is_err());
+        let r = EffectSize::rank_biserial_paired_fisher(&x, &y, 0.95).unwrap();
+        assert!(r.convert(EffectSizeKind::CohensD).is_err());
+    }
+}
@@ -0,0 +1,143 @@
+//! Defines effect sizes and their confidence intervals.
+
+pub(crate) use self::rank::dominance;
+pub(crate) use self::standardized::noncentral_t_interval;
+
+mod association;
+mod conversion;
+mod rank;
+mod standardized;
+mod variance;
+
+/// Confidence level of the effect sizes reported by statistical tests.
+pub(crate) static CONFIDENCE_LEVEL: f64 = 0.95;
+
+/// Kind of effect size.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EffectSizeKind {
+    /// Standardized mean difference, using the pooled standard deviation for independent
+    /// samples and the standard deviation of the differences for paired samples.
+    CohensD,
+    /// Cohen's d with a correction for small-sample bias.
+    HedgesG,
+    /// Mean difference standardized by the standard deviation of the control group.
+    GlassDelta,
+    /// Rank-biserial correlation.
+    RankBiserial,
+    /// Probability that an observation from one group is larger than an observation from the
+    /// other, minus the reverse.
+    CliffsDelta,
+    /// Probability that an observation from one group is larger than an observation from the
+    /// other, counting ties as half.
+    VarghaDelaneyA,
+    /// Ratio of two variances.
+    VarianceRatio,
+    /// Point-biserial correlation between group membership and the outcome.
+    PointBiserial,
+    /// Natural logarithm of the odds ratio.
+    LogOddsRatio,
+    /// Ratio of the odds of an outcome in one group to the odds in the other.
+    OddsRatio,
+    /// Probability that an observation from one group is larger than an observation from the
+    /// other, also known as the area under the ROC curve (AUC) or the common-language effect size.
+    ProbabilityOfSuperiority,
+    /// Proportion of variance in the outcome explained by group membership.
+    EtaSquared,
+    /// Association between two categorical variables, scaled to lie between 0 and 1.
+    CramersV,
+    /// Correlation between two binary variables.
+    Phi,
+    /// Deviation of categorical counts from their expected proportions.
+    CohensW,
+    /// Proportion of successes in a binomial sample.
+    Proportion,
+    /// Difference between the proportions of successes in two groups.
+    RiskDifference,
+    /// Ratio of the proportions of successes in two groups, also known as the relative risk.
+    RiskRatio,
+    /// Number of events per unit of exposure.
+    Rate,
+    /// Ratio of the event rates of two groups.
+    RateRatio,
+    /// Change in the proportion of successes per unit of an ordered score.
+    ProportionTrend,
+}
+
+/// An effect size estimate with a confidence interval.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct EffectSize {
+    kind: EffectSizeKind,
+    estimate: f64,
+    confidence_interval: (f64, f64),
+    confidence_level: f64,
+    sample_sizes: Option<(usize, usize)>,
+    paired: bool,
+}
+
+impl EffectSize {
+    /// Create an effect size of a given `kind` from an `estimate` and a `confidence_interval`
+    /// at a given `confidence_level`.
+    pub fn new(
+        kind: EffectSizeKind,
+        estimate: f64,
+        confidence_interval: (f64, f64),
+        confidence_level: f64,
+    ) -> EffectSize {
+        EffectSize {
+            kind,
+            estimate,
+            confidence_interval,
+            confidence_level,
+            sample_sizes: None,
+            paired: false,
+        }
+    }
+
+    /// Returns the effect size with the sizes `n_x` and `n_y` of the samples it was estimated
+    /// from, which are used when converting between kinds of effect sizes.
+    pub fn with_sample_sizes(self, n_x: usize, n_y: usize) -> EffectSize {
+        EffectSize {
+            sample_sizes: Some((n_x, n_y)),
+            ..self
+        }
+    }
+
+    /// Returns the effect size marked as estimated from paired samples, which cannot be
+    /// converted to other kinds of effect sizes.
+    pub fn with_paired_samples(self) -> EffectSize {
+        EffectSize {
+            paired: true,
+            ..self
+        }
+    }
+
+    /// Returns the kind of effect size.
+    pub fn kind(&self) -> EffectSizeKind {
+        self.kind
+    }
+
+    /// Returns the estimate of the effect size.
+    pub fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    /// Returns the lower and upper bounds of the confidence interval.
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        self.confidence_interval
+    }
+
+    /// Returns the confidence level of the confidence interval.
+    pub fn confidence_level(&self) -> f64 {
+        self.confidence_level
+    }
+
+    /// Returns the sizes of the samples the effect size was estimated from, if known.
+    pub fn sample_sizes(&self) -> Option<(usize, usize)> {
+        self.sample_sizes
+    }
+
+    /// Returns whether the effect size was estimated from paired samples.
+    pub fn is_paired(&self) -> bool {
+        self.paired
+    }
+}
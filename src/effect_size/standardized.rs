@@ -0,0 +1,182 @@
+use crate::distribution::{invert, NoncentralStudentsT};
+use crate::statistics::StatisticsExt;
+use statrs::distribution::ContinuousCDF;
+use statrs::function::gamma::ln_gamma;
+use statrs::statistics::Statistics;
+use statrs::{Result, StatsError};
+
+use super::{EffectSize, EffectSizeKind};
+
+impl EffectSize {
+    /// Calculate Cohen's d for the independent samples `x` and `y`, with a confidence interval
+    /// at `confidence_level` based on the noncentral t-distribution (Steiger & Fouladi, 1997).
+    ///
+    /// # References
+    ///
+    /// Steiger, J. H., & Fouladi, R. T. (1997). Noncentrality interval estimation and the
+    ///     evaluation of statistical models. In L. L. Harlow, S. A. Mulaik, & J. H. Steiger (Eds.),
+    ///     What if there were no significance tests? (pp. 221–257). Lawrence Erlbaum Associates.
+    pub fn cohens_d(x: &[f64], y: &[f64], confidence_level: f64) -> Result<EffectSize> {
+        let scale = (x.n().recip() + y.n().recip()).sqrt();
+        let df = x.n() + y.n() - 2.0;
+        let d = (x.mean() - y.mean()) / x.pooled_std_dev(y);
+        let (lower, upper) = noncentral_t_interval(d / scale, df, confidence_level)?;
+
+        Ok(EffectSize::new(
+            EffectSizeKind::CohensD,
+            d,
+            (lower * scale, upper * scale),
+            confidence_level,
+        )
+        .with_sample_sizes(x.len(), y.len()))
+    }
+
+    /// Calculate Cohen's d for the paired samples `x` and `y`, standardized by the standard
+    /// deviation of the differences, with a confidence interval at `confidence_level` based
+    /// on the noncentral t-distribution.
+    pub fn cohens_d_paired(x: &[f64], y: &[f64], confidence_level: f64) -> Result<EffectSize> {
+        let d: Vec<_> = x.iter().zip(y).map(|(x, y)| x - y).collect();
+        let scale = (&d).n().sqrt().recip();
+        let df = (&d).df();
+        let estimate = (&d).mean() / (&d).std_dev();
+        let (lower, upper) = noncentral_t_interval(estimate / scale, df, confidence_level)?;
+
+        Ok(EffectSize::new(
+            EffectSizeKind::CohensD,
+            estimate,
+            (lower * scale, upper * scale),
+            confidence_level,
+        )
+        .with_paired_samples())
+    }
+
+    /// Calculate Hedges' g for the independent samples `x` and `y`, with a confidence interval
+    /// at `confidence_level` based on the noncentral t-distribution (Hedges, 1981).
+    ///
+    /// # References
+    ///
+    /// Hedges, L. V. (1981). Distribution Theory for Glass’s Estimator of Effect size and Related
+    ///     Estimators. Journal of Educational Statistics, 6(2), 107–128.
+    ///     <https://doi.org/10.3102/10769986006002107>
+    pub fn hedges_g(x: &[f64], y: &[f64], confidence_level: f64) -> Result<EffectSize> {
+        let d = EffectSize::cohens_d(x, y, confidence_level)?;
+        let correction = hedges_correction(x.n() + y.n() - 2.0);
+        let (lower, upper) = d.confidence_interval();
+
+        Ok(EffectSize::new(
+            EffectSizeKind::HedgesG,
+            d.estimate() * correction,
+            (lower * correction, upper * correction),
+            confidence_level,
+        )
+        .with_sample_sizes(x.len(), y.len()))
+    }
+
+    /// Calculate Glass's delta for the independent samples `x` and `y`, where `y` is the
+    /// control group, with a confidence interval at `confidence_level` based on the
+    /// noncentral t-distribution of the estimator (Hedges, 1981).
+    ///
+    /// The interval assumes that the variances of the groups are equal, and can be far from
+    /// its nominal coverage when they are not. For groups with unequal variances, bootstrap
+    /// the statistic instead with [`Bootstrap`](crate::bootstrap::Bootstrap).
+    pub fn glass_delta(x: &[f64], y: &[f64], confidence_level: f64) -> Result<EffectSize> {
+        let scale = (x.n().recip() + y.n().recip()).sqrt();
+        let delta = (x.mean() - y.mean()) / y.std_dev();
+        let (lower, upper) = noncentral_t_interval(delta / scale, y.df(), confidence_level)?;
+
+        Ok(EffectSize::new(
+            EffectSizeKind::GlassDelta,
+            delta,
+            (lower * scale, upper * scale),
+            confidence_level,
+        )
+        .with_sample_sizes(x.len(), y.len()))
+    }
+}
+
+/// Returns the exact small-sample bias correction of Cohen's d with `df` degrees of freedom.
+pub(crate) fn hedges_correction(df: f64) -> f64 {
+    (ln_gamma(df / 2.0) - ln_gamma((df - 1.0) / 2.0)).exp() / (df / 2.0).sqrt()
+}
+
+/// Returns the confidence interval at `confidence_level` of the noncentrality parameter of a
+/// t-distribution with `df` degrees of freedom, given an observed statistic `t`.
+pub(crate) fn noncentral_t_interval(t: f64, df: f64, confidence_level: f64) -> Result<(f64, f64)> {
+    if !(0.0..1.0).contains(&confidence_level) || df.is_nan() || df <= 0.0 || !t.is_finite() {
+        return Err(StatsError::BadParams);
+    }
+
+    let alpha = 1.0 - confidence_level;
+    let survival = |ncp: f64| NoncentralStudentsT::new(df, ncp).unwrap().sf(t);
+    let lower = invert(survival, alpha / 2.0, f64::NEG_INFINITY, f64::INFINITY, t);
+    let upper = invert(
+        survival,
+        1.0 - alpha / 2.0,
+        f64::NEG_INFINITY,
+        f64::INFINITY,
+        t,
+    );
+
+    Ok((lower, upper))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cohens_d() {
+        let x = vec![
+            134.0, 146.0, 104.0, 119.0, 124.0, 161.0, 107.0, 83.0, 113.0, 129.0, 97.0, 123.0,
+        ];
+        let y = vec![70.0, 118.0, 101.0, 85.0, 107.0, 132.0, 94.0];
+        let d = EffectSize::cohens_d(&x, &y, 0.95).unwrap();
+        assert_eq!(d.kind(), EffectSizeKind::CohensD);
+        assert_eq!(d.estimate(), 0.8995574392432595);
+        assert_eq!(
+            d.confidence_interval(),
+            (-0.09161262194797916, 1.866901131792742)
+        );
+    }
+
+    #[test]
+    fn hedges_g() {
+        let x = vec![
+            134.0, 146.0, 104.0, 119.0, 124.0, 161.0, 107.0, 83.0, 113.0, 129.0, 97.0, 123.0,
+        ];
+        let y = vec![70.0, 118.0, 101.0, 85.0, 107.0, 132.0, 94.0];
+        let g = EffectSize::hedges_g(&x, &y, 0.95).unwrap();
+        assert_eq!(g.estimate(), 0.8591776601815225);
+        assert_eq!(
+            g.confidence_interval(),
+            (-0.08750026928194171, 1.783098750818255)
+        );
+    }
+
+    #[test]
+    fn glass_delta() {
+        let x = vec![
+            134.0, 146.0, 104.0, 119.0, 124.0, 161.0, 107.0, 83.0, 113.0, 129.0, 97.0, 123.0,
+        ];
+        let y = vec![70.0, 118.0, 101.0, 85.0, 107.0, 132.0, 94.0];
+        let delta = EffectSize::glass_delta(&x, &y, 0.95).unwrap();
+        assert_eq!(delta.estimate(), 0.9212741619652112);
+        assert_eq!(
+            delta.confidence_interval(),
+            (-0.16935214249901137, 1.9551408390567506)
+        );
+    }
+
+    #[test]
+    fn paired() {
+        let x = vec![8.0, 6.0, 5.5, 11.0, 8.5, 5.0, 6.0, 6.0];
+        let y = vec![8.5, 9.0, 6.5, 10.5, 9.0, 7.0, 6.5, 7.0];
+        let d = EffectSize::cohens_d_paired(&x, &y, 0.95).unwrap();
+        assert!(d.is_paired());
+        assert_eq!(d.estimate(), -0.9354143466934856);
+        assert_eq!(
+            d.confidence_interval(),
+            (-1.7569102929603473, -0.0705694354453236)
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use crate::distribution::fisher_snedecor_inverse_cdf;
+use crate::statistics::StatisticsExt;
+use statrs::{Result, StatsError};
+
+use super::{EffectSize, EffectSizeKind};
+
+impl EffectSize {
+    /// Calculate the ratio between the variances of `x` and `y`, with a confidence interval at
+    /// `confidence_level` based on the F-distribution.
+    pub fn variance_ratio(x: &[f64], y: &[f64], confidence_level: f64) -> Result<EffectSize> {
+        if !(0.0..1.0).contains(&confidence_level) {
+            return Err(StatsError::BadParams);
+        }
+
+        let ratio = x.variance_ratio(y);
+        let alpha = 1.0 - confidence_level;
+        let (df_x, df_y) = (x.df(), y.df());
+        if df_x <= 0.0 || df_y <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        let lower = ratio / fisher_snedecor_inverse_cdf(df_x, df_y, 1.0 - alpha / 2.0);
+        let upper = ratio / fisher_snedecor_inverse_cdf(df_x, df_y, alpha / 2.0);
+
+        Ok(EffectSize::new(
+            EffectSizeKind::VarianceRatio,
+            ratio,
+            (lower, upper),
+            confidence_level,
+        )
+        .with_sample_sizes(x.len(), y.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variance_ratio() {
+        let x = vec![
+            134.0, 146.0, 104.0, 119.0, 124.0, 161.0, 107.0, 83.0, 113.0, 129.0, 97.0, 123.0,
+        ];
+        let y = vec![70.0, 118.0, 101.0, 85.0, 107.0, 132.0, 94.0];
+        let ratio = EffectSize::variance_ratio(&x, &y, 0.95).unwrap();
+        assert_eq!(ratio.kind(), EffectSizeKind::VarianceRatio);
+        assert_eq!(ratio.estimate(), 1.0755200911940725);
+        assert_eq!(
+            ratio.confidence_interval(),
+            (0.1988110169857296, 4.173718299078523)
+        );
+    }
+}
@@ -1,3 +1,6 @@
+pub mod bootstrap;
 pub mod distribution;
+pub mod effect_size;
+pub mod permutation;
 pub mod statistics;
 pub mod test;
@@ -0,0 +1,168 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use statrs::function::factorial::ln_factorial;
+use statrs::{Result, StatsError};
+
+use super::{next_permutation, Counter, PermutationOptions, PermutationTest};
+
+impl PermutationTest {
+    /// Run a permutation test of the `statistic` on the independent samples `x` and `y`, by
+    /// reassigning the pooled observations to groups of the original sizes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::Statistics;
+    /// use stattest::permutation::{PermutationOptions, PermutationTest};
+    ///
+    /// let x = vec![68.0, 68.0, 59.0, 72.0, 64.0, 67.0, 70.0, 74.0];
+    /// let y = vec![60.0, 67.0, 61.0, 62.0, 67.0, 63.0, 56.0, 58.0];
+    /// let difference = |x: &[f64], y: &[f64]| x.mean() - y.mean();
+    /// let test = PermutationTest::two_sample(&x, &y, difference, PermutationOptions::default());
+    /// assert!(test.is_ok());
+    /// ```
+    pub fn two_sample<F>(
+        x: &[f64],
+        y: &[f64],
+        statistic: F,
+        options: PermutationOptions,
+    ) -> Result<PermutationTest>
+    where
+        F: Fn(&[f64], &[f64]) -> f64,
+    {
+        PermutationTest::k_sample(
+            &[x, y],
+            |samples| statistic(samples[0], samples[1]),
+            options,
+        )
+    }
+
+    /// Run a permutation test of the `statistic` on the independent `samples`, by reassigning
+    /// the pooled observations to groups of the original sizes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are fewer than two samples, or if any sample is empty.
+    pub fn k_sample<F>(
+        samples: &[&[f64]],
+        statistic: F,
+        options: PermutationOptions,
+    ) -> Result<PermutationTest>
+    where
+        F: Fn(&[&[f64]]) -> f64,
+    {
+        if samples.len() < 2 || samples.iter().any(|sample| sample.is_empty()) {
+            return Err(StatsError::BadParams);
+        }
+
+        let pooled: Vec<f64> = samples
+            .iter()
+            .flat_map(|sample| sample.iter())
+            .copied()
+            .collect();
+        let mut labels: Vec<usize> = samples
+            .iter()
+            .enumerate()
+            .flat_map(|(label, sample)| std::iter::repeat_n(label, sample.len()))
+            .collect();
+        let mut groups: Vec<Vec<f64>> = samples.iter().map(|sample| sample.to_vec()).collect();
+        let mut evaluate = |labels: &[usize]| {
+            for group in groups.iter_mut() {
+                group.clear();
+            }
+            for (value, label) in pooled.iter().zip(labels) {
+                groups[*label].push(*value);
+            }
+            let slices: Vec<&[f64]> = groups.iter().map(|group| group.as_slice()).collect();
+            statistic(&slices)
+        };
+
+        let mut counter = Counter::new(options.alternative, statistic(samples));
+        let ln_count = ln_factorial(pooled.len() as u64)
+            - samples
+                .iter()
+                .map(|sample| ln_factorial(sample.len() as u64))
+                .sum::<f64>();
+        let exact = ln_count.exp().round() <= options.max_exact as f64;
+
+        if exact {
+            loop {
+                counter.add(evaluate(&labels));
+                if !next_permutation(&mut labels) {
+                    break;
+                }
+            }
+        } else {
+            let mut rng = StdRng::seed_from_u64(options.seed);
+            for _ in 0..options.resamples {
+                labels.shuffle(&mut rng);
+                counter.add(evaluate(&labels));
+            }
+        }
+
+        Ok(counter.finish(exact))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::AlternativeHypothesis;
+    use statrs::statistics::Statistics;
+
+    fn difference(x: &[f64], y: &[f64]) -> f64 {
+        x.mean() - y.mean()
+    }
+
+    #[test]
+    fn exact() {
+        let x = vec![68.0, 68.0, 59.0, 72.0, 64.0, 67.0, 70.0, 74.0];
+        let y = vec![60.0, 67.0, 61.0, 62.0, 67.0, 63.0, 56.0, 58.0];
+        let test =
+            PermutationTest::two_sample(&x, &y, difference, PermutationOptions::default()).unwrap();
+        assert!(test.is_exact());
+        assert_eq!(test.permutations(), 12870);
+        assert_eq!(test.statistic(), 6.0);
+        assert_eq!(test.p_value(), 0.019580419580419582);
+
+        let options =
+            PermutationOptions::default().with_alternative(AlternativeHypothesis::Greater);
+        let test = PermutationTest::two_sample(&x, &y, difference, options).unwrap();
+        assert_eq!(test.p_value(), 0.009790209790209791);
+    }
+
+    #[test]
+    fn monte_carlo() {
+        let x = vec![
+            134.0, 146.0, 104.0, 119.0, 124.0, 161.0, 107.0, 83.0, 113.0, 129.0, 97.0, 123.0,
+        ];
+        let y = vec![70.0, 118.0, 101.0, 85.0, 107.0, 132.0, 94.0];
+        let options = PermutationOptions::default().with_max_exact(0);
+        let test = PermutationTest::two_sample(&x, &y, difference, options).unwrap();
+        assert!(!test.is_exact());
+        assert_eq!(test.permutations(), 9999);
+        assert_eq!(test.p_value(), 0.075);
+    }
+
+    #[test]
+    fn k_sample() {
+        let a = vec![4.2, 4.6, 3.9, 4.0];
+        let b = vec![4.9, 5.1, 4.8];
+        let c = vec![3.1, 3.5, 3.3, 3.6];
+        let between = |samples: &[&[f64]]| {
+            let n = samples.iter().map(|sample| sample.len()).sum::<usize>() as f64;
+            let mean = samples.iter().flat_map(|sample| sample.iter()).sum::<f64>() / n;
+            samples
+                .iter()
+                .map(|sample| sample.len() as f64 * (sample.mean() - mean).powi(2))
+                .sum::<f64>()
+        };
+        let options =
+            PermutationOptions::default().with_alternative(AlternativeHypothesis::Greater);
+        let test = PermutationTest::k_sample(&[&a, &b, &c], between, options).unwrap();
+        assert!(test.is_exact());
+        assert_eq!(test.permutations(), 11550);
+        assert_eq!(test.p_value(), 0.0005194805194805195);
+    }
+}
@@ -0,0 +1,222 @@
+//! Defines permutation tests with custom test statistics.
+
+pub use self::randomization::RandomizationDesign;
+
+use crate::test::AlternativeHypothesis;
+
+mod independent;
+mod paired;
+mod randomization;
+
+/// Default number of random permutations when exact enumeration is not feasible.
+static RESAMPLES: usize = 9999;
+/// Default seed of the random permutations, so that p-values are reproducible.
+static SEED: u64 = 0x5EED;
+/// Default largest number of permutations that are enumerated exactly.
+static MAX_EXACT: usize = 100_000;
+/// Relative tolerance when comparing permuted statistics to the observed statistic, so that
+/// rounding errors do not break ties.
+static TOLERANCE: f64 = 1E-12;
+
+/// Options of a permutation test.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PermutationOptions {
+    alternative: AlternativeHypothesis,
+    resamples: usize,
+    seed: u64,
+    max_exact: usize,
+}
+
+impl Default for PermutationOptions {
+    fn default() -> PermutationOptions {
+        PermutationOptions {
+            alternative: AlternativeHypothesis::Different,
+            resamples: RESAMPLES,
+            seed: SEED,
+            max_exact: MAX_EXACT,
+        }
+    }
+}
+
+impl PermutationOptions {
+    /// Sets the alternative hypothesis. `Different` compares the absolute values of the
+    /// statistics, which suits statistics that are centered at zero under the null hypothesis.
+    pub fn with_alternative(self, alternative: AlternativeHypothesis) -> PermutationOptions {
+        PermutationOptions {
+            alternative,
+            ..self
+        }
+    }
+
+    /// Sets the number of random permutations when exact enumeration is not feasible.
+    pub fn with_resamples(self, resamples: usize) -> PermutationOptions {
+        PermutationOptions { resamples, ..self }
+    }
+
+    /// Sets the seed of the random permutations.
+    pub fn with_seed(self, seed: u64) -> PermutationOptions {
+        PermutationOptions { seed, ..self }
+    }
+
+    /// Sets the largest number of permutations that are enumerated exactly. Set to zero to
+    /// always use random permutations.
+    pub fn with_max_exact(self, max_exact: usize) -> PermutationOptions {
+        PermutationOptions { max_exact, ..self }
+    }
+
+    /// Returns the alternative hypothesis.
+    pub fn alternative(&self) -> AlternativeHypothesis {
+        self.alternative
+    }
+
+    /// Returns the number of random permutations.
+    pub fn resamples(&self) -> usize {
+        self.resamples
+    }
+
+    /// Returns the seed of the random permutations.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the largest number of permutations that are enumerated exactly.
+    pub fn max_exact(&self) -> usize {
+        self.max_exact
+    }
+}
+
+/// Implements a [permutation test](https://en.wikipedia.org/wiki/Permutation_test) for a
+/// custom test statistic.
+///
+/// When the number of distinct permutations is small enough, all of them are enumerated and
+/// the p-value is exact. Otherwise, the p-value is estimated from random permutations as
+/// (1 + b) / (1 + m), where b of the m permuted statistics are at least as extreme as the
+/// observed statistic (Phipson & Smyth, 2010).
+///
+/// # References
+///
+/// Phipson, B., & Smyth, G. K. (2010). Permutation P-values Should Never Be Zero: Calculating
+///     Exact P-values When Permutations Are Randomly Drawn. Statistical Applications in Genetics
+///     and Molecular Biology, 9(1). <https://doi.org/10.2202/1544-6115.1585>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PermutationTest {
+    statistic: f64,
+    p_value: f64,
+    permutations: usize,
+    exact: bool,
+}
+
+impl PermutationTest {
+    /// Returns the observed value of the test statistic.
+    pub fn statistic(&self) -> f64 {
+        self.statistic
+    }
+
+    /// Returns the p-value.
+    pub fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    /// Returns the number of permutations that were evaluated.
+    pub fn permutations(&self) -> usize {
+        self.permutations
+    }
+
+    /// Returns whether all permutations were enumerated.
+    pub fn is_exact(&self) -> bool {
+        self.exact
+    }
+}
+
+/// Counts the permuted statistics that are at least as extreme as the observed statistic,
+/// measured from the value of the statistic under the null hypothesis.
+pub(crate) struct Counter {
+    alternative: AlternativeHypothesis,
+    observed: f64,
+    center: f64,
+    extreme: usize,
+    total: usize,
+}
+
+impl Counter {
+    pub(crate) fn new(alternative: AlternativeHypothesis, observed: f64) -> Counter {
+        Counter::centered(alternative, observed, 0.0)
+    }
+
+    /// Constructs a counter for a statistic that equals `center` under the null hypothesis.
+    pub(crate) fn centered(
+        alternative: AlternativeHypothesis,
+        observed: f64,
+        center: f64,
+    ) -> Counter {
+        Counter {
+            alternative,
+            observed,
+            center,
+            extreme: 0,
+            total: 0,
+        }
+    }
+
+    pub(crate) fn add(&mut self, statistic: f64) {
+        let (statistic, observed) = (statistic - self.center, self.observed - self.center);
+        let tolerance = TOLERANCE * observed.abs().max(1.0);
+        let extreme = match self.alternative {
+            AlternativeHypothesis::Greater => statistic >= observed - tolerance,
+            AlternativeHypothesis::Less => statistic <= observed + tolerance,
+            AlternativeHypothesis::Different => statistic.abs() >= observed.abs() - tolerance,
+        };
+        if extreme {
+            self.extreme += 1;
+        }
+        self.total += 1;
+    }
+
+    pub(crate) fn finish(self, exact: bool) -> PermutationTest {
+        let p_value = if exact {
+            self.extreme as f64 / self.total as f64
+        } else {
+            (self.extreme + 1) as f64 / (self.total + 1) as f64
+        };
+        PermutationTest {
+            statistic: self.observed,
+            p_value,
+            permutations: self.total,
+            exact,
+        }
+    }
+}
+
+/// Rearranges `values` into the next permutation in lexicographic order, which enumerates
+/// every distinct arrangement of a multiset once. Returns `false` after the last permutation,
+/// leaving `values` sorted again.
+fn next_permutation<T: Ord>(values: &mut [T]) -> bool {
+    let pivot = match values.windows(2).rposition(|pair| pair[0] < pair[1]) {
+        Some(pivot) => pivot,
+        None => {
+            values.reverse();
+            return false;
+        }
+    };
+    let successor = values
+        .iter()
+        .rposition(|value| *value > values[pivot])
+        .unwrap();
+    values.swap(pivot, successor);
+    values[pivot + 1..].reverse();
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn next_permutation() {
+        let mut labels = vec![0, 0, 1, 1];
+        let mut count = 1;
+        while super::next_permutation(&mut labels) {
+            count += 1;
+        }
+        assert_eq!(count, 6);
+        assert_eq!(labels, vec![0, 0, 1, 1]);
+    }
+}
@@ -0,0 +1,133 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use statrs::{Result, StatsError};
+
+use super::{Counter, PermutationOptions, PermutationTest};
+
+impl PermutationTest {
+    /// Run a permutation test of the `statistic` on the paired samples `x` and `y`, by swapping
+    /// the observations within pairs.
+    pub fn paired<F>(
+        x: &[f64],
+        y: &[f64],
+        statistic: F,
+        options: PermutationOptions,
+    ) -> Result<PermutationTest>
+    where
+        F: Fn(&[f64], &[f64]) -> f64,
+    {
+        if x.len() != y.len() {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+
+        let mut swapped_x = x.to_vec();
+        let mut swapped_y = y.to_vec();
+        let evaluate = |swaps: &[bool]| {
+            for (i, swap) in swaps.iter().enumerate() {
+                let (x_i, y_i) = if *swap { (y[i], x[i]) } else { (x[i], y[i]) };
+                swapped_x[i] = x_i;
+                swapped_y[i] = y_i;
+            }
+            statistic(&swapped_x, &swapped_y)
+        };
+
+        flip(x.len(), statistic(x, y), evaluate, options)
+    }
+
+    /// Run a permutation test of the `statistic` on the paired differences or one-sample
+    /// deviations `d`, by flipping their signs, which assumes that they are symmetric around
+    /// zero under the null hypothesis.
+    pub fn sign_flip<F>(
+        d: &[f64],
+        statistic: F,
+        options: PermutationOptions,
+    ) -> Result<PermutationTest>
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let mut flipped = d.to_vec();
+        let evaluate = |flips: &[bool]| {
+            for ((flipped, d), flip) in flipped.iter_mut().zip(d).zip(flips) {
+                *flipped = if *flip { -d } else { *d };
+            }
+            statistic(&flipped)
+        };
+
+        flip(d.len(), statistic(d), evaluate, options)
+    }
+}
+
+/// Evaluates the permutations that flip any subset of the `n` pairs.
+fn flip<F>(
+    n: usize,
+    observed: f64,
+    mut evaluate: F,
+    options: PermutationOptions,
+) -> Result<PermutationTest>
+where
+    F: FnMut(&[bool]) -> f64,
+{
+    if n == 0 {
+        return Err(StatsError::BadParams);
+    }
+
+    let mut counter = Counter::new(options.alternative, observed);
+    let mut flips = vec![false; n];
+    let exact = n < usize::BITS as usize && 1 << n <= options.max_exact;
+
+    if exact {
+        for subset in 0..1_usize << n {
+            for (i, flip) in flips.iter_mut().enumerate() {
+                *flip = subset >> i & 1 == 1;
+            }
+            counter.add(evaluate(&flips));
+        }
+    } else {
+        let mut rng = StdRng::seed_from_u64(options.seed);
+        for _ in 0..options.resamples {
+            for flip in flips.iter_mut() {
+                *flip = rng.gen();
+            }
+            counter.add(evaluate(&flips));
+        }
+    }
+
+    Ok(counter.finish(exact))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use statrs::statistics::Statistics;
+
+    #[test]
+    fn paired() {
+        let x = vec![8.0, 6.0, 5.5, 11.0, 8.5, 5.0, 6.0, 6.0];
+        let y = vec![8.5, 9.0, 6.5, 10.5, 9.0, 7.0, 6.5, 7.0];
+        let difference = |x: &[f64], y: &[f64]| x.mean() - y.mean();
+        let test =
+            PermutationTest::paired(&x, &y, difference, PermutationOptions::default()).unwrap();
+        assert!(test.is_exact());
+        assert_eq!(test.permutations(), 256);
+        assert_eq!(test.statistic(), -1.0);
+        assert_eq!(test.p_value(), 0.0390625);
+
+        let d: Vec<_> = x.iter().zip(&y).map(|(x, y)| x - y).collect();
+        let test =
+            PermutationTest::sign_flip(&d, |d| d.mean(), PermutationOptions::default()).unwrap();
+        assert_eq!(test.p_value(), 0.0390625);
+    }
+
+    #[test]
+    fn monte_carlo() {
+        let d = vec![
+            0.8, -0.2, 1.4, 0.5, 0.9, -0.6, 1.1, 0.3, 0.7, -0.1, 1.6, 0.4, 0.2, 0.9, -0.3, 1.0,
+            0.6, 0.1, 1.3, -0.4, 0.8, 0.5,
+        ];
+        let test =
+            PermutationTest::sign_flip(&d, |d| d.mean(), PermutationOptions::default()).unwrap();
+        assert!(!test.is_exact());
+        assert_eq!(test.permutations(), 9999);
+        assert_eq!(test.p_value(), 0.0012);
+    }
+}
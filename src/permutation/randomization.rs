@@ -0,0 +1,337 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use statrs::function::factorial::ln_factorial;
+use statrs::{Result, StatsError};
+use std::collections::BTreeMap;
+
+use super::{next_permutation, Counter, PermutationOptions, PermutationTest};
+
+/// Randomization design of an experiment, describing how observations were assigned to groups.
+///
+/// Group labels run from zero to the number of groups minus one. Treatment is assigned at
+/// random within each stratum or block, and to whole clusters at once when clusters are given.
+/// Strata and clusters are identified by arbitrary numbers.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RandomizationDesign<'a> {
+    groups: &'a [usize],
+    strata: Option<&'a [usize]>,
+    clusters: Option<&'a [usize]>,
+}
+
+impl<'a> RandomizationDesign<'a> {
+    /// Constructs a completely randomized design with the group of each observation.
+    pub fn new(groups: &'a [usize]) -> RandomizationDesign<'a> {
+        RandomizationDesign {
+            groups,
+            strata: None,
+            clusters: None,
+        }
+    }
+
+    /// Restricts the randomization to within the stratum or block of each observation.
+    pub fn with_strata(self, strata: &'a [usize]) -> RandomizationDesign<'a> {
+        RandomizationDesign {
+            strata: Some(strata),
+            ..self
+        }
+    }
+
+    /// Randomizes the cluster of each observation as a whole.
+    pub fn with_clusters(self, clusters: &'a [usize]) -> RandomizationDesign<'a> {
+        RandomizationDesign {
+            clusters: Some(clusters),
+            ..self
+        }
+    }
+}
+
+/// Units of randomization, which are either the observations or their clusters.
+struct Units {
+    /// The unit of each observation.
+    observations: Vec<usize>,
+    /// The group of each unit.
+    labels: Vec<usize>,
+    /// The units of each stratum.
+    strata: Vec<Vec<usize>>,
+}
+
+impl Units {
+    fn new(n: usize, design: &RandomizationDesign) -> Result<Units> {
+        let lengths = [design.strata, design.clusters];
+        if design.groups.len() != n || lengths.iter().flatten().any(|ids| ids.len() != n) {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+
+        let observations: Vec<usize> = match design.clusters {
+            Some(clusters) => {
+                let mut ids = BTreeMap::new();
+                clusters
+                    .iter()
+                    .map(|cluster| {
+                        let next = ids.len();
+                        *ids.entry(cluster).or_insert(next)
+                    })
+                    .collect()
+            }
+            None => (0..n).collect(),
+        };
+
+        let units = observations.iter().max().map_or(0, |unit| unit + 1);
+        let mut labels = vec![None; units];
+        let mut unit_strata = vec![None; units];
+        for (i, unit) in observations.iter().enumerate() {
+            let stratum = design.strata.map_or(0, |strata| strata[i]);
+            // Clusters must be nested in strata and receive a single treatment.
+            if *labels[*unit].get_or_insert(design.groups[i]) != design.groups[i]
+                || *unit_strata[*unit].get_or_insert(stratum) != stratum
+            {
+                return Err(StatsError::BadParams);
+            }
+        }
+
+        let mut strata: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (unit, stratum) in unit_strata.into_iter().enumerate() {
+            strata.entry(stratum.unwrap()).or_default().push(unit);
+        }
+
+        Ok(Units {
+            observations,
+            labels: labels.into_iter().map(Option::unwrap).collect(),
+            strata: strata.into_values().collect(),
+        })
+    }
+
+    /// Returns the natural logarithm of the number of distinct assignments.
+    fn ln_assignments(&self) -> f64 {
+        self.strata
+            .iter()
+            .map(|units| {
+                let mut counts = BTreeMap::new();
+                for unit in units {
+                    *counts.entry(self.labels[*unit]).or_insert(0_u64) += 1;
+                }
+                ln_factorial(units.len() as u64)
+                    - counts
+                        .values()
+                        .map(|count| ln_factorial(*count))
+                        .sum::<f64>()
+            })
+            .sum()
+    }
+}
+
+impl PermutationTest {
+    /// Run a randomization test of the `statistic` on the `values` of an experiment with the
+    /// given randomization `design`, by reassigning groups in the same way as the original
+    /// randomization. The statistic receives the values of each group.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::Statistics;
+    /// use stattest::permutation::{PermutationOptions, PermutationTest, RandomizationDesign};
+    ///
+    /// let values = vec![3.1, 4.2, 2.8, 3.9, 5.0, 6.1, 4.4, 5.8];
+    /// let groups = vec![0, 1, 0, 1, 0, 1, 0, 1];
+    /// let strata = vec![0, 0, 0, 0, 1, 1, 1, 1];
+    /// let design = RandomizationDesign::new(&groups).with_strata(&strata);
+    /// let difference = |groups: &[&[f64]]| groups[1].mean() - groups[0].mean();
+    /// let test = PermutationTest::randomization(&values, design, difference, PermutationOptions::default());
+    /// assert!(test.is_ok());
+    /// ```
+    pub fn randomization<F>(
+        values: &[f64],
+        design: RandomizationDesign,
+        statistic: F,
+        options: PermutationOptions,
+    ) -> Result<PermutationTest>
+    where
+        F: Fn(&[&[f64]]) -> f64,
+    {
+        PermutationTest::sharp_null(values, design, 0.0, statistic, options)
+    }
+
+    /// Run a randomization test of Fisher's sharp null hypothesis that the treatment changes
+    /// the value of every observation by exactly `effect`, for an experiment with a control
+    /// group 0 and a treatment group 1 (Fisher, 1935; Imbens & Rubin, 2015).
+    ///
+    /// Under the sharp null hypothesis all potential outcomes are known, so the `statistic` is
+    /// evaluated on the outcomes that every possible assignment would have produced. The
+    /// statistic must estimate the effect, like a difference in means, because the statistics
+    /// are compared by their distance from `effect`.
+    ///
+    /// # References
+    ///
+    /// Fisher, R. A. (1935). The Design of Experiments. Oliver and Boyd.
+    ///
+    /// Imbens, G. W., & Rubin, D. B. (2015). Fisher's Exact P-Values for Completely Randomized
+    ///     Experiments. In Causal Inference for Statistics, Social, and Biomedical Sciences
+    ///     (pp. 57–82). Cambridge University Press. <https://doi.org/10.1017/CBO9781139025751.006>
+    pub fn sharp_null<F>(
+        values: &[f64],
+        design: RandomizationDesign,
+        effect: f64,
+        statistic: F,
+        options: PermutationOptions,
+    ) -> Result<PermutationTest>
+    where
+        F: Fn(&[&[f64]]) -> f64,
+    {
+        let units = Units::new(values.len(), &design)?;
+        let k = design.groups.iter().max().map_or(0, |group| group + 1);
+        if k < 2 || (effect != 0.0 && k != 2) || !effect.is_finite() {
+            return Err(StatsError::BadParams);
+        }
+
+        let controls: Vec<f64> = values
+            .iter()
+            .zip(design.groups)
+            .map(|(value, group)| if *group == 1 { value - effect } else { *value })
+            .collect();
+        let exact = units.ln_assignments().exp().round() <= options.max_exact as f64;
+        let mut labels: Vec<Vec<usize>> = units
+            .strata
+            .iter()
+            .map(|stratum| stratum.iter().map(|unit| units.labels[*unit]).collect())
+            .collect();
+        let Units {
+            observations,
+            labels: mut assignment,
+            strata,
+        } = units;
+        let mut groups: Vec<Vec<f64>> = vec![Vec::with_capacity(values.len()); k];
+        let mut evaluate = |labels: &[usize]| {
+            for group in groups.iter_mut() {
+                group.clear();
+            }
+            for (control, unit) in controls.iter().zip(&observations) {
+                let label = labels[*unit];
+                let value = if label == 1 {
+                    control + effect
+                } else {
+                    *control
+                };
+                groups[label].push(value);
+            }
+            let slices: Vec<&[f64]> = groups.iter().map(|group| group.as_slice()).collect();
+            statistic(&slices)
+        };
+
+        let mut counter = Counter::centered(options.alternative, evaluate(&assignment), effect);
+
+        if exact {
+            for stratum in labels.iter_mut() {
+                stratum.sort_unstable();
+            }
+            loop {
+                assign(&mut assignment, &strata, &labels);
+                counter.add(evaluate(&assignment));
+                // Advance the strata like the digits of an odometer.
+                if !labels.iter_mut().any(|stratum| next_permutation(stratum)) {
+                    break;
+                }
+            }
+        } else {
+            let mut rng = StdRng::seed_from_u64(options.seed);
+            for _ in 0..options.resamples {
+                for stratum in labels.iter_mut() {
+                    stratum.shuffle(&mut rng);
+                }
+                assign(&mut assignment, &strata, &labels);
+                counter.add(evaluate(&assignment));
+            }
+        }
+
+        Ok(counter.finish(exact))
+    }
+}
+
+/// Assigns the `labels` of each stratum to its units.
+fn assign(assignment: &mut [usize], strata: &[Vec<usize>], labels: &[Vec<usize>]) {
+    for (units, labels) in strata.iter().zip(labels) {
+        for (unit, label) in units.iter().zip(labels) {
+            assignment[*unit] = *label;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::AlternativeHypothesis;
+    use statrs::statistics::Statistics;
+
+    fn difference(groups: &[&[f64]]) -> f64 {
+        groups[1].mean() - groups[0].mean()
+    }
+
+    #[test]
+    fn stratified() {
+        let values = vec![3.1, 4.2, 2.8, 3.9, 5.0, 6.1, 4.4, 5.8, 3.5, 4.1];
+        let groups = vec![0, 1, 0, 1, 0, 1, 0, 1, 0, 1];
+        let strata = vec![0, 0, 0, 0, 1, 1, 1, 1, 2, 2];
+        let design = RandomizationDesign::new(&groups).with_strata(&strata);
+        let options = PermutationOptions::default();
+        let test = PermutationTest::randomization(&values, design, difference, options).unwrap();
+        assert!(test.is_exact());
+        assert_eq!(test.permutations(), 72);
+        assert_eq!(test.statistic(), 1.06);
+        assert_eq!(test.p_value(), 0.027777777777777776);
+
+        let complete = RandomizationDesign::new(&groups);
+        let test = PermutationTest::randomization(&values, complete, difference, options).unwrap();
+        assert_eq!(test.permutations(), 252);
+        assert_eq!(test.p_value(), 0.14285714285714285);
+    }
+
+    #[test]
+    fn clustered() {
+        let values = vec![3.1, 3.4, 4.2, 4.0, 2.8, 2.5, 3.9, 4.4, 5.0, 4.6, 6.1, 5.7];
+        let groups = vec![0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1];
+        let clusters = vec![1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6];
+        let design = RandomizationDesign::new(&groups).with_clusters(&clusters);
+        let options =
+            PermutationOptions::default().with_alternative(AlternativeHypothesis::Greater);
+        let test = PermutationTest::randomization(&values, design, difference, options).unwrap();
+        assert_eq!(test.permutations(), 20);
+        assert_eq!(test.p_value(), 0.15);
+
+        let mixed = vec![0, 1, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1];
+        let design = RandomizationDesign::new(&mixed).with_clusters(&clusters);
+        assert!(PermutationTest::randomization(&values, design, difference, options).is_err());
+    }
+
+    #[test]
+    fn sharp_null() {
+        let values = vec![3.1, 4.2, 2.8, 3.9, 5.0, 6.1, 4.4, 5.8, 3.5, 4.1];
+        let groups = vec![0, 1, 0, 1, 0, 1, 0, 1, 0, 1];
+        let strata = vec![0, 0, 0, 0, 1, 1, 1, 1, 2, 2];
+        let design = RandomizationDesign::new(&groups).with_strata(&strata);
+        let options = PermutationOptions::default();
+        let test = PermutationTest::sharp_null(&values, design, 1.0, difference, options).unwrap();
+        assert_eq!(test.statistic(), 1.06);
+        assert_eq!(test.p_value(), 0.7777777777777778);
+
+        let options =
+            PermutationOptions::default().with_alternative(AlternativeHypothesis::Greater);
+        let test = PermutationTest::sharp_null(&values, design, 1.0, difference, options).unwrap();
+        assert_eq!(test.p_value(), 0.3888888888888889);
+    }
+
+    #[test]
+    fn monte_carlo() {
+        let values: Vec<f64> = (0..40)
+            .map(|i| (i % 7) as f64 + (i % 2) as f64 * 0.8)
+            .collect();
+        let groups: Vec<usize> = (0..40).map(|i| i % 2).collect();
+        let strata: Vec<usize> = (0..40).map(|i| i / 10).collect();
+        let design = RandomizationDesign::new(&groups).with_strata(&strata);
+        let options = PermutationOptions::default()
+            .with_resamples(999)
+            .with_seed(42);
+        let test = PermutationTest::randomization(&values, design, difference, options).unwrap();
+        assert!(!test.is_exact());
+        assert_eq!(test.p_value(), 0.287);
+    }
+}
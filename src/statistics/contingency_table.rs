@@ -0,0 +1,161 @@
+use statrs::{Result, StatsError};
+use std::collections::BTreeMap;
+
+/// A [contingency table](https://en.wikipedia.org/wiki/Contingency_table) of the counts of
+/// observations for every combination of the categories of two variables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContingencyTable {
+    counts: Vec<Vec<usize>>,
+}
+
+impl ContingencyTable {
+    /// Constructs a contingency table from the `counts` of every row.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no rows or columns, or if the rows differ in length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::statistics::ContingencyTable;
+    ///
+    /// let table = ContingencyTable::new(vec![vec![12, 5], vec![7, 9]]);
+    /// assert!(table.is_ok());
+    /// ```
+    pub fn new(counts: Vec<Vec<usize>>) -> Result<ContingencyTable> {
+        let columns = counts.first().map_or(0, |row| row.len());
+        if columns == 0 {
+            return Err(StatsError::BadParams);
+        }
+        if counts.iter().any(|row| row.len() != columns) {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+        Ok(ContingencyTable { counts })
+    }
+
+    /// Constructs a contingency table by cross-tabulating the paired labels `x` and `y`. The
+    /// rows and columns are ordered by label.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::statistics::ContingencyTable;
+    ///
+    /// let x = vec!["a", "a", "b", "b", "b"];
+    /// let y = vec![true, false, true, true, false];
+    /// let table = ContingencyTable::from_labels(&x, &y).unwrap();
+    /// assert_eq!(table.get(1, 1), 2);
+    /// ```
+    pub fn from_labels<X: Ord, Y: Ord>(x: &[X], y: &[Y]) -> Result<ContingencyTable> {
+        if x.len() != y.len() {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+
+        let rows = index(x);
+        let columns = index(y);
+
+        let mut counts = vec![vec![0; columns.len()]; rows.len()];
+        for (x, y) in x.iter().zip(y) {
+            counts[rows[x]][columns[y]] += 1;
+        }
+        ContingencyTable::new(counts)
+    }
+
+    /// Returns the number of rows.
+    pub fn rows(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Returns the number of columns.
+    pub fn columns(&self) -> usize {
+        self.counts[0].len()
+    }
+
+    /// Returns the count in row `i` and column `j`.
+    pub fn get(&self, i: usize, j: usize) -> usize {
+        self.counts[i][j]
+    }
+
+    /// Returns the counts of every row.
+    pub fn counts(&self) -> &[Vec<usize>] {
+        &self.counts
+    }
+
+    /// Returns the total count.
+    pub fn total(&self) -> usize {
+        self.counts.iter().flatten().sum()
+    }
+
+    /// Returns the total count of every row.
+    pub fn row_totals(&self) -> Vec<usize> {
+        self.counts.iter().map(|row| row.iter().sum()).collect()
+    }
+
+    /// Returns the total count of every column.
+    pub fn column_totals(&self) -> Vec<usize> {
+        (0..self.columns())
+            .map(|j| self.counts.iter().map(|row| row[j]).sum())
+            .collect()
+    }
+
+    /// Returns the counts expected in every cell if rows and columns were independent, given
+    /// the totals of the rows and columns.
+    pub fn expected(&self) -> Vec<Vec<f64>> {
+        let total = self.total() as f64;
+        let column_totals = self.column_totals();
+        self.row_totals()
+            .iter()
+            .map(|row| {
+                column_totals
+                    .iter()
+                    .map(|column| (row * column) as f64 / total)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the table with rows and columns interchanged.
+    pub fn transpose(&self) -> ContingencyTable {
+        ContingencyTable {
+            counts: (0..self.columns())
+                .map(|j| self.counts.iter().map(|row| row[j]).collect())
+                .collect(),
+        }
+    }
+}
+
+/// Returns the index of every distinct label in sorted order.
+fn index<T: Ord>(labels: &[T]) -> BTreeMap<&T, usize> {
+    let mut index = BTreeMap::new();
+    for label in labels {
+        index.insert(label, 0);
+    }
+    for (i, value) in index.values_mut().enumerate() {
+        *value = i;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_labels() {
+        let x = vec![2, 1, 2, 2, 1, 3];
+        let y = vec!['b', 'a', 'a', 'b', 'b', 'a'];
+        let table = ContingencyTable::from_labels(&x, &y).unwrap();
+        assert_eq!(table.counts(), &[vec![1, 1], vec![1, 2], vec![1, 0]]);
+        assert_eq!(table.row_totals(), vec![2, 3, 1]);
+        assert_eq!(table.column_totals(), vec![3, 3]);
+        assert_eq!(table.expected()[1], vec![1.5, 1.5]);
+        assert_eq!(table.transpose().counts(), &[vec![1, 1, 1], vec![1, 2, 0]]);
+    }
+
+    #[test]
+    fn new() {
+        assert!(ContingencyTable::new(vec![]).is_err());
+        assert!(ContingencyTable::new(vec![vec![1, 2], vec![3]]).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+/// Returns the quadratic form `x' A⁻¹ x` of the vector `x` and the inverse of the symmetric
+/// matrix `a`, or `None` if the matrix is singular.
+pub(crate) fn inverse_quadratic_form(x: &[f64], a: &[Vec<f64>]) -> Option<f64> {
+    let solution = solve(a, x)?;
+    Some(x.iter().zip(&solution).map(|(x, y)| x * y).sum())
+}
+
+/// Returns the solution `y` of `A y = b` by Gaussian elimination with partial pivoting, or
+/// `None` if the matrix is singular.
+pub(crate) fn solve(a: &[Vec<f64>], b: &[f64]) -> Option<Vec<f64>> {
+    let n = b.len();
+    let mut augmented: Vec<Vec<f64>> = a
+        .iter()
+        .zip(b)
+        .map(|(row, b)| {
+            let mut row = row.clone();
+            row.push(*b);
+            row
+        })
+        .collect();
+    // Pivots are compared to the largest entry, so that singularity does not depend on scale.
+    let scale = a
+        .iter()
+        .flatten()
+        .fold(0.0_f64, |max, entry| max.max(entry.abs()));
+    let tolerance = scale * n as f64 * f64::EPSILON * 16.0;
+
+    for column in 0..n {
+        let pivot = (column..n).max_by(|i, j| {
+            augmented[*i][column]
+                .abs()
+                .partial_cmp(&augmented[*j][column].abs())
+                .unwrap()
+        })?;
+        if augmented[pivot][column].abs() <= tolerance {
+            return None;
+        }
+        augmented.swap(column, pivot);
+        let (above, below) = augmented.split_at_mut(column + 1);
+        let pivot_row = &above[column];
+        for row in below {
+            let factor = row[column] / pivot_row[column];
+            for (entry, pivot_entry) in row[column..].iter_mut().zip(&pivot_row[column..]) {
+                *entry -= factor * pivot_entry;
+            }
+        }
+    }
+
+    let mut solution = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum = (row + 1..n)
+            .map(|k| augmented[row][k] * solution[k])
+            .sum::<f64>();
+        solution[row] = (augmented[row][n] - sum) / augmented[row][row];
+    }
+    Some(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve() {
+        let a = vec![
+            vec![4.0, 2.0, 0.0],
+            vec![2.0, 5.0, 1.0],
+            vec![0.0, 1.0, 3.0],
+        ];
+        let y = super::solve(&a, &[2.0, 1.0, 4.0]).unwrap();
+        for (row, b) in a.iter().zip(&[2.0, 1.0, 4.0]) {
+            let product = row.iter().zip(&y).map(|(a, y)| a * y).sum::<f64>();
+            assert!((product - b).abs() < 1E-12);
+        }
+        assert_eq!(
+            inverse_quadratic_form(&[1.0, 1.0], &[vec![2.0, 0.0], vec![0.0, 4.0]]),
+            Some(0.75)
+        );
+
+        let singular = vec![vec![1.0, 2.0], vec![2.0, 4.0]];
+        assert_eq!(super::solve(&singular, &[1.0, 2.0]), None);
+    }
+}
@@ -1,8 +1,13 @@
 //! Provides traits for statistical computation.
 
+pub use self::contingency_table::*;
 pub use self::ranks::*;
 pub use self::statistics_ext::*;
 
+pub(crate) use self::linear::*;
+
+mod contingency_table;
 mod iter_statistics_ext;
+mod linear;
 mod ranks;
 mod statistics_ext;
@@ -0,0 +1,513 @@
+use crate::distribution::invert;
+use crate::statistics::solve;
+use statrs::distribution::{ContinuousCDF, Normal};
+use statrs::statistics::Statistics;
+use statrs::{Result, StatsError};
+
+use super::StatisticalTest;
+
+/// Family of distributions whose parameters are estimated from the sample in goodness of fit
+/// tests, which changes the null distribution of the statistics (D'Agostino & Stephens,
+/// 1986).
+///
+/// # References
+///
+/// D'Agostino, R. B., & Stephens, M. A. (1986). Goodness-of-Fit Techniques. Marcel Dekker.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DistributionFamily {
+    /// Normal distribution, with the mean and the standard deviation estimated.
+    Normal,
+    /// Exponential distribution, with the mean estimated.
+    Exponential,
+    /// Weibull distribution, with the shape and the scale estimated by maximum likelihood.
+    ///
+    /// Its p-values are interpolated in tables of critical values at the levels from 0.25 to
+    /// 0.01, without extrapolation, so a p-value of 0.25 means at least 0.25 and one of 0.01
+    /// means at most 0.01.
+    Weibull,
+}
+
+/// Implements the [Anderson-Darling test](https://en.wikipedia.org/wiki/Anderson%E2%80%93Darling_test)
+/// of whether samples come from a continuous distribution (Anderson & Darling, 1954), which
+/// weights deviations in the tails more than the Kolmogorov-Smirnov test.
+///
+/// # References
+///
+/// Anderson, T. W., & Darling, D. A. (1954). A Test of Goodness of Fit. Journal of the
+///     American Statistical Association, 49(268), 765–769.
+///     <https://doi.org/10.1080/01621459.1954.10501232>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AndersonDarlingTest {
+    estimate: f64,
+    p_value: f64,
+}
+
+impl AndersonDarlingTest {
+    /// Run the Anderson-Darling test of whether the sample `x` comes from the continuous
+    /// `distribution`, whose parameters must not be estimated from the sample. The p-value
+    /// is accurate for any sample size (Marsaglia & Marsaglia, 2004). The estimate and the
+    /// effect size are the statistic A².
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Normal;
+    /// use stattest::test::{AndersonDarlingTest, StatisticalTest};
+    ///
+    /// let x = vec![-1.2, -0.4, 0.1, 0.3, 0.8, 1.1, -0.7, 0.5, 2.1, -0.2];
+    /// let normal = Normal::new(0.0, 1.0).unwrap();
+    /// let test = AndersonDarlingTest::new(&x, &normal).unwrap();
+    /// assert!(test.p_value() > 0.05);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample is empty or contains NaN.
+    ///
+    /// # References
+    ///
+    /// Marsaglia, G., & Marsaglia, J. (2004). Evaluating the Anderson-Darling Distribution.
+    ///     Journal of Statistical Software, 9(2), 1–5. <https://doi.org/10.18637/jss.v009.i02>
+    pub fn new<D>(x: &[f64], distribution: &D) -> Result<AndersonDarlingTest>
+    where
+        D: ContinuousCDF<f64, f64>,
+    {
+        let z = probabilities(x, |x| distribution.cdf(x))?;
+        let estimate = statistic(&z);
+        Ok(AndersonDarlingTest {
+            estimate,
+            p_value: (1.0 - anderson_darling_cdf(x.len(), estimate)).clamp(0.0, 1.0),
+        })
+    }
+
+    /// Run the Anderson-Darling test of whether the sample `x` comes from some distribution
+    /// of the `family`, with its parameters estimated from the sample. The p-value uses the
+    /// statistic modified for the sample size, with the approximations of D'Agostino and
+    /// Stephens (1986) for the normal and exponential families, and interpolation between the
+    /// critical values of Stephens (1977) for the Weibull family, which bounds the p-value as
+    /// described for [`DistributionFamily::Weibull`]. The estimate and the effect size are the
+    /// unmodified statistic A².
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample has fewer than two observations or no variation, if it
+    /// contains negative observations for the exponential family or non-positive
+    /// observations for the Weibull family, or if it contains NaN.
+    ///
+    /// # References
+    ///
+    /// Stephens, M. A. (1977). Goodness of Fit for the Extreme Value Distribution. Biometrika,
+    ///     64(3), 583–588. <https://doi.org/10.1093/biomet/64.3.583>
+    pub fn fitted(x: &[f64], family: DistributionFamily) -> Result<AndersonDarlingTest> {
+        let z = fitted_probabilities(x, family)?;
+        let estimate = statistic(&z);
+        let n = x.len() as f64;
+
+        let p_value = match family {
+            DistributionFamily::Normal => {
+                let a = estimate * (1.0 + 0.75 / n + 2.25 / (n * n));
+                if a < 0.2 {
+                    1.0 - (-13.436 + 101.14 * a - 223.73 * a * a).exp()
+                } else if a < 0.34 {
+                    1.0 - (-8.318 + 42.796 * a - 59.938 * a * a).exp()
+                } else if a < 0.6 {
+                    (0.9177 - 4.279 * a - 1.38 * a * a).exp()
+                } else {
+                    (1.2937 - 5.709 * a + 0.0186 * a * a).exp()
+                }
+            }
+            DistributionFamily::Exponential => {
+                let a = estimate * (1.0 + 0.6 / n);
+                if a < 0.26 {
+                    1.0 - (-12.2204 + 67.459 * a - 110.3 * a * a).exp()
+                } else if a < 0.51 {
+                    1.0 - (-6.1327 + 20.218 * a - 18.663 * a * a).exp()
+                } else if a < 0.95 {
+                    (0.9209 - 3.353 * a + 0.300 * a * a).exp()
+                } else {
+                    (0.731 - 3.009 * a + 0.15 * a * a).exp()
+                }
+            }
+            DistributionFamily::Weibull => interpolate_p_value(
+                estimate * (1.0 + 0.2 / n.sqrt()),
+                &[0.474, 0.637, 0.757, 0.877, 1.038],
+            ),
+        };
+
+        Ok(AndersonDarlingTest {
+            estimate,
+            p_value: p_value.clamp(0.0, 1.0),
+        })
+    }
+
+    /// Run the k-sample Anderson-Darling test of whether the `samples` come from the same
+    /// continuous distribution, in the version for ties that uses midranks (Scholz & Stephens,
+    /// 1987). The p-value comes from a quadratic in the statistic standardized by its mean and
+    /// variance, fitted to the logarithms of the levels from 0.25 to 0.01 at their critical
+    /// values. Outside that range the quadratic is extrapolated, so p-values above 0.25 or
+    /// below 0.01 are only rough approximations. The estimate and the effect size are the
+    /// unstandardized statistic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::test::{AndersonDarlingTest, StatisticalTest};
+    ///
+    /// let x = vec![38.7, 41.5, 43.8, 44.5, 45.5, 46.0, 47.7, 58.0];
+    /// let y = vec![39.2, 39.3, 39.7, 41.4, 41.8, 42.9, 43.3, 45.8];
+    /// let z = vec![34.0, 34.8, 34.8, 35.4, 37.2, 37.8, 41.2, 42.8];
+    /// let test = AndersonDarlingTest::k_sample(&[&x, &y, &z]).unwrap();
+    /// assert!(test.p_value() < 0.05);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are fewer than two samples, if a sample is empty, if there
+    /// are fewer than four observations, if every observation is equal, or if a sample
+    /// contains NaN.
+    ///
+    /// # References
+    ///
+    /// Scholz, F. W., & Stephens, M. A. (1987). K-Sample Anderson–Darling Tests. Journal of
+    ///     the American Statistical Association, 82(399), 918–924.
+    ///     <https://doi.org/10.1080/01621459.1987.10478517>
+    pub fn k_sample(samples: &[&[f64]]) -> Result<AndersonDarlingTest> {
+        let k = samples.len();
+        if k < 2 || samples.iter().any(|sample| sample.is_empty()) {
+            return Err(StatsError::BadParams);
+        }
+        let mut pooled: Vec<f64> = samples.iter().flat_map(|s| s.iter()).copied().collect();
+        if pooled.len() < 4 || pooled.iter().any(|x| x.is_nan()) {
+            return Err(StatsError::BadParams);
+        }
+        pooled.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mut distinct = pooled.clone();
+        distinct.dedup();
+        if distinct.len() < 2 {
+            return Err(StatsError::BadParams);
+        }
+        let n = pooled.len() as f64;
+
+        // Number of observations of the pooled sample at or below each distinct value, and
+        // equal to it.
+        let mut below = Vec::with_capacity(distinct.len());
+        let mut equal = Vec::with_capacity(distinct.len());
+        let mut start = 0;
+        for value in &distinct {
+            let end = start + pooled[start..].iter().take_while(|x| *x == value).count();
+            below.push(end as f64);
+            equal.push((end - start) as f64);
+            start = end;
+        }
+
+        let mut estimate = 0.0;
+        for sample in samples {
+            let mut sorted = sample.to_vec();
+            sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let n_i = sorted.len() as f64;
+            let mut start = 0;
+            let mut sum = 0.0;
+            for ((value, b), l) in distinct.iter().zip(&below).zip(&equal) {
+                let end = start + sorted[start..].iter().take_while(|x| *x == value).count();
+                let m = end as f64 - (end - start) as f64 / 2.0;
+                let b = b - l / 2.0;
+                sum += l * (n * m - n_i * b).powi(2) / (b * (n - b) - n * l / 4.0);
+                start = end;
+            }
+            estimate += sum / n_i;
+        }
+        estimate *= (n - 1.0) / (n * n);
+
+        let standardized = (estimate - (k - 1) as f64) / k_sample_variance(samples, n).sqrt();
+        let m = (k - 1) as f64;
+        let critical: Vec<f64> = [
+            (0.675, -0.245, -0.105),
+            (1.281, 0.25, -0.305),
+            (1.645, 0.678, -0.362),
+            (1.96, 1.149, -0.391),
+            (2.326, 1.822, -0.396),
+        ]
+        .iter()
+        .map(|(b0, b1, b2)| b0 + b1 / m.sqrt() + b2 / m)
+        .collect();
+
+        Ok(AndersonDarlingTest {
+            estimate,
+            p_value: quadratic_p_value(standardized, &critical)?,
+        })
+    }
+}
+
+impl StatisticalTest for AndersonDarlingTest {
+    type Estimate = f64;
+    type EffectSize = f64;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> f64 {
+        self.estimate
+    }
+}
+
+/// Significance levels of the tables of critical values.
+static LEVELS: [f64; 5] = [0.25, 0.1, 0.05, 0.025, 0.01];
+
+/// Returns the sorted values of the distribution function `cdf` at the observations `x`.
+pub(super) fn probabilities<F>(x: &[f64], cdf: F) -> Result<Vec<f64>>
+where
+    F: Fn(f64) -> f64,
+{
+    if x.is_empty() || x.iter().any(|x| x.is_nan()) {
+        return Err(StatsError::BadParams);
+    }
+    let mut z: Vec<f64> = x.iter().map(|x| cdf(*x)).collect();
+    z.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Ok(z)
+}
+
+/// Returns the sorted values of the distribution function of the `family` at the observations
+/// `x`, with the parameters estimated from `x`.
+pub(super) fn fitted_probabilities(x: &[f64], family: DistributionFamily) -> Result<Vec<f64>> {
+    if x.len() < 2 || x.iter().any(|x| x.is_nan()) {
+        return Err(StatsError::BadParams);
+    }
+    match family {
+        DistributionFamily::Normal => {
+            let normal = Normal::new(x.mean(), x.std_dev())?;
+            probabilities(x, |x| normal.cdf(x))
+        }
+        DistributionFamily::Exponential => {
+            let mean = x.mean();
+            if x.iter().any(|x| *x < 0.0) || mean <= 0.0 {
+                return Err(StatsError::BadParams);
+            }
+            probabilities(x, |x| 1.0 - (-x / mean).exp())
+        }
+        DistributionFamily::Weibull => {
+            let (shape, scale) = weibull_mle(x)?;
+            probabilities(x, |x| 1.0 - (-(x / scale).powf(shape)).exp())
+        }
+    }
+}
+
+/// Returns the maximum likelihood estimates of the shape and the scale of a Weibull
+/// distribution fitted to the positive observations `x`.
+fn weibull_mle(x: &[f64]) -> Result<(f64, f64)> {
+    let max = x.max();
+    if x.iter().any(|x| *x <= 0.0) || x.min() == max {
+        return Err(StatsError::BadParams);
+    }
+    // The likelihood equation of the shape does not depend on the scale, so solve it for the
+    // observations divided by their maximum, which avoids overflow, on a log scale.
+    let ln_y: Vec<f64> = x.iter().map(|x| (x / max).ln()).collect();
+    let mean_ln_y = ln_y.iter().sum::<f64>() / ln_y.len() as f64;
+    let equation = |ln_shape: f64| {
+        let shape = ln_shape.exp();
+        let (weighted, total) = ln_y.iter().fold((0.0, 0.0), |(weighted, total), ln_y| {
+            let power = (shape * ln_y).exp();
+            (weighted + power * ln_y, total + power)
+        });
+        weighted / total - mean_ln_y - 1.0 / shape
+    };
+    let shape = invert(equation, 0.0, f64::NEG_INFINITY, f64::INFINITY, 0.0).exp();
+    let mean_power = ln_y.iter().map(|ln_y| (shape * ln_y).exp()).sum::<f64>() / x.len() as f64;
+    Ok((shape, max * mean_power.powf(1.0 / shape)))
+}
+
+/// Returns the Anderson-Darling statistic of the sorted probabilities `z`.
+fn statistic(z: &[f64]) -> f64 {
+    let n = z.len() as f64;
+    let sum = z
+        .iter()
+        .zip(z.iter().rev())
+        .enumerate()
+        .map(|(i, (low, high))| (2 * i + 1) as f64 * (low.ln() + (1.0 - high).ln()))
+        .sum::<f64>();
+    -n - sum / n
+}
+
+/// Returns the probability that the Anderson-Darling statistic of a sample of size `n` from a
+/// fully specified distribution is less than `z` (Marsaglia & Marsaglia, 2004).
+fn anderson_darling_cdf(n: usize, z: f64) -> f64 {
+    if z <= 0.0 {
+        return 0.0;
+    }
+    let asymptotic = if z < 2.0 {
+        (-1.2337141 / z).exp() / z.sqrt()
+            * (2.00012
+                + (0.247105 - (0.0649821 - (0.0347962 - (0.011672 - 0.00168691 * z) * z) * z) * z)
+                    * z)
+    } else {
+        (-(1.0776
+            - (2.30695 - (0.43424 - (0.082433 - (0.008056 - 0.0003146 * z) * z) * z) * z) * z)
+            .exp())
+        .exp()
+    };
+
+    // Correct the asymptotic distribution for the sample size.
+    let n = n as f64;
+    let x = asymptotic;
+    let c = 0.01265 + 0.1757 / n;
+    let correction = if x > 0.8 {
+        (-130.2137
+            + (745.2337 - (1705.091 - (1950.646 - (1116.360 - 255.7844 * x) * x) * x) * x) * x)
+            / n
+    } else if x < c {
+        let t = x / c;
+        let t = t.sqrt() * (1.0 - t) * (49.0 * t - 102.0);
+        t * (0.0037 / (n * n) + 0.00078 / n + 0.00006) / n
+    } else {
+        let t = (x - c) / (0.8 - c);
+        let t =
+            -0.00022633 + (6.54034 - (14.6538 - (14.458 - (8.259 - 1.91864 * t) * t) * t) * t) * t;
+        t * (0.04213 + 0.01365 / n) / n
+    };
+    x + correction
+}
+
+/// Returns the p-value of a `statistic` by interpolating the logarithm of the significance
+/// level linearly between the `critical` values at the `LEVELS`, without extrapolating beyond
+/// the first and the last level.
+pub(super) fn interpolate_p_value(statistic: f64, critical: &[f64]) -> f64 {
+    if statistic <= critical[0] {
+        return LEVELS[0];
+    }
+    if statistic >= critical[critical.len() - 1] {
+        return LEVELS[LEVELS.len() - 1];
+    }
+    let segment = critical[1..critical.len() - 1]
+        .iter()
+        .take_while(|c| statistic > **c)
+        .count();
+    let (c_0, c_1) = (critical[segment], critical[segment + 1]);
+    let (ln_0, ln_1) = (LEVELS[segment].ln(), LEVELS[segment + 1].ln());
+    (ln_0 + (statistic - c_0) / (c_1 - c_0) * (ln_1 - ln_0)).exp()
+}
+
+/// Returns the p-value of a `statistic` from a quadratic polynomial fitted by least squares
+/// to the logarithms of the `LEVELS` at their `critical` values, as suggested by Scholz and
+/// Stephens (1987).
+fn quadratic_p_value(statistic: f64, critical: &[f64]) -> Result<f64> {
+    let mut a = vec![vec![0.0; 3]; 3];
+    let mut b = vec![0.0; 3];
+    for (c, level) in critical.iter().zip(LEVELS.iter()) {
+        let powers = [1.0, *c, c * c];
+        for ((row, b), p) in a.iter_mut().zip(b.iter_mut()).zip(&powers) {
+            for (a, q) in row.iter_mut().zip(&powers) {
+                *a += p * q;
+            }
+            *b += p * level.ln();
+        }
+    }
+    let coefficients = solve(&a, &b).ok_or(StatsError::BadParams)?;
+    let ln_p = coefficients[0] + coefficients[1] * statistic + coefficients[2] * statistic.powi(2);
+    Ok(ln_p.exp().min(1.0))
+}
+
+/// Returns the variance of the k-sample Anderson-Darling statistic of the `samples` with `n`
+/// observations in total (Scholz & Stephens, 1987).
+fn k_sample_variance(samples: &[&[f64]], n: f64) -> f64 {
+    let k = samples.len() as f64;
+    let big_h = samples.iter().map(|s| 1.0 / s.len() as f64).sum::<f64>();
+    let count = n as usize;
+    let h = (1..count).map(|i| 1.0 / i as f64).sum::<f64>();
+    // g sums 1 / ((n - i) j) over i < j < n, that is 1 / (n - i) times the harmonic sum
+    // from i + 1 to n - 1.
+    let mut g = 0.0;
+    let mut tail = 0.0;
+    for i in (1..count - 1).rev() {
+        tail += 1.0 / (i + 1) as f64;
+        g += tail / (n - i as f64);
+    }
+
+    let a = (4.0 * g - 6.0) * (k - 1.0) + (10.0 - 6.0 * g) * big_h;
+    let b = (2.0 * g - 4.0) * k * k + 8.0 * h * k + (2.0 * g - 14.0 * h - 4.0) * big_h - 8.0 * h
+        + 4.0 * g
+        - 6.0;
+    let c = (6.0 * h + 2.0 * g - 2.0) * k * k
+        + (4.0 * h - 4.0 * g + 6.0) * k
+        + (2.0 * h - 6.0) * big_h
+        + 4.0 * h;
+    let d = (2.0 * h + 6.0) * k * k - 4.0 * h * k;
+    (a * n.powi(3) + b * n * n + c * n + d) / ((n - 1.0) * (n - 2.0) * (n - 3.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use statrs::distribution::Uniform;
+
+    fn sample() -> Vec<f64> {
+        vec![
+            0.61, 0.29, 0.06, 0.59, -1.73, -0.74, 0.51, -0.56, 0.39, 1.64, 0.05, -0.06, 0.64,
+            -0.82, 0.37, 1.77, 1.09, -1.28, 2.36, 1.31, 1.05, -0.32, -0.4, 1.06, -2.47,
+        ]
+    }
+
+    #[test]
+    fn anderson_darling() {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let test = AndersonDarlingTest::new(&sample(), &normal).unwrap();
+        assert_eq!(test.estimate(), 0.9827149859037156);
+        assert_eq!(test.p_value(), 0.3655826609682712);
+
+        let uniform = Uniform::new(-3.0, 3.0).unwrap();
+        let test = AndersonDarlingTest::new(&sample(), &uniform).unwrap();
+        assert_eq!(test.p_value(), 0.06480263990676072);
+
+        assert_eq!(anderson_darling_cdf(10, 2.492), 0.9487555817725932);
+        assert_eq!(anderson_darling_cdf(1000, 3.857), 0.9897511300434936);
+    }
+
+    #[test]
+    fn fitted() {
+        let test = AndersonDarlingTest::fitted(&sample(), DistributionFamily::Normal).unwrap();
+        assert_eq!(test.estimate(), 0.18843457083060144);
+        assert_eq!(test.p_value(), 0.8918563720269443);
+
+        let x = vec![
+            0.12, 0.35, 0.41, 0.77, 0.9, 1.04, 1.38, 1.52, 2.01, 2.43, 2.9, 3.3, 4.1, 5.6,
+        ];
+        let test = AndersonDarlingTest::fitted(&x, DistributionFamily::Exponential).unwrap();
+        assert_eq!(test.estimate(), 0.1867715298300432);
+        assert_eq!(test.p_value(), 0.9618233976693764);
+        let test = AndersonDarlingTest::fitted(&x, DistributionFamily::Weibull).unwrap();
+        assert_eq!(test.estimate(), 0.1045283470632068);
+        assert_eq!(test.p_value(), 0.25);
+        assert_eq!(
+            weibull_mle(&x).unwrap(),
+            (1.2199533184241076, 2.045529604460386)
+        );
+
+        assert!(AndersonDarlingTest::fitted(&sample(), DistributionFamily::Weibull).is_err());
+    }
+
+    #[test]
+    fn k_sample() {
+        let samples = [
+            vec![38.7, 41.5, 43.8, 44.5, 45.5, 46.0, 47.7, 58.0],
+            vec![39.2, 39.3, 39.7, 41.4, 41.8, 42.9, 43.3, 45.8],
+            vec![34.0, 35.0, 39.0, 40.0, 43.0, 43.0, 44.0, 45.0],
+            vec![34.0, 34.8, 34.8, 35.4, 37.2, 37.8, 41.2, 42.8],
+        ];
+        let samples: Vec<&[f64]> = samples.iter().map(|s| s.as_slice()).collect();
+        let test = AndersonDarlingTest::k_sample(&samples).unwrap();
+        assert_eq!(test.estimate(), 8.392609326838489);
+        assert_eq!(test.p_value(), 0.002049105707435112);
+        assert!(AndersonDarlingTest::k_sample(&samples[..1]).is_err());
+    }
+
+    #[test]
+    fn interpolation() {
+        let critical = [0.474, 0.637, 0.757, 0.877, 1.038];
+        assert_eq!(interpolate_p_value(0.1, &critical), 0.25);
+        assert_eq!(interpolate_p_value(0.757, &critical), 0.05000000000000001);
+        assert_eq!(interpolate_p_value(5.0, &critical), 0.01);
+    }
+}
@@ -0,0 +1,341 @@
+use crate::distribution::beta_inverse_cdf;
+use crate::effect_size::{EffectSize, EffectSizeKind, CONFIDENCE_LEVEL};
+use statrs::distribution::{Binomial, ContinuousCDF, Discrete, DiscreteCDF, Normal};
+use statrs::{Result, StatsError};
+
+use super::fisher_exact::TOLERANCE;
+use super::{AlternativeHypothesis, StatisticalTest};
+
+/// Method of computing a [confidence interval of a binomial proportion](https://en.wikipedia.org/wiki/Binomial_proportion_confidence_interval)
+/// (Brown et al., 2001).
+///
+/// # References
+///
+/// Brown, L. D., Cai, T. T., & DasGupta, A. (2001). Interval Estimation for a Binomial
+///     Proportion. Statistical Science, 16(2), 101–133. <https://doi.org/10.1214/ss/1009213286>
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ProportionInterval {
+    /// Exact interval that inverts two one-sided binomial tests (Clopper & Pearson, 1934),
+    /// which is conservative.
+    ///
+    /// Clopper, C. J., & Pearson, E. S. (1934). The Use of Confidence or Fiducial Limits
+    ///     Illustrated in the Case of the Binomial. Biometrika, 26(4), 404–413.
+    ///     <https://doi.org/10.2307/2331986>
+    ClopperPearson,
+    /// Interval that inverts the score test (Wilson, 1927).
+    ///
+    /// Wilson, E. B. (1927). Probable Inference, the Law of Succession, and Statistical
+    ///     Inference. Journal of the American Statistical Association, 22(158), 209–212.
+    ///     <https://doi.org/10.1080/01621459.1927.10502953>
+    Wilson,
+    /// Wald interval around the proportion with z²/2 successes and failures added (Agresti &
+    /// Coull, 1998).
+    ///
+    /// Agresti, A., & Coull, B. A. (1998). Approximate Is Better than "Exact" for Interval
+    ///     Estimation of Binomial Proportions. The American Statistician, 52(2), 119–126.
+    ///     <https://doi.org/10.1080/00031305.1998.10480550>
+    AgrestiCoull,
+    /// Equal-tailed interval of the posterior beta distribution under Jeffreys's prior.
+    Jeffreys,
+}
+
+impl ProportionInterval {
+    /// Returns the two-sided confidence interval at `confidence_level` of the proportion of
+    /// `successes` out of `trials`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::test::ProportionInterval;
+    ///
+    /// let (lower, upper) = ProportionInterval::Wilson.interval(7, 20, 0.95).unwrap();
+    /// assert!(lower < 0.35 && 0.35 < upper);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no trials, if there are more successes than trials, or if
+    /// the confidence level is not between 0 and 1.
+    pub fn interval(
+        &self,
+        successes: usize,
+        trials: usize,
+        confidence_level: f64,
+    ) -> Result<(f64, f64)> {
+        if trials == 0 || successes > trials || !(0.0..1.0).contains(&confidence_level) {
+            return Err(StatsError::BadParams);
+        }
+        let alpha = 1.0 - confidence_level;
+        let (x, n) = (successes as f64, trials as f64);
+        let z = Normal::new(0.0, 1.0)
+            .unwrap()
+            .inverse_cdf(1.0 - alpha / 2.0);
+
+        let interval = match self {
+            ProportionInterval::ClopperPearson => (
+                beta_bound(x, n - x + 1.0, alpha / 2.0, x == 0.0),
+                beta_bound(x + 1.0, n - x, 1.0 - alpha / 2.0, x == n),
+            ),
+            ProportionInterval::Jeffreys => (
+                beta_bound(x + 0.5, n - x + 0.5, alpha / 2.0, x == 0.0),
+                beta_bound(x + 0.5, n - x + 0.5, 1.0 - alpha / 2.0, x == n),
+            ),
+            ProportionInterval::Wilson => {
+                let center = (x + z * z / 2.0) / (n + z * z);
+                let margin = z / (n + z * z) * (x * (n - x) / n + z * z / 4.0).sqrt();
+                (center - margin, center + margin)
+            }
+            ProportionInterval::AgrestiCoull => {
+                let n = n + z * z;
+                let p = (x + z * z / 2.0) / n;
+                let margin = z * (p * (1.0 - p) / n).sqrt();
+                (p - margin, p + margin)
+            }
+        };
+        Ok((interval.0.max(0.0), interval.1.min(1.0)))
+    }
+}
+
+/// Implements the exact [binomial test](https://en.wikipedia.org/wiki/Binomial_test) of the
+/// proportion of successes, and the [sign test](https://en.wikipedia.org/wiki/Sign_test)
+/// that builds on it.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BinomialTest {
+    successes: usize,
+    trials: usize,
+    effect_size: EffectSize,
+    p_value: f64,
+}
+
+impl BinomialTest {
+    /// Run the exact binomial test of whether the proportion of `successes` out of `trials`
+    /// equals `probability`. Two-sided p-values sum the probabilities of all counts that are
+    /// at most as probable as the observed count.
+    ///
+    /// The estimate is the proportion of successes, and the effect size is the same proportion
+    /// with a 95% Clopper-Pearson confidence interval, which is one-sided for one-sided
+    /// alternatives.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::test::{AlternativeHypothesis, BinomialTest, StatisticalTest};
+    ///
+    /// let test = BinomialTest::new(682, 925, 0.75, AlternativeHypothesis::Different).unwrap();
+    /// assert!(test.p_value() > 0.05);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no trials, if there are more successes than trials, or if
+    /// the probability is not between 0 and 1.
+    pub fn new(
+        successes: usize,
+        trials: usize,
+        probability: f64,
+        alternative: AlternativeHypothesis,
+    ) -> Result<BinomialTest> {
+        if trials == 0 || successes > trials || !(0.0..=1.0).contains(&probability) {
+            return Err(StatsError::BadParams);
+        }
+
+        let binomial = Binomial::new(probability, trials as u64)?;
+        let x = successes as u64;
+        let p_value = match alternative {
+            AlternativeHypothesis::Less => binomial.cdf(x),
+            AlternativeHypothesis::Greater => binomial.sf(x) + binomial.pmf(x),
+            AlternativeHypothesis::Different => {
+                let observed = binomial.pmf(x) * (1.0 + TOLERANCE);
+                (0..=trials as u64)
+                    .map(|k| binomial.pmf(k))
+                    .filter(|p| *p <= observed)
+                    .sum()
+            }
+        };
+
+        let alpha = 1.0 - CONFIDENCE_LEVEL;
+        let interval = |confidence_level: f64| {
+            ProportionInterval::ClopperPearson.interval(successes, trials, confidence_level)
+        };
+        let confidence_interval = match alternative {
+            AlternativeHypothesis::Less => (0.0, interval(1.0 - 2.0 * alpha)?.1),
+            AlternativeHypothesis::Greater => (interval(1.0 - 2.0 * alpha)?.0, 1.0),
+            AlternativeHypothesis::Different => interval(CONFIDENCE_LEVEL)?,
+        };
+        let effect_size = EffectSize::new(
+            EffectSizeKind::Proportion,
+            successes as f64 / trials as f64,
+            confidence_interval,
+            CONFIDENCE_LEVEL,
+        );
+
+        Ok(BinomialTest {
+            successes,
+            trials,
+            effect_size,
+            p_value: p_value.min(1.0),
+        })
+    }
+
+    /// Run the sign test of whether the median of the sample `x` equals `median`, as the
+    /// binomial test of the proportion of observations above the median among those that
+    /// differ from it. Unlike the Wilcoxon signed rank test, it does not assume that the
+    /// distribution is symmetric.
+    ///
+    /// The `alternative` hypothesis refers to the median of `x` compared to `median`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if every observation equals the median.
+    pub fn sign(
+        x: &[f64],
+        median: f64,
+        alternative: AlternativeHypothesis,
+    ) -> Result<BinomialTest> {
+        let above = x.iter().filter(|x| **x > median).count();
+        let below = x.iter().filter(|x| **x < median).count();
+        BinomialTest::new(above, above + below, 0.5, alternative)
+    }
+
+    /// Run the sign test on the paired samples `x` and `y`, as the binomial test of the
+    /// proportion of pairs in which `x` is larger among the pairs that differ.
+    ///
+    /// The `alternative` hypothesis refers to the median difference of `x` and `y` compared to
+    /// zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the samples differ in length or if every pair is tied.
+    pub fn sign_paired(
+        x: &[f64],
+        y: &[f64],
+        alternative: AlternativeHypothesis,
+    ) -> Result<BinomialTest> {
+        if x.len() != y.len() {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+        let d: Vec<f64> = x.iter().zip(y).map(|(x, y)| x - y).collect();
+        BinomialTest::sign(&d, 0.0, alternative)
+    }
+
+    /// Returns the number of successes.
+    pub fn successes(&self) -> usize {
+        self.successes
+    }
+
+    /// Returns the number of trials.
+    pub fn trials(&self) -> usize {
+        self.trials
+    }
+
+    /// Returns the two-sided confidence interval of the proportion of successes at
+    /// `confidence_level`, computed with the given `method`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the confidence level is not between 0 and 1.
+    pub fn confidence_interval(
+        &self,
+        method: ProportionInterval,
+        confidence_level: f64,
+    ) -> Result<(f64, f64)> {
+        method.interval(self.successes, self.trials, confidence_level)
+    }
+}
+
+impl StatisticalTest for BinomialTest {
+    type Estimate = f64;
+    type EffectSize = EffectSize;
+
+    fn estimate(&self) -> f64 {
+        self.effect_size.estimate()
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> EffectSize {
+        self.effect_size
+    }
+}
+
+/// Returns the quantile `p` of the beta distribution with shape parameters `a` and `b`, or the
+/// bound of the unit interval on the same side if the bound is `fixed`.
+fn beta_bound(a: f64, b: f64, p: f64, fixed: bool) -> f64 {
+    match (fixed, p < 0.5) {
+        (true, true) => 0.0,
+        (true, false) => 1.0,
+        (false, _) => beta_inverse_cdf(a, b, p),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binomial() {
+        let test = BinomialTest::new(682, 925, 0.75, AlternativeHypothesis::Different).unwrap();
+        assert_eq!(test.estimate(), 0.7372972972972973);
+        assert_eq!(test.p_value(), 0.38249155957513536);
+        assert_eq!(
+            test.effect_size().confidence_interval(),
+            (0.7076682640790363, 0.7654065582415246)
+        );
+
+        let test = BinomialTest::new(682, 925, 0.75, AlternativeHypothesis::Less).unwrap();
+        assert_eq!(test.p_value(), 0.1960092670538993);
+        assert_eq!(
+            test.effect_size().confidence_interval(),
+            (0.0, 0.7610552746895459)
+        );
+
+        let test = BinomialTest::new(682, 925, 0.75, AlternativeHypothesis::Greater).unwrap();
+        assert_eq!(test.p_value(), 0.8240891223523382);
+
+        assert!(BinomialTest::new(5, 4, 0.5, AlternativeHypothesis::Different).is_err());
+    }
+
+    #[test]
+    fn proportion_interval() {
+        let interval = |method: ProportionInterval| method.interval(7, 20, 0.95).unwrap();
+        assert_eq!(
+            interval(ProportionInterval::ClopperPearson),
+            (0.15390920478454032, 0.5921885345328284)
+        );
+        assert_eq!(
+            interval(ProportionInterval::Wilson),
+            (0.1811918241010821, 0.5671457233147637)
+        );
+        assert_eq!(
+            interval(ProportionInterval::AgrestiCoull),
+            (0.17992636143822802, 0.5684111859776177)
+        );
+        assert_eq!(
+            interval(ProportionInterval::Jeffreys),
+            (0.17227621363191067, 0.5677660938414981)
+        );
+
+        let interval = ProportionInterval::ClopperPearson
+            .interval(0, 10, 0.95)
+            .unwrap();
+        assert_eq!(interval, (0.0, 0.3084971078187606));
+    }
+
+    #[test]
+    fn sign() {
+        let x = vec![1.83, 0.50, 1.62, 2.48, 1.68, 1.88, 1.55, 3.06, 1.30];
+        let y = vec![0.878, 0.647, 0.598, 2.05, 1.06, 1.29, 1.06, 3.12, 1.29];
+        let test = BinomialTest::sign_paired(&x, &y, AlternativeHypothesis::Greater).unwrap();
+        assert_eq!(test.successes(), 7);
+        assert_eq!(test.trials(), 9);
+        assert_eq!(test.p_value(), 0.08984375000000065);
+
+        let test = BinomialTest::sign(&x, 1.0, AlternativeHypothesis::Different).unwrap();
+        assert_eq!(test.successes(), 8);
+        assert_eq!(test.p_value(), 0.039062499999999965);
+
+        assert!(BinomialTest::sign(&[1.0, 1.0], 1.0, AlternativeHypothesis::Different).is_err());
+    }
+}
@@ -0,0 +1,440 @@
+use crate::effect_size::{EffectSize, CONFIDENCE_LEVEL};
+use crate::permutation::{Counter, PermutationOptions, PermutationTest, RandomizationDesign};
+use crate::statistics::ContingencyTable;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use statrs::distribution::{ChiSquared, ContinuousCDF};
+use statrs::{Result, StatsError};
+
+use super::{AlternativeHypothesis, StatisticalTest};
+
+/// Implements [Pearson's chi-squared test](https://en.wikipedia.org/wiki/Pearson%27s_chi-squared_test)
+/// of independence and goodness of fit (Pearson, 1900; Agresti, 2013).
+///
+/// # References
+///
+/// Agresti, A. (2013). Categorical Data Analysis (3rd ed.). John Wiley & Sons.
+///
+/// Pearson, K. (1900). On the criterion that a given system of deviations from the probable in
+///     the case of a correlated system of variables is such that it can be reasonably supposed to
+///     have arisen from random sampling. The London, Edinburgh, and Dublin Philosophical Magazine
+///     and Journal of Science, 50(302), 157–175. <https://doi.org/10.1080/14786440009463897>
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChiSquaredTest {
+    df: f64,
+    estimate: f64,
+    effect_size: EffectSize,
+    p_value: f64,
+    residuals: Vec<Vec<f64>>,
+    standardized_residuals: Vec<Vec<f64>>,
+    status: ChiSquaredStatus,
+}
+
+/// Whether the expected counts are large enough for the chi-squared approximation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ChiSquaredStatus {
+    /// All expected counts are at least 1 and at most 20% of them are smaller than 5.
+    Ok,
+    /// Some expected count is smaller than 1, or more than 20% of them are smaller than 5
+    /// (Cochran, 1954), so the p-value may be inaccurate. Consider an exact or Monte Carlo test.
+    ///
+    /// Cochran, W. G. (1954). Some Methods for Strengthening the Common χ² Tests. Biometrics,
+    ///     10(4), 417–451. <https://doi.org/10.2307/3001616>
+    SmallExpectedCounts,
+}
+
+impl ChiSquaredTest {
+    /// Run the chi-squared test of independence of the rows and columns of the `table`.
+    ///
+    /// The effect size is the phi coefficient for 2×2 tables and Cramér's V otherwise, with a
+    /// 95% confidence interval based on the noncentral chi-squared distribution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::statistics::ContingencyTable;
+    /// use stattest::test::{ChiSquaredTest, StatisticalTest};
+    ///
+    /// let table = ContingencyTable::new(vec![vec![20, 15, 5], vec![10, 25, 25]]).unwrap();
+    /// let test = ChiSquaredTest::independence(&table).unwrap();
+    /// assert!(test.p_value() < 0.01);
+    /// ```
+    pub fn independence(table: &ContingencyTable) -> Result<ChiSquaredTest> {
+        ChiSquaredTest::new(table, false)
+    }
+
+    /// Run the chi-squared test of independence of the rows and columns of the 2×2 `table`
+    /// with Yates's continuity correction (Yates, 1934). The correction is limited to the
+    /// absolute deviation of each cell from its expected count.
+    ///
+    /// The effect size is the uncorrected phi coefficient.
+    ///
+    /// # References
+    ///
+    /// Yates, F. (1934). Contingency Tables Involving Small Numbers and the χ² Test. Supplement
+    ///     to the Journal of the Royal Statistical Society, 1(2), 217–235.
+    ///     <https://doi.org/10.2307/2983604>
+    pub fn yates(table: &ContingencyTable) -> Result<ChiSquaredTest> {
+        if table.rows() != 2 || table.columns() != 2 {
+            return Err(StatsError::BadParams);
+        }
+        ChiSquaredTest::new(table, true)
+    }
+
+    /// Run the chi-squared test of independence of the rows and columns of the `table`, with
+    /// the p-value computed from random tables with the same row and column totals (Hope, 1968).
+    /// All tables are enumerated when there are few enough of them.
+    ///
+    /// # References
+    ///
+    /// Hope, A. C. A. (1968). A Simplified Monte Carlo Significance Test Procedure. Journal of
+    ///     the Royal Statistical Society. Series B (Methodological), 30(3), 582–598.
+    ///     <https://doi.org/10.1111/j.2517-6161.1968.tb00759.x>
+    pub fn independence_monte_carlo(
+        table: &ContingencyTable,
+        options: PermutationOptions,
+    ) -> Result<ChiSquaredTest> {
+        let test = ChiSquaredTest::independence(table)?;
+
+        // Every observation is assigned its row as group and its column as value, so that
+        // permuting the groups keeps the row and column totals fixed.
+        let mut rows = Vec::with_capacity(table.total());
+        let mut columns = Vec::with_capacity(table.total());
+        for (i, row) in table.counts().iter().enumerate() {
+            for (j, count) in row.iter().enumerate() {
+                rows.extend(std::iter::repeat_n(i, *count));
+                columns.extend(std::iter::repeat_n(j as f64, *count));
+            }
+        }
+
+        let expected = table.expected();
+        let statistic = |groups: &[&[f64]]| {
+            groups
+                .iter()
+                .zip(&expected)
+                .map(|(group, expected)| {
+                    let mut counts = vec![0.0; expected.len()];
+                    for column in group.iter() {
+                        counts[*column as usize] += 1.0;
+                    }
+                    chi_squared(&counts, expected)
+                })
+                .sum()
+        };
+        let permutation = PermutationTest::randomization(
+            &columns,
+            RandomizationDesign::new(&rows),
+            statistic,
+            options.with_alternative(AlternativeHypothesis::Greater),
+        )?;
+
+        Ok(ChiSquaredTest {
+            p_value: permutation.p_value(),
+            ..test
+        })
+    }
+
+    /// Run the chi-squared goodness-of-fit test of the `observed` counts against the given
+    /// `probabilities` of the categories, which are rescaled to sum to one.
+    ///
+    /// The effect size is Cohen's w, with a 95% confidence interval based on the noncentral
+    /// chi-squared distribution.
+    pub fn goodness_of_fit(observed: &[usize], probabilities: &[f64]) -> Result<ChiSquaredTest> {
+        let expected = goodness_of_fit_expected(observed, probabilities)?;
+        let counts: Vec<f64> = observed.iter().map(|o| *o as f64).collect();
+        let n = counts.iter().sum::<f64>();
+
+        let estimate = chi_squared(&counts, &expected);
+        let df = (observed.len() - 1) as f64;
+        let p_value = ChiSquared::new(df)?.sf(estimate);
+        let effect_size = EffectSize::cohens_w(observed, &expected, CONFIDENCE_LEVEL)?;
+
+        let residuals = pearson_residuals(&counts, &expected);
+        let standardized_residuals = residuals
+            .iter()
+            .zip(&expected)
+            .map(|(r, e)| r / (1.0 - e / n).sqrt())
+            .collect();
+
+        Ok(ChiSquaredTest {
+            df,
+            estimate,
+            effect_size,
+            p_value,
+            residuals: vec![residuals],
+            standardized_residuals: vec![standardized_residuals],
+            status: ChiSquaredStatus::new(expected.iter()),
+        })
+    }
+
+    /// Run the chi-squared goodness-of-fit test of the `observed` counts against the given
+    /// `probabilities` of the categories, with the p-value computed from random multinomial
+    /// samples of the same size. Only the number of resamples and the seed of the `options`
+    /// are used.
+    pub fn goodness_of_fit_monte_carlo(
+        observed: &[usize],
+        probabilities: &[f64],
+        options: PermutationOptions,
+    ) -> Result<ChiSquaredTest> {
+        let test = ChiSquaredTest::goodness_of_fit(observed, probabilities)?;
+        let expected = goodness_of_fit_expected(observed, probabilities)?;
+        let n = observed.iter().sum::<usize>();
+
+        let total = expected.iter().sum::<f64>();
+        let cumulative: Vec<f64> = expected
+            .iter()
+            .scan(0.0, |sum, e| {
+                *sum += e / total;
+                Some(*sum)
+            })
+            .collect();
+
+        let mut rng = StdRng::seed_from_u64(options.seed());
+        let mut counter = Counter::new(AlternativeHypothesis::Greater, test.estimate);
+        let mut counts = vec![0.0; expected.len()];
+        for _ in 0..options.resamples() {
+            counts.iter_mut().for_each(|count| *count = 0.0);
+            for _ in 0..n {
+                let u: f64 = rng.gen();
+                let category = cumulative
+                    .partition_point(|p| *p <= u)
+                    .min(counts.len() - 1);
+                counts[category] += 1.0;
+            }
+            counter.add(chi_squared(&counts, &expected));
+        }
+
+        Ok(ChiSquaredTest {
+            p_value: counter.finish(false).p_value(),
+            ..test
+        })
+    }
+
+    fn new(table: &ContingencyTable, yates: bool) -> Result<ChiSquaredTest> {
+        let expected = table.expected();
+        if expected.iter().flatten().any(|e| *e <= 0.0) {
+            return Err(StatsError::BadParams);
+        }
+
+        let n = table.total() as f64;
+        let row_totals = table.row_totals();
+        let column_totals = table.column_totals();
+        let mut estimate = 0.0;
+        let mut residuals = Vec::with_capacity(table.rows());
+        let mut standardized_residuals = Vec::with_capacity(table.rows());
+        for (i, (row, expected)) in table.counts().iter().zip(&expected).enumerate() {
+            let counts: Vec<f64> = row.iter().map(|o| *o as f64).collect();
+            estimate += if yates {
+                counts
+                    .iter()
+                    .zip(expected)
+                    .map(|(o, e)| {
+                        let deviation = (o - e).abs();
+                        (deviation - deviation.min(0.5)).powi(2) / e
+                    })
+                    .sum()
+            } else {
+                chi_squared(&counts, expected)
+            };
+
+            let row_residuals = pearson_residuals(&counts, expected);
+            let row_proportion = row_totals[i] as f64 / n;
+            standardized_residuals.push(
+                row_residuals
+                    .iter()
+                    .zip(&column_totals)
+                    .map(|(r, column)| {
+                        r / ((1.0 - row_proportion) * (1.0 - *column as f64 / n)).sqrt()
+                    })
+                    .collect(),
+            );
+            residuals.push(row_residuals);
+        }
+
+        let df = ((table.rows() - 1) * (table.columns() - 1)) as f64;
+        let p_value = ChiSquared::new(df)?.sf(estimate);
+        let effect_size = if table.rows() == 2 && table.columns() == 2 {
+            EffectSize::phi(table, CONFIDENCE_LEVEL)?
+        } else {
+            EffectSize::cramers_v(table, CONFIDENCE_LEVEL)?
+        };
+
+        Ok(ChiSquaredTest {
+            df,
+            estimate,
+            effect_size,
+            p_value,
+            residuals,
+            standardized_residuals,
+            status: ChiSquaredStatus::new(expected.iter().flatten()),
+        })
+    }
+
+    /// Returns the degrees of freedom.
+    pub fn df(&self) -> f64 {
+        self.df
+    }
+
+    /// Returns the Pearson residuals `(o - e) / sqrt(e)` of every cell, with a single row for
+    /// goodness-of-fit tests.
+    pub fn residuals(&self) -> &[Vec<f64>] {
+        &self.residuals
+    }
+
+    /// Returns the standardized (adjusted) residuals of every cell, which are approximately
+    /// standard normal under the null hypothesis (Haberman, 1973), with a single row for
+    /// goodness-of-fit tests.
+    ///
+    /// # References
+    ///
+    /// Haberman, S. J. (1973). The Analysis of Residuals in Cross-Classified Tables. Biometrics,
+    ///     29(1), 205–220. <https://doi.org/10.2307/2529686>
+    pub fn standardized_residuals(&self) -> &[Vec<f64>] {
+        &self.standardized_residuals
+    }
+
+    /// Returns whether the expected counts are large enough for the chi-squared approximation.
+    pub fn status(&self) -> ChiSquaredStatus {
+        self.status
+    }
+}
+
+impl ChiSquaredStatus {
+    fn new<'a>(expected: impl Iterator<Item = &'a f64>) -> ChiSquaredStatus {
+        let (mut cells, mut small) = (0, 0);
+        for e in expected {
+            if *e < 1.0 {
+                return ChiSquaredStatus::SmallExpectedCounts;
+            }
+            if *e < 5.0 {
+                small += 1;
+            }
+            cells += 1;
+        }
+        if 5 * small > cells {
+            ChiSquaredStatus::SmallExpectedCounts
+        } else {
+            ChiSquaredStatus::Ok
+        }
+    }
+}
+
+impl StatisticalTest for ChiSquaredTest {
+    type Estimate = f64;
+    type EffectSize = EffectSize;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> EffectSize {
+        self.effect_size
+    }
+}
+
+/// Returns the expected counts of the `observed` categories under the given `probabilities`.
+pub(super) fn goodness_of_fit_expected(
+    observed: &[usize],
+    probabilities: &[f64],
+) -> Result<Vec<f64>> {
+    if observed.len() != probabilities.len() {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    if observed.len() < 2 || probabilities.iter().any(|p| !p.is_finite() || *p <= 0.0) {
+        return Err(StatsError::BadParams);
+    }
+    let n = observed.iter().sum::<usize>() as f64;
+    if n == 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    let total = probabilities.iter().sum::<f64>();
+    Ok(probabilities.iter().map(|p| n * p / total).collect())
+}
+
+/// Returns Pearson's chi-squared statistic of the `observed` counts.
+fn chi_squared(observed: &[f64], expected: &[f64]) -> f64 {
+    observed
+        .iter()
+        .zip(expected)
+        .map(|(o, e)| (o - e).powi(2) / e)
+        .sum()
+}
+
+/// Returns the Pearson residuals of the `observed` counts.
+fn pearson_residuals(observed: &[f64], expected: &[f64]) -> Vec<f64> {
+    observed
+        .iter()
+        .zip(expected)
+        .map(|(o, e)| (o - e) / e.sqrt())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect_size::EffectSizeKind;
+
+    #[test]
+    fn independence() {
+        let table = ContingencyTable::new(vec![vec![20, 15, 5], vec![10, 25, 25]]).unwrap();
+        let test = ChiSquaredTest::independence(&table).unwrap();
+        assert_eq!(test.df(), 2.0);
+        assert_eq!(test.estimate(), 15.79861111111111);
+        assert_eq!(test.p_value(), 0.00037100109066787965);
+        assert_eq!(test.residuals()[0][0], 2.3094010767585034);
+        assert_eq!(test.standardized_residuals()[0][0], 3.5634832254989925);
+        assert_eq!(test.status(), ChiSquaredStatus::Ok);
+        assert_eq!(test.effect_size().kind(), EffectSizeKind::CramersV);
+    }
+
+    #[test]
+    fn yates() {
+        let table = ContingencyTable::new(vec![vec![3, 12], vec![9, 4]]).unwrap();
+        let test = ChiSquaredTest::yates(&table).unwrap();
+        assert_eq!(test.estimate(), 5.02863247863248);
+        assert_eq!(test.p_value(), 0.024931576731126257);
+        assert_eq!(test.status(), ChiSquaredStatus::Ok);
+        assert_eq!(test.effect_size().kind(), EffectSizeKind::Phi);
+
+        let table = ContingencyTable::new(vec![vec![3, 1, 2], vec![1, 3, 2]]).unwrap();
+        assert!(ChiSquaredTest::yates(&table).is_err());
+        let test = ChiSquaredTest::independence(&table).unwrap();
+        assert_eq!(test.status(), ChiSquaredStatus::SmallExpectedCounts);
+    }
+
+    #[test]
+    fn independence_monte_carlo() {
+        let table = ContingencyTable::new(vec![vec![3, 1, 2], vec![1, 3, 2]]).unwrap();
+        let test = ChiSquaredTest::independence_monte_carlo(&table, PermutationOptions::default())
+            .unwrap();
+        assert_eq!(test.p_value(), 0.7662337662337663);
+
+        let table = ContingencyTable::new(vec![vec![20, 15, 5], vec![10, 25, 25]]).unwrap();
+        let test = ChiSquaredTest::independence_monte_carlo(&table, PermutationOptions::default())
+            .unwrap();
+        assert_eq!(test.p_value(), 0.0005);
+    }
+
+    #[test]
+    fn goodness_of_fit() {
+        let test = ChiSquaredTest::goodness_of_fit(&[30, 20, 50], &[1.0, 1.0, 2.0]).unwrap();
+        assert_eq!(test.df(), 2.0);
+        assert_eq!(test.estimate(), 2.0);
+        assert_eq!(test.p_value(), 0.3678794411714419);
+        assert_eq!(test.standardized_residuals()[0][0], 1.1547005383792517);
+        assert_eq!(test.effect_size().kind(), EffectSizeKind::CohensW);
+
+        let test = ChiSquaredTest::goodness_of_fit_monte_carlo(
+            &[30, 20, 50],
+            &[1.0, 1.0, 2.0],
+            PermutationOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(test.p_value(), 0.3608);
+        assert!(ChiSquaredTest::goodness_of_fit(&[30, 20], &[1.0, 1.0, 2.0]).is_err());
+    }
+}
@@ -0,0 +1,236 @@
+use crate::effect_size::{EffectSize, EffectSizeKind, CONFIDENCE_LEVEL};
+use crate::permutation::{PermutationOptions, PermutationTest};
+use crate::statistics::ContingencyTable;
+use statrs::distribution::{ContinuousCDF, Normal};
+use statrs::{Result, StatsError};
+
+use super::{AlternativeHypothesis, StatisticalTest};
+
+/// Implements the [Cochran-Armitage test](https://en.wikipedia.org/wiki/Cochran%E2%80%93Armitage_test_for_trend)
+/// for a linear trend in proportions across ordered groups (Cochran, 1954; Armitage, 1955).
+///
+/// # References
+///
+/// Armitage, P. (1955). Tests for Linear Trends in Proportions and Frequencies. Biometrics,
+///     11(3), 375–386. <https://doi.org/10.2307/3001775>
+///
+/// Cochran, W. G. (1954). Some Methods for Strengthening the Common χ² Tests. Biometrics,
+///     10(4), 417–451. <https://doi.org/10.2307/3001616>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CochranArmitageTest {
+    estimate: f64,
+    effect_size: EffectSize,
+    p_value: f64,
+}
+
+impl CochranArmitageTest {
+    /// Run the Cochran-Armitage test on the k×2 `table`, where the rows are the ordered groups
+    /// with the given `scores`, such as doses, and the columns are the counts of successes and
+    /// failures. Equally spaced scores like 0, 1, …, k − 1 suit groups without a natural
+    /// scale. The `alternative` hypothesis refers to the slope of the proportions over the
+    /// scores compared to zero.
+    ///
+    /// The estimate is the z statistic, and the effect size is the slope of the least squares
+    /// line of the proportions over the scores, weighted by the sizes of the groups, with a
+    /// 95% confidence interval based on the variance under the null hypothesis.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::statistics::ContingencyTable;
+    /// use stattest::test::{AlternativeHypothesis, CochranArmitageTest, StatisticalTest};
+    ///
+    /// let table = ContingencyTable::new(vec![vec![2, 48], vec![5, 45], vec![11, 39]]).unwrap();
+    /// let scores = vec![0.0, 1.0, 2.0];
+    /// let test = CochranArmitageTest::new(&table, &scores, AlternativeHypothesis::Greater)
+    ///     .unwrap();
+    /// assert!(test.p_value() < 0.05);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the table does not have two columns, if the number of scores does
+    /// not match the number of rows, if the scores of the non-empty rows are all equal, or if
+    /// every count is a success or every count is a failure.
+    pub fn new(
+        table: &ContingencyTable,
+        scores: &[f64],
+        alternative: AlternativeHypothesis,
+    ) -> Result<CochranArmitageTest> {
+        let trend = Trend::new(table, scores)?;
+        let normal = Normal::new(0.0, 1.0)?;
+        let estimate = trend.z();
+        let p_value = match alternative {
+            AlternativeHypothesis::Less => normal.cdf(estimate),
+            AlternativeHypothesis::Greater => normal.sf(estimate),
+            AlternativeHypothesis::Different => 2.0 * normal.sf(estimate.abs()),
+        };
+
+        Ok(CochranArmitageTest {
+            estimate,
+            effect_size: trend.slope()?,
+            p_value,
+        })
+    }
+
+    /// Run the Cochran-Armitage test on the k×2 `table` with the given `scores`, with the
+    /// p-value computed by permuting the outcomes across the groups, which conditions on the
+    /// sizes of the groups and the total number of successes. All permutations are enumerated
+    /// when there are few enough of them, as set in the `options`, and the alternative
+    /// hypothesis of the `options` refers to the slope of the proportions.
+    pub fn permutation(
+        table: &ContingencyTable,
+        scores: &[f64],
+        options: PermutationOptions,
+    ) -> Result<CochranArmitageTest> {
+        let test = CochranArmitageTest::new(table, scores, options.alternative())?;
+
+        let mut successes = Vec::new();
+        let mut failures = Vec::new();
+        for (row, score) in table.counts().iter().zip(scores) {
+            successes.extend(std::iter::repeat_n(*score, row[0]));
+            failures.extend(std::iter::repeat_n(*score, row[1]));
+        }
+        // The total of the scores is fixed by the permutations, so the sum of the scores of
+        // the successes, centered at its expectation, orders them like the z statistic.
+        let total = successes.iter().chain(&failures).sum::<f64>();
+        let fraction = successes.len() as f64 / table.total() as f64;
+        let statistic = |x: &[f64], _: &[f64]| x.iter().sum::<f64>() - fraction * total;
+        let permutation = PermutationTest::two_sample(&successes, &failures, statistic, options)?;
+
+        Ok(CochranArmitageTest {
+            p_value: permutation.p_value(),
+            ..test
+        })
+    }
+}
+
+impl StatisticalTest for CochranArmitageTest {
+    type Estimate = f64;
+    type EffectSize = EffectSize;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> EffectSize {
+        self.effect_size
+    }
+}
+
+/// Sums of a k×2 table with scores that make up the statistic of the trend test.
+struct Trend {
+    /// Sum of the scores of the successes, centered at its expectation.
+    numerator: f64,
+    /// Sum of the squared deviations of the scores from their mean over all observations.
+    sum_of_squares: f64,
+    /// Proportion of successes over all groups.
+    proportion: f64,
+}
+
+impl Trend {
+    fn new(table: &ContingencyTable, scores: &[f64]) -> Result<Trend> {
+        if table.columns() != 2 {
+            return Err(StatsError::BadParams);
+        }
+        if table.rows() != scores.len() {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+        let n = table.total() as f64;
+        let proportion = table.column_totals()[0] as f64 / n;
+        let sizes: Vec<f64> = table.row_totals().iter().map(|n| *n as f64).collect();
+        let mean = sizes.iter().zip(scores).map(|(n, s)| n * s).sum::<f64>() / n;
+
+        let numerator = table
+            .counts()
+            .iter()
+            .zip(&sizes)
+            .zip(scores)
+            .map(|((row, n), s)| (row[0] as f64 - n * proportion) * (s - mean))
+            .sum::<f64>();
+        let sum_of_squares = sizes
+            .iter()
+            .zip(scores)
+            .map(|(n, s)| n * (s - mean).powi(2))
+            .sum::<f64>();
+
+        let variance = proportion * (1.0 - proportion) * sum_of_squares;
+        if variance.is_nan() || variance <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        Ok(Trend {
+            numerator,
+            sum_of_squares,
+            proportion,
+        })
+    }
+
+    fn z(&self) -> f64 {
+        let variance = self.proportion * (1.0 - self.proportion) * self.sum_of_squares;
+        self.numerator / variance.sqrt()
+    }
+
+    fn slope(&self) -> Result<EffectSize> {
+        let slope = self.numerator / self.sum_of_squares;
+        let standard_error =
+            (self.proportion * (1.0 - self.proportion) / self.sum_of_squares).sqrt();
+        let z = Normal::new(0.0, 1.0)?.inverse_cdf((1.0 + CONFIDENCE_LEVEL) / 2.0);
+        Ok(EffectSize::new(
+            EffectSizeKind::ProportionTrend,
+            slope,
+            (slope - z * standard_error, slope + z * standard_error),
+            CONFIDENCE_LEVEL,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> ContingencyTable {
+        ContingencyTable::new(vec![vec![15, 17], vec![10, 22], vec![7, 27], vec![3, 29]]).unwrap()
+    }
+
+    #[test]
+    fn cochran_armitage() {
+        let scores = vec![0.0, 1.0, 2.0, 3.0];
+        let test =
+            CochranArmitageTest::new(&table(), &scores, AlternativeHypothesis::Different).unwrap();
+        assert_eq!(test.estimate(), -3.5181162644105504);
+        assert_eq!(test.p_value(), 0.00043462189844180955);
+        assert_eq!(test.effect_size().estimate(), -0.12317868098159508);
+        assert_eq!(
+            test.effect_size().confidence_interval(),
+            (-0.19180227390527163, -0.05455508805791853)
+        );
+
+        let test =
+            CochranArmitageTest::new(&table(), &scores, AlternativeHypothesis::Less).unwrap();
+        assert_eq!(test.p_value(), 0.00021731094922090478);
+
+        assert!(
+            CochranArmitageTest::new(&table(), &scores[..3], AlternativeHypothesis::Less).is_err()
+        );
+        assert!(
+            CochranArmitageTest::new(&table(), &[1.0; 4], AlternativeHypothesis::Less).is_err()
+        );
+    }
+
+    #[test]
+    fn permutation() {
+        let table = ContingencyTable::new(vec![vec![0, 5], vec![1, 4], vec![4, 1]]).unwrap();
+        let scores = vec![1.0, 2.0, 4.0];
+        let options =
+            PermutationOptions::default().with_alternative(AlternativeHypothesis::Greater);
+        let test = CochranArmitageTest::permutation(&table, &scores, options).unwrap();
+        assert_eq!(test.p_value(), 0.008658008658008658);
+        let test =
+            CochranArmitageTest::new(&table, &scores, AlternativeHypothesis::Greater).unwrap();
+        assert_eq!(test.p_value(), 0.0027069571438618164);
+    }
+}
@@ -0,0 +1,256 @@
+use crate::effect_size::{EffectSize, EffectSizeKind, CONFIDENCE_LEVEL};
+use crate::permutation::{PermutationOptions, PermutationTest, RandomizationDesign};
+use crate::statistics::ContingencyTable;
+use statrs::distribution::{ChiSquared, ContinuousCDF};
+use statrs::{Result, StatsError};
+
+use super::{AlternativeHypothesis, McNemarMethod, McNemarTest, StatisticalTest};
+
+/// Implements [Cochran's Q test](https://en.wikipedia.org/wiki/Cochran%27s_Q_test) of
+/// whether k related binary samples have the same proportion of successes (Cochran, 1950).
+///
+/// # References
+///
+/// Cochran, W. G. (1950). The Comparison of Percentages in Matched Samples. Biometrika,
+///     37(3/4), 256–266. <https://doi.org/10.2307/2332378>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CochranQTest {
+    df: f64,
+    estimate: f64,
+    effect_size: EffectSize,
+    p_value: f64,
+}
+
+impl CochranQTest {
+    /// Run Cochran's Q test on the `samples`, which hold the binary outcome of every subject
+    /// under each condition, in the same order of subjects.
+    ///
+    /// The effect size is the eta squared of Serlin et al. (1982), the statistic divided by
+    /// its maximum, with a 95% confidence interval based on the noncentral chi-squared
+    /// distribution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::test::{CochranQTest, StatisticalTest};
+    ///
+    /// let a = vec![true, true, false, true, true, true, false, true];
+    /// let b = vec![true, false, false, false, true, false, false, true];
+    /// let c = vec![false, false, false, false, true, false, false, false];
+    /// let test = CochranQTest::new(&[&a, &b, &c]).unwrap();
+    /// assert!(test.p_value() < 0.05);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are fewer than two samples, if the samples differ in length,
+    /// or if every subject has the same outcome under all conditions.
+    ///
+    /// # References
+    ///
+    /// Serlin, R. C., Carr, J., & Marascuilo, L. A. (1982). A Measure of Association for
+    ///     Selected Nonparametric Procedures. Psychological Bulletin, 92(3), 786–790.
+    ///     <https://doi.org/10.1037/0033-2909.92.3.786>
+    pub fn new(samples: &[&[bool]]) -> Result<CochranQTest> {
+        let (n, k) = dimensions(samples)?;
+        let estimate = q(samples);
+        if !estimate.is_finite() {
+            return Err(StatsError::BadParams);
+        }
+
+        let df = (k - 1) as f64;
+        let p_value = ChiSquared::new(df)?.sf(estimate);
+        let w = EffectSize::from_chi_squared(
+            EffectSizeKind::EtaSquared,
+            estimate,
+            df,
+            n as f64 * df,
+            CONFIDENCE_LEVEL,
+        )?;
+        let (lower, upper) = w.confidence_interval();
+        let effect_size = EffectSize::new(
+            EffectSizeKind::EtaSquared,
+            w.estimate().powi(2),
+            (lower.powi(2), upper.powi(2)),
+            CONFIDENCE_LEVEL,
+        );
+
+        Ok(CochranQTest {
+            df,
+            estimate,
+            effect_size,
+            p_value,
+        })
+    }
+
+    /// Run Cochran's Q test on the `samples`, with the p-value computed by permuting the
+    /// outcomes of every subject across the conditions. All permutations are enumerated when
+    /// there are few enough of them, as set in the `options`, and the alternative hypothesis
+    /// of the `options` is ignored.
+    pub fn randomization(samples: &[&[bool]], options: PermutationOptions) -> Result<CochranQTest> {
+        let test = CochranQTest::new(samples)?;
+        let (n, k) = dimensions(samples)?;
+
+        let mut values = Vec::with_capacity(n * k);
+        let mut groups = Vec::with_capacity(n * k);
+        let mut subjects = Vec::with_capacity(n * k);
+        for (condition, sample) in samples.iter().enumerate() {
+            for (subject, outcome) in sample.iter().enumerate() {
+                values.push(if *outcome { 1.0 } else { 0.0 });
+                groups.push(condition);
+                subjects.push(subject);
+            }
+        }
+
+        // The totals of the subjects are fixed by the permutations, so Q only depends on the
+        // sum of the squared totals of the conditions.
+        let statistic = |groups: &[&[f64]]| {
+            groups
+                .iter()
+                .map(|group| group.iter().sum::<f64>().powi(2))
+                .sum()
+        };
+        let permutation = PermutationTest::randomization(
+            &values,
+            RandomizationDesign::new(&groups).with_strata(&subjects),
+            statistic,
+            options.with_alternative(AlternativeHypothesis::Greater),
+        )?;
+
+        Ok(CochranQTest {
+            p_value: permutation.p_value(),
+            ..test
+        })
+    }
+
+    /// Run McNemar's test with the given `method` on every pair of the `samples`, as a
+    /// follow-up to Cochran's Q test. Returns the indices of the samples of every pair with
+    /// its test, in lexicographic order. The p-values are not adjusted for multiple testing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are fewer than two samples, if the samples differ in length,
+    /// or if a pair of samples has no discordant pairs.
+    pub fn pairwise(
+        samples: &[&[bool]],
+        method: McNemarMethod,
+    ) -> Result<Vec<(usize, usize, McNemarTest)>> {
+        let (_, k) = dimensions(samples)?;
+        let mut tests = Vec::with_capacity(k * (k - 1) / 2);
+        for i in 0..k {
+            for j in i + 1..k {
+                let table = paired_table(samples[i], samples[j])?;
+                tests.push((i, j, McNemarTest::new(&table, method)?));
+            }
+        }
+        Ok(tests)
+    }
+
+    /// Returns the degrees of freedom.
+    pub fn df(&self) -> f64 {
+        self.df
+    }
+}
+
+impl StatisticalTest for CochranQTest {
+    type Estimate = f64;
+    type EffectSize = EffectSize;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> EffectSize {
+        self.effect_size
+    }
+}
+
+/// Returns the number of subjects and the number of conditions of the `samples`.
+fn dimensions(samples: &[&[bool]]) -> Result<(usize, usize)> {
+    if samples.len() < 2 {
+        return Err(StatsError::BadParams);
+    }
+    let n = samples[0].len();
+    if samples.iter().any(|sample| sample.len() != n) {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    Ok((n, samples.len()))
+}
+
+/// Returns Cochran's Q statistic of the `samples`, which is not finite if every subject has
+/// the same outcome under all conditions.
+fn q(samples: &[&[bool]]) -> f64 {
+    let k = samples.len() as f64;
+    let n = samples[0].len();
+    let conditions: Vec<f64> = samples
+        .iter()
+        .map(|sample| sample.iter().filter(|outcome| **outcome).count() as f64)
+        .collect();
+    let subjects: Vec<f64> = (0..n)
+        .map(|i| samples.iter().filter(|sample| sample[i]).count() as f64)
+        .collect();
+    let total = conditions.iter().sum::<f64>();
+
+    let squares = |totals: &[f64]| totals.iter().map(|t| t * t).sum::<f64>();
+
+    let numerator = (k - 1.0) * (k * squares(&conditions) - total * total);
+    let denominator = k * total - squares(&subjects);
+    numerator / denominator
+}
+
+/// Returns the 2×2 table of the paired outcomes `x` and `y`, with successes first.
+fn paired_table(x: &[bool], y: &[bool]) -> Result<ContingencyTable> {
+    let mut counts = vec![vec![0; 2]; 2];
+    for (x, y) in x.iter().zip(y) {
+        counts[usize::from(!*x)][usize::from(!*y)] += 1;
+    }
+    ContingencyTable::new(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples() -> Vec<Vec<bool>> {
+        let parse = |s: &str| s.chars().map(|c| c == '1').collect();
+        vec![
+            parse("111011101111"),
+            parse("101001000110"),
+            parse("000001000100"),
+        ]
+    }
+
+    #[test]
+    fn cochran_q() {
+        let samples = samples();
+        let samples: Vec<&[bool]> = samples.iter().map(|s| s.as_slice()).collect();
+        let test = CochranQTest::new(&samples).unwrap();
+        assert_eq!(test.df(), 2.0);
+        assert_eq!(test.estimate(), 12.25);
+        assert_eq!(test.p_value(), 0.002187491118182885);
+        assert_eq!(test.effect_size().estimate(), 0.5104166666666666);
+
+        let test = CochranQTest::randomization(&samples, PermutationOptions::default()).unwrap();
+        assert_eq!(test.p_value(), 0.0016);
+
+        let constant = vec![true; 12];
+        assert!(CochranQTest::new(&[&constant, &constant]).is_err());
+        assert!(CochranQTest::new(&samples[..1]).is_err());
+    }
+
+    #[test]
+    fn pairwise() {
+        let samples = samples();
+        let samples: Vec<&[bool]> = samples.iter().map(|s| s.as_slice()).collect();
+        let tests = CochranQTest::pairwise(&samples, McNemarMethod::Exact).unwrap();
+        assert_eq!(tests.len(), 3);
+        assert_eq!((tests[0].0, tests[0].1), (0, 1));
+        assert_eq!(tests[0].2.estimate(), 5.0);
+        assert_eq!(tests[0].2.p_value(), 0.062499999999999535);
+        assert_eq!(tests[2].2.p_value(), 0.24999999999999878);
+    }
+}
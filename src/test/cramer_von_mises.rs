@@ -0,0 +1,241 @@
+use statrs::distribution::ContinuousCDF;
+use statrs::Result;
+use std::f64::consts::PI;
+
+use super::anderson_darling::{fitted_probabilities, interpolate_p_value, probabilities};
+use super::{DistributionFamily, StatisticalTest};
+
+/// Implements the [Cramér-von Mises test](https://en.wikipedia.org/wiki/Cram%C3%A9r%E2%80%93von_Mises_criterion)
+/// of whether samples come from a continuous distribution, whose statistic W² integrates the
+/// squared deviations of the empirical distribution function over the distribution.
+///
+/// # References
+///
+/// Anderson, T. W., & Darling, D. A. (1952). Asymptotic Theory of Certain "Goodness of Fit"
+///     Criteria Based on Stochastic Processes. The Annals of Mathematical Statistics, 23(2),
+///     193–212. <https://doi.org/10.1214/aoms/1177729437>
+///
+/// D'Agostino, R. B., & Stephens, M. A. (1986). Goodness-of-Fit Techniques. Marcel Dekker.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CramerVonMisesTest {
+    estimate: f64,
+    p_value: f64,
+}
+
+impl CramerVonMisesTest {
+    /// Run the Cramér-von Mises test of whether the sample `x` comes from the continuous
+    /// `distribution`, whose parameters must not be estimated from the sample. The p-value
+    /// uses the asymptotic distribution of the statistic modified for the sample size by
+    /// Stephens (1970). The estimate and the effect size are the statistic W².
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Normal;
+    /// use stattest::test::{CramerVonMisesTest, StatisticalTest};
+    ///
+    /// let x = vec![-1.2, -0.4, 0.1, 0.3, 0.8, 1.1, -0.7, 0.5, 2.1, -0.2];
+    /// let normal = Normal::new(0.0, 1.0).unwrap();
+    /// let test = CramerVonMisesTest::new(&x, &normal).unwrap();
+    /// assert!(test.p_value() > 0.05);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample is empty or contains NaN.
+    ///
+    /// # References
+    ///
+    /// Stephens, M. A. (1970). Use of the Kolmogorov-Smirnov, Cramer-Von Mises and Related
+    ///     Statistics Without Extensive Tables. Journal of the Royal Statistical Society.
+    ///     Series B (Methodological), 32(1), 115–122.
+    ///     <https://doi.org/10.1111/j.2517-6161.1970.tb00821.x>
+    pub fn new<D>(x: &[f64], distribution: &D) -> Result<CramerVonMisesTest>
+    where
+        D: ContinuousCDF<f64, f64>,
+    {
+        let z = probabilities(x, |x| distribution.cdf(x))?;
+        let estimate = statistic(&z);
+        let n = x.len() as f64;
+        let modified = ((estimate - 0.4 / n + 0.6 / (n * n)) * (1.0 + 1.0 / n)).max(0.0);
+        Ok(CramerVonMisesTest {
+            estimate,
+            p_value: (1.0 - cramer_von_mises_cdf(modified)).clamp(0.0, 1.0),
+        })
+    }
+
+    /// Run the Cramér-von Mises test of whether the sample `x` comes from some distribution
+    /// of the `family`, with its parameters estimated from the sample. The p-value uses the
+    /// statistic modified for the sample size, with the approximations of D'Agostino and
+    /// Stephens (1986) for the normal and exponential families, and interpolation between the
+    /// critical values of Stephens (1977) for the Weibull family, which bounds the p-value as
+    /// described for [`DistributionFamily::Weibull`]. The estimate and the effect size are the
+    /// unmodified statistic W².
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample has fewer than two observations or no variation, if it
+    /// contains negative observations for the exponential family or non-positive
+    /// observations for the Weibull family, or if it contains NaN.
+    ///
+    /// # References
+    ///
+    /// Stephens, M. A. (1977). Goodness of Fit for the Extreme Value Distribution. Biometrika,
+    ///     64(3), 583–588. <https://doi.org/10.1093/biomet/64.3.583>
+    pub fn fitted(x: &[f64], family: DistributionFamily) -> Result<CramerVonMisesTest> {
+        let z = fitted_probabilities(x, family)?;
+        let estimate = statistic(&z);
+        let n = x.len() as f64;
+
+        let p_value = match family {
+            DistributionFamily::Normal => {
+                let w = estimate * (1.0 + 0.5 / n);
+                if w < 0.0275 {
+                    1.0 - (-13.953 + 775.5 * w - 12542.61 * w * w).exp()
+                } else if w < 0.051 {
+                    1.0 - (-5.903 + 179.546 * w - 1515.29 * w * w).exp()
+                } else if w < 0.092 {
+                    (0.886 - 31.62 * w + 10.897 * w * w).exp()
+                } else {
+                    (1.111 - 34.242 * w + 12.832 * w * w).exp()
+                }
+            }
+            DistributionFamily::Exponential => {
+                let w = estimate * (1.0 + 0.16 / n);
+                if w < 0.035 {
+                    1.0 - (-11.334 + 459.098 * w - 5652.1 * w * w).exp()
+                } else if w < 0.074 {
+                    1.0 - (-5.779 + 132.89 * w - 866.58 * w * w).exp()
+                } else if w < 0.16 {
+                    (0.586 - 17.87 * w + 7.417 * w * w).exp()
+                } else {
+                    (0.447 - 16.592 * w + 4.849 * w * w).exp()
+                }
+            }
+            DistributionFamily::Weibull => interpolate_p_value(
+                estimate * (1.0 + 0.2 / n.sqrt()),
+                &[0.073, 0.102, 0.124, 0.146, 0.175],
+            ),
+        };
+
+        Ok(CramerVonMisesTest {
+            estimate,
+            p_value: p_value.clamp(0.0, 1.0),
+        })
+    }
+}
+
+impl StatisticalTest for CramerVonMisesTest {
+    type Estimate = f64;
+    type EffectSize = f64;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> f64 {
+        self.estimate
+    }
+}
+
+/// Returns the Cramér-von Mises statistic of the sorted probabilities `z`.
+fn statistic(z: &[f64]) -> f64 {
+    let n = z.len() as f64;
+    let sum = z
+        .iter()
+        .enumerate()
+        .map(|(i, z)| (z - (2 * i + 1) as f64 / (2.0 * n)).powi(2))
+        .sum::<f64>();
+    1.0 / (12.0 * n) + sum
+}
+
+/// Returns the asymptotic probability that the Cramér-von Mises statistic is less than `x`,
+/// from the series of Anderson and Darling (1952).
+fn cramer_von_mises_cdf(x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    let mut sum = 0.0;
+    // Γ(j + 1/2) / (Γ(1/2) j!), updated at each term.
+    let mut coefficient = 1.0;
+    for j in 0..50 {
+        let m = (4 * j + 1) as f64;
+        let u = m * m / (16.0 * x);
+        let term = coefficient * m.sqrt() * scaled_bessel_k(u);
+        sum += term;
+        if term <= 1E-16 * sum {
+            break;
+        }
+        coefficient *= (2 * j + 1) as f64 / (2 * j + 2) as f64;
+    }
+    sum / (PI * x.sqrt())
+}
+
+/// Returns exp(−u) K¼(u), where K¼ is the modified Bessel function of the second kind of
+/// order 1/4, from its integral representation with the trapezoidal rule, which converges
+/// quickly because the integrand decays doubly exponentially.
+fn scaled_bessel_k(u: f64) -> f64 {
+    let step = 0.01_f64;
+    let mut sum = 0.5 * (-2.0 * u).exp();
+    let mut t = step;
+    loop {
+        let term = (-u * (1.0 + t.cosh())).exp() * (t / 4.0).cosh();
+        sum += term;
+        if term <= 1E-17 * sum {
+            break;
+        }
+        t += step;
+    }
+    sum * step
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use statrs::distribution::{Normal, Uniform};
+
+    fn sample() -> Vec<f64> {
+        vec![
+            0.61, 0.29, 0.06, 0.59, -1.73, -0.74, 0.51, -0.56, 0.39, 1.64, 0.05, -0.06, 0.64,
+            -0.82, 0.37, 1.77, 1.09, -1.28, 2.36, 1.31, 1.05, -0.32, -0.4, 1.06, -2.47,
+        ]
+    }
+
+    #[test]
+    fn cramer_von_mises() {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let test = CramerVonMisesTest::new(&sample(), &normal).unwrap();
+        assert_eq!(test.estimate(), 0.18922733055183105);
+        assert_eq!(test.p_value(), 0.30715916129360477);
+
+        let uniform = Uniform::new(-3.0, 3.0).unwrap();
+        let test = CramerVonMisesTest::new(&sample(), &uniform).unwrap();
+        assert_eq!(test.p_value(), 0.06708862306106622);
+
+        // Upper percentage points of the asymptotic distribution.
+        assert!((cramer_von_mises_cdf(0.347) - 0.9).abs() < 1E-3);
+        assert!((cramer_von_mises_cdf(0.461) - 0.95).abs() < 1E-3);
+        assert!((cramer_von_mises_cdf(0.743) - 0.99).abs() < 1E-3);
+    }
+
+    #[test]
+    fn fitted() {
+        let test = CramerVonMisesTest::fitted(&sample(), DistributionFamily::Normal).unwrap();
+        assert_eq!(test.estimate(), 0.03032414088734881);
+        assert_eq!(test.p_value(), 0.8345710458969021);
+
+        let x = vec![
+            0.12, 0.35, 0.41, 0.77, 0.9, 1.04, 1.38, 1.52, 2.01, 2.43, 2.9, 3.3, 4.1, 5.6,
+        ];
+        let test = CramerVonMisesTest::fitted(&x, DistributionFamily::Exponential).unwrap();
+        assert_eq!(test.estimate(), 0.027686261636197192);
+        assert_eq!(test.p_value(), 0.945530855167498);
+        let test = CramerVonMisesTest::fitted(&x, DistributionFamily::Weibull).unwrap();
+        assert_eq!(test.estimate(), 0.013786572563288664);
+        assert_eq!(test.p_value(), 0.25);
+    }
+}
@@ -0,0 +1,331 @@
+use statrs::distribution::{ChiSquared, ContinuousCDF, Normal};
+use statrs::{Result, StatsError};
+
+use super::{AlternativeHypothesis, StatisticalTest};
+
+/// Implements D'Agostino's test of whether the skewness of the population is zero, as in a
+/// normal distribution (D'Agostino, 1970), which transforms the sample skewness to a
+/// standard normal variable.
+///
+/// # References
+///
+/// D'Agostino, R. B. (1970). Transformation to Normality of the Null Distribution of g₁.
+///     Biometrika, 57(3), 679–681. <https://doi.org/10.1093/biomet/57.3.679>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SkewnessTest {
+    estimate: f64,
+    effect_size: f64,
+    p_value: f64,
+}
+
+impl SkewnessTest {
+    /// Run D'Agostino's skewness test on the sample `x`. The `alternative` hypothesis refers
+    /// to the skewness compared to zero. The estimate is the z statistic, and the effect size
+    /// is the sample skewness g₁.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::test::{AlternativeHypothesis, SkewnessTest, StatisticalTest};
+    ///
+    /// let x = vec![
+    ///     0.1, 0.1, 0.2, 0.2, 0.3, 0.3, 0.4, 0.5, 0.6, 0.8, 1.0, 1.3, 1.9, 2.8, 4.5, 7.2,
+    /// ];
+    /// let test = SkewnessTest::new(&x, AlternativeHypothesis::Greater).unwrap();
+    /// assert!(test.p_value() < 0.05);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample has fewer than eight observations or no variation, or if
+    /// it contains NaN.
+    pub fn new(x: &[f64], alternative: AlternativeHypothesis) -> Result<SkewnessTest> {
+        let moments = Moments::new(x)?;
+        if moments.n < 8.0 {
+            return Err(StatsError::BadParams);
+        }
+        let estimate = moments.skewness_z();
+        Ok(SkewnessTest {
+            estimate,
+            effect_size: moments.skewness,
+            p_value: normal_p_value(estimate, alternative)?,
+        })
+    }
+}
+
+impl StatisticalTest for SkewnessTest {
+    type Estimate = f64;
+    type EffectSize = f64;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> f64 {
+        self.effect_size
+    }
+}
+
+/// Implements the Anscombe-Glynn test of whether the kurtosis of the population is that of a
+/// normal distribution (Anscombe & Glynn, 1983), which transforms the sample kurtosis to a
+/// standard normal variable.
+///
+/// # References
+///
+/// Anscombe, F. J., & Glynn, W. J. (1983). Distribution of the Kurtosis Statistic b₂ for
+///     Normal Samples. Biometrika, 70(1), 227–234. <https://doi.org/10.1093/biomet/70.1.227>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct KurtosisTest {
+    estimate: f64,
+    effect_size: f64,
+    p_value: f64,
+}
+
+impl KurtosisTest {
+    /// Run the Anscombe-Glynn kurtosis test on the sample `x`. The `alternative` hypothesis
+    /// refers to the kurtosis compared to that of a normal distribution, so `Greater` tests
+    /// for heavy tails. The estimate is the z statistic, and the effect size is the sample
+    /// excess kurtosis b₂ − 3. The approximation is recommended for at least 20 observations.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample has fewer than five observations or no variation, or if
+    /// it contains NaN.
+    pub fn new(x: &[f64], alternative: AlternativeHypothesis) -> Result<KurtosisTest> {
+        let moments = Moments::new(x)?;
+        if moments.n < 5.0 {
+            return Err(StatsError::BadParams);
+        }
+        let estimate = moments.kurtosis_z();
+        Ok(KurtosisTest {
+            estimate,
+            effect_size: moments.kurtosis - 3.0,
+            p_value: normal_p_value(estimate, alternative)?,
+        })
+    }
+}
+
+impl StatisticalTest for KurtosisTest {
+    type Estimate = f64;
+    type EffectSize = f64;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> f64 {
+        self.effect_size
+    }
+}
+
+/// Implements [D'Agostino's K² test](https://en.wikipedia.org/wiki/D%27Agostino%27s_K-squared_test)
+/// of normality (D'Agostino & Pearson, 1973), which combines the skewness and kurtosis tests
+/// into an omnibus test, and scales to samples of any size.
+///
+/// # References
+///
+/// D'Agostino, R., & Pearson, E. S. (1973). Tests for Departure from Normality. Empirical
+///     Results for the Distributions of b₂ and √b₁. Biometrika, 60(3), 613–622.
+///     <https://doi.org/10.1093/biomet/60.3.613>
+///
+/// D'Agostino, R. B., Belanger, A., & D'Agostino, R. B., Jr. (1990). A Suggestion for Using
+///     Powerful and Informative Tests of Normality. The American Statistician, 44(4),
+///     316–321. <https://doi.org/10.1080/00031305.1990.10475751>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DAgostinoPearsonTest {
+    estimate: f64,
+    p_value: f64,
+    skewness: SkewnessTest,
+    kurtosis: KurtosisTest,
+}
+
+impl DAgostinoPearsonTest {
+    /// Run D'Agostino's K² test on the sample `x`. The estimate and the effect size are the
+    /// statistic K², the sum of the squared z statistics of the two-sided skewness and
+    /// kurtosis tests, which are available to tell which of them departs from normality.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::test::{DAgostinoPearsonTest, StatisticalTest};
+    ///
+    /// let x = vec![
+    ///     0.1, 0.1, 0.2, 0.2, 0.3, 0.3, 0.4, 0.5, 0.6, 0.8, 1.0, 1.3, 1.9, 2.8, 4.5, 7.2,
+    ///     0.2, 0.4, 0.7, 1.1,
+    /// ];
+    /// let test = DAgostinoPearsonTest::new(&x).unwrap();
+    /// assert!(test.p_value() < 0.05);
+    /// assert!(test.skewness().p_value() < 0.05);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample has fewer than eight observations or no variation, or if
+    /// it contains NaN.
+    pub fn new(x: &[f64]) -> Result<DAgostinoPearsonTest> {
+        let skewness = SkewnessTest::new(x, AlternativeHypothesis::Different)?;
+        let kurtosis = KurtosisTest::new(x, AlternativeHypothesis::Different)?;
+        let estimate = skewness.estimate.powi(2) + kurtosis.estimate.powi(2);
+        Ok(DAgostinoPearsonTest {
+            estimate,
+            p_value: ChiSquared::new(2.0)?.sf(estimate),
+            skewness,
+            kurtosis,
+        })
+    }
+
+    /// Returns the two-sided skewness test that makes up the statistic.
+    pub fn skewness(&self) -> &SkewnessTest {
+        &self.skewness
+    }
+
+    /// Returns the two-sided kurtosis test that makes up the statistic.
+    pub fn kurtosis(&self) -> &KurtosisTest {
+        &self.kurtosis
+    }
+}
+
+impl StatisticalTest for DAgostinoPearsonTest {
+    type Estimate = f64;
+    type EffectSize = f64;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> f64 {
+        self.estimate
+    }
+}
+
+/// Sample size, skewness g₁ and kurtosis b₂ of a sample, from its central moments.
+pub(super) struct Moments {
+    pub(super) n: f64,
+    pub(super) skewness: f64,
+    pub(super) kurtosis: f64,
+}
+
+impl Moments {
+    pub(super) fn new(x: &[f64]) -> Result<Moments> {
+        if x.is_empty() || x.iter().any(|x| x.is_nan()) {
+            return Err(StatsError::BadParams);
+        }
+        let n = x.len() as f64;
+        let mean = x.iter().sum::<f64>() / n;
+        let (m2, m3, m4) = x.iter().fold((0.0, 0.0, 0.0), |(m2, m3, m4), x| {
+            let d = x - mean;
+            (m2 + d * d, m3 + d.powi(3), m4 + d.powi(4))
+        });
+        let (m2, m3, m4) = (m2 / n, m3 / n, m4 / n);
+        if m2 <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        Ok(Moments {
+            n,
+            skewness: m3 / m2.powf(1.5),
+            kurtosis: m4 / (m2 * m2),
+        })
+    }
+
+    /// Returns the skewness transformed to a standard normal variable under normality with
+    /// Johnson's S_U approximation (D'Agostino, 1970).
+    fn skewness_z(&self) -> f64 {
+        let n = self.n;
+        let y = self.skewness * ((n + 1.0) * (n + 3.0) / (6.0 * (n - 2.0))).sqrt();
+        let beta_2 = 3.0 * (n * n + 27.0 * n - 70.0) * (n + 1.0) * (n + 3.0)
+            / ((n - 2.0) * (n + 5.0) * (n + 7.0) * (n + 9.0));
+        let w_2 = -1.0 + (2.0 * (beta_2 - 1.0)).sqrt();
+        let delta = 1.0 / (0.5 * w_2.ln()).sqrt();
+        let alpha = (2.0 / (w_2 - 1.0)).sqrt();
+        delta * (y / alpha).asinh()
+    }
+
+    /// Returns the kurtosis transformed to a standard normal variable under normality with
+    /// the Wilson-Hilferty approximation (Anscombe & Glynn, 1983).
+    fn kurtosis_z(&self) -> f64 {
+        let n = self.n;
+        let mean = 3.0 * (n - 1.0) / (n + 1.0);
+        let variance =
+            24.0 * n * (n - 2.0) * (n - 3.0) / ((n + 1.0).powi(2) * (n + 3.0) * (n + 5.0));
+        let x = (self.kurtosis - mean) / variance.sqrt();
+        // Square root of the skewness of b₂.
+        let root_beta_1 = 6.0 * (n * n - 5.0 * n + 2.0) / ((n + 7.0) * (n + 9.0))
+            * (6.0 * (n + 3.0) * (n + 5.0) / (n * (n - 2.0) * (n - 3.0))).sqrt();
+        let a = 6.0
+            + 8.0 / root_beta_1 * (2.0 / root_beta_1 + (1.0 + 4.0 / root_beta_1.powi(2)).sqrt());
+        let denominator = 1.0 + x * (2.0 / (a - 4.0)).sqrt();
+        ((1.0 - 2.0 / (9.0 * a)) - ((1.0 - 2.0 / a) / denominator).cbrt())
+            / (2.0 / (9.0 * a)).sqrt()
+    }
+}
+
+/// Returns the p-value of a standard normal statistic `z` under the `alternative` hypothesis.
+fn normal_p_value(z: f64, alternative: AlternativeHypothesis) -> Result<f64> {
+    let normal = Normal::new(0.0, 1.0)?;
+    Ok(match alternative {
+        AlternativeHypothesis::Less => normal.cdf(z),
+        AlternativeHypothesis::Greater => normal.sf(z),
+        AlternativeHypothesis::Different => 2.0 * normal.sf(z.abs()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<f64> {
+        vec![
+            148.0, 154.0, 158.0, 160.0, 161.0, 162.0, 166.0, 170.0, 182.0, 195.0, 236.0, 149.0,
+            155.0, 158.0, 160.0, 161.0, 163.0, 167.0, 171.0, 184.0, 196.0, 239.0,
+        ]
+    }
+
+    #[test]
+    fn skewness() {
+        let test = SkewnessTest::new(&sample(), AlternativeHypothesis::Different).unwrap();
+        assert_eq!(test.estimate(), 3.216964694047444);
+        assert_eq!(test.effect_size(), 1.687567057289822);
+        assert_eq!(test.p_value(), 0.0012955454052653195);
+        let test = SkewnessTest::new(&sample(), AlternativeHypothesis::Less).unwrap();
+        assert_eq!(test.p_value(), 0.9993522272973674);
+        assert!(SkewnessTest::new(&sample()[..7], AlternativeHypothesis::Different).is_err());
+
+        let x = vec![2.0, 8.0, 0.0, 4.0, 1.0, 9.0, 9.0, 0.0];
+        let test = SkewnessTest::new(&x, AlternativeHypothesis::Different).unwrap();
+        assert_eq!(test.estimate(), 0.4462638537419693);
+        assert_eq!(test.p_value(), 0.6554066631275463);
+    }
+
+    #[test]
+    fn kurtosis() {
+        let test = KurtosisTest::new(&sample(), AlternativeHypothesis::Different).unwrap();
+        assert_eq!(test.estimate(), 2.185898010439591);
+        assert_eq!(test.effect_size(), 2.0197052930025627);
+        assert_eq!(test.p_value(), 0.028823067842547012);
+        assert!(KurtosisTest::new(&[1.0; 10], AlternativeHypothesis::Different).is_err());
+
+        let x: Vec<f64> = (0..20).map(|i| i as f64).collect();
+        let test = KurtosisTest::new(&x, AlternativeHypothesis::Different).unwrap();
+        assert_eq!(test.estimate(), -1.7058104152122062);
+        assert_eq!(test.p_value(), 0.08804338332896584);
+    }
+
+    #[test]
+    fn dagostino_pearson() {
+        let test = DAgostinoPearsonTest::new(&sample()).unwrap();
+        assert_eq!(test.estimate(), 15.127011954791527);
+        assert_eq!(test.p_value(), 0.0005190522645833701);
+    }
+}
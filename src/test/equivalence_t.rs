@@ -0,0 +1,313 @@
+use crate::effect_size::{EffectSize, CONFIDENCE_LEVEL};
+use statrs::distribution::{ContinuousCDF, StudentsT};
+use statrs::{Result, StatsError};
+
+use super::{MeanDifference, StatisticalTest, WelchsTTest};
+
+/// Scale on which equivalence bounds are expressed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BoundScale {
+    /// In the units of the observations.
+    Raw,
+    /// In standard deviations, like Cohen's d.
+    Standardized,
+}
+
+/// Bounds on the difference between two means for equivalence, non-inferiority and
+/// superiority testing.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct EquivalenceBounds {
+    lower: f64,
+    upper: f64,
+    scale: BoundScale,
+}
+
+impl EquivalenceBounds {
+    /// Constructs the equivalence interval from `lower` to `upper` on the given `scale`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `lower` is not smaller than `upper`, or if either bound is `NaN`.
+    pub fn new(lower: f64, upper: f64, scale: BoundScale) -> Result<EquivalenceBounds> {
+        if lower.is_nan() || upper.is_nan() || lower >= upper {
+            return Err(StatsError::BadParams);
+        }
+        Ok(EquivalenceBounds {
+            lower,
+            upper,
+            scale,
+        })
+    }
+
+    /// Constructs the bounds to test that `x` is not inferior to `y` by more than `margin`,
+    /// when larger values are better. Swap the samples when smaller values are better.
+    pub fn non_inferiority(margin: f64, scale: BoundScale) -> Result<EquivalenceBounds> {
+        if margin.is_nan() || margin < 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        EquivalenceBounds::new(-margin, f64::INFINITY, scale)
+    }
+
+    /// Constructs the bounds to test that `x` is superior to `y` by more than `margin`, when
+    /// larger values are better. Swap the samples when smaller values are better.
+    pub fn superiority(margin: f64, scale: BoundScale) -> Result<EquivalenceBounds> {
+        if margin.is_nan() || margin < 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        EquivalenceBounds::new(margin, f64::INFINITY, scale)
+    }
+
+    /// Returns the lower bound.
+    pub fn lower(&self) -> f64 {
+        self.lower
+    }
+
+    /// Returns the upper bound.
+    pub fn upper(&self) -> f64 {
+        self.upper
+    }
+
+    /// Returns the scale of the bounds.
+    pub fn scale(&self) -> BoundScale {
+        self.scale
+    }
+
+    /// Returns the bounds in the units of the observations.
+    pub(crate) fn raw(&self, standardizer: f64) -> (f64, f64) {
+        match self.scale {
+            BoundScale::Raw => (self.lower, self.upper),
+            BoundScale::Standardized => (self.lower * standardizer, self.upper * standardizer),
+        }
+    }
+}
+
+/// Implements the two one-sided tests (TOST) procedure for the equivalence of two means
+/// (Schuirmann, 1987), and the one-sided tests for non-inferiority and superiority by a margin.
+///
+/// The null hypothesis is that the difference between the means lies outside the bounds, so a
+/// small p-value is evidence of equivalence. The difference is equivalent at significance level
+/// α when its 1 - 2α confidence interval lies within the bounds.
+///
+/// # References
+///
+/// Schuirmann, D. J. (1987). A comparison of the Two One-Sided Tests Procedure and the Power
+///     Approach for assessing the equivalence of average bioavailability. Journal of
+///     Pharmacokinetics and Biopharmaceutics, 15(6), 657–680. <https://doi.org/10.1007/BF01068419>
+///
+/// Lakens, D. (2017). Equivalence Tests: A Practical Primer for t Tests, Correlations, and
+///     Meta-Analyses. Social Psychological and Personality Science, 8(4), 355–362.
+///     <https://doi.org/10.1177/1948550617697177>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct EquivalenceTTest {
+    df: f64,
+    estimate: (f64, f64),
+    bounds: (f64, f64),
+    mean_difference: f64,
+    standard_error: f64,
+    alpha: f64,
+    confidence_interval: (f64, f64),
+    effect_size: EffectSize,
+    p_value: f64,
+}
+
+impl EquivalenceTTest {
+    /// Run the equivalence test with Student's two-sample t-test on samples `x` and `y`.
+    ///
+    /// Standardized bounds are relative to the pooled standard deviation. The effect size is
+    /// Cohen's d, with a 95% confidence interval.
+    pub fn independent(
+        x: &[f64],
+        y: &[f64],
+        bounds: EquivalenceBounds,
+    ) -> Result<EquivalenceTTest> {
+        let effect_size = EffectSize::cohens_d(x, y, CONFIDENCE_LEVEL)?;
+        EquivalenceTTest::new(MeanDifference::pooled(x, y), bounds, effect_size)
+    }
+
+    /// Run the equivalence test with Welch's two-sample t-test on samples `x` and `y`.
+    ///
+    /// Standardized bounds are relative to the root mean square of the two standard
+    /// deviations. The effect size is that of [`WelchsTTest`].
+    pub fn welch(x: &[f64], y: &[f64], bounds: EquivalenceBounds) -> Result<EquivalenceTTest> {
+        let effect_size = WelchsTTest::independent(x, y)?.effect_size();
+        EquivalenceTTest::new(MeanDifference::welch(x, y), bounds, effect_size)
+    }
+
+    /// Run the equivalence test with the paired t-test on samples `x` and `y`.
+    ///
+    /// Standardized bounds are relative to the standard deviation of the differences. The
+    /// effect size is Cohen's d of the differences, with a 95% confidence interval.
+    pub fn paired(x: &[f64], y: &[f64], bounds: EquivalenceBounds) -> Result<EquivalenceTTest> {
+        if x.len() != y.len() {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+        let effect_size = EffectSize::cohens_d_paired(x, y, CONFIDENCE_LEVEL)?;
+        EquivalenceTTest::new(MeanDifference::paired(x, y), bounds, effect_size)
+    }
+
+    fn new(
+        difference: MeanDifference,
+        bounds: EquivalenceBounds,
+        effect_size: EffectSize,
+    ) -> Result<EquivalenceTTest> {
+        let df = difference.df;
+        let t_distribution = StudentsT::new(0.0, 1.0, df)?;
+
+        let (lower, upper) = bounds.raw(difference.standardizer);
+        let t_lower = (difference.estimate - lower) / difference.standard_error;
+        let t_upper = (difference.estimate - upper) / difference.standard_error;
+        let p_value = t_distribution.sf(t_lower).max(t_distribution.cdf(t_upper));
+
+        let test = EquivalenceTTest {
+            df,
+            estimate: (t_lower, t_upper),
+            bounds: (lower, upper),
+            mean_difference: difference.estimate,
+            standard_error: difference.standard_error,
+            alpha: 0.0,
+            confidence_interval: (f64::NAN, f64::NAN),
+            effect_size,
+            p_value,
+        };
+        test.with_alpha(1.0 - CONFIDENCE_LEVEL)
+    }
+
+    /// Returns the test with its confidence interval at the level 1 - 2`alpha`, which
+    /// corresponds to the two one-sided tests at the significance level `alpha`. The default
+    /// significance level is 5%.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `alpha` is not between 0 and 0.5.
+    pub fn with_alpha(self, alpha: f64) -> Result<EquivalenceTTest> {
+        if !(alpha > 0.0 && alpha < 0.5) {
+            return Err(StatsError::BadParams);
+        }
+        let t_distribution = StudentsT::new(0.0, 1.0, self.df)?;
+        let margin = t_distribution.inverse_cdf(1.0 - alpha) * self.standard_error;
+
+        Ok(EquivalenceTTest {
+            alpha,
+            confidence_interval: (self.mean_difference - margin, self.mean_difference + margin),
+            ..self
+        })
+    }
+
+    /// Returns the difference between the means.
+    pub fn mean_difference(&self) -> f64 {
+        self.mean_difference
+    }
+
+    /// Returns the 1 - 2α confidence interval of the difference between the means, which is
+    /// the 90% interval unless the significance level was changed with
+    /// [`with_alpha`](EquivalenceTTest::with_alpha).
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        self.confidence_interval
+    }
+
+    /// Returns the significance level α of the confidence interval.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Returns the bounds in the units of the observations.
+    pub fn bounds(&self) -> (f64, f64) {
+        self.bounds
+    }
+}
+
+impl StatisticalTest for EquivalenceTTest {
+    type Estimate = (f64, f64);
+    type EffectSize = EffectSize;
+
+    /// Returns the t-statistics against the lower and the upper bound.
+    fn estimate(&self) -> (f64, f64) {
+        self.estimate
+    }
+
+    /// Returns the larger of the p-values of the two one-sided tests.
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> EffectSize {
+        self.effect_size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn independent() {
+        let x = vec![
+            134.0, 146.0, 104.0, 119.0, 124.0, 161.0, 107.0, 83.0, 113.0, 129.0, 97.0, 123.0,
+        ];
+        let y = vec![70.0, 118.0, 101.0, 85.0, 107.0, 132.0, 94.0];
+        let bounds = EquivalenceBounds::new(-30.0, 30.0, BoundScale::Raw).unwrap();
+        let test = EquivalenceTTest::independent(&x, &y, bounds).unwrap();
+        assert_eq!(test.df, 17.0);
+        assert_eq!(test.mean_difference(), 19.0);
+        assert_eq!(test.estimate(), (4.877914919719694, -1.0950421248350333));
+        assert_eq!(
+            test.confidence_interval(),
+            (1.52517090179672, 36.47482909820328)
+        );
+        assert_eq!(test.p_value(), 0.144385292488308);
+    }
+
+    #[test]
+    fn welch() {
+        let x = vec![
+            134.0, 146.0, 104.0, 119.0, 124.0, 161.0, 107.0, 83.0, 113.0, 129.0, 97.0, 123.0,
+        ];
+        let y = vec![70.0, 118.0, 101.0, 85.0, 107.0, 132.0, 94.0];
+        let bounds = EquivalenceBounds::new(-1.5, 1.5, BoundScale::Standardized).unwrap();
+        let test = EquivalenceTTest::welch(&x, &y, bounds).unwrap();
+        assert_eq!(test.bounds(), (-31.514066123500527, 31.514066123500527));
+        assert_eq!(test.estimate(), (5.079854284633346, -1.2584540761424634));
+        assert_eq!(test.p_value(), 0.1151122219343998);
+    }
+
+    #[test]
+    fn paired() {
+        let x = vec![8.0, 6.0, 5.5, 11.0, 8.5, 5.0, 6.0, 6.0];
+        let y = vec![8.5, 9.0, 6.5, 10.5, 9.0, 7.0, 6.5, 7.0];
+        let bounds = EquivalenceBounds::new(-1.5, 1.5, BoundScale::Raw).unwrap();
+        let test = EquivalenceTTest::paired(&x, &y, bounds).unwrap();
+        assert_eq!(test.estimate(), (1.322875655532295, -6.614378277661477));
+        assert_eq!(
+            test.confidence_interval(),
+            (-1.7160834040474011, -0.2839165959525992)
+        );
+        assert_eq!(test.p_value(), 0.11372640902988201);
+
+        let test = test.with_alpha(0.025).unwrap();
+        assert_eq!(test.alpha(), 0.025);
+        assert_eq!(
+            test.confidence_interval(),
+            (-1.8937439591181056, -0.106256040881895)
+        );
+        assert_eq!(test.p_value(), 0.11372640902988201);
+        assert!(test.with_alpha(0.5).is_err());
+    }
+
+    #[test]
+    fn non_inferiority() {
+        let x = vec![8.0, 6.0, 5.5, 11.0, 8.5, 5.0, 6.0, 6.0];
+        let y = vec![8.5, 9.0, 6.5, 10.5, 9.0, 7.0, 6.5, 7.0];
+        let bounds = EquivalenceBounds::non_inferiority(1.5, BoundScale::Raw).unwrap();
+        let test = EquivalenceTTest::paired(&x, &y, bounds).unwrap();
+        assert_eq!(test.p_value(), 0.11372640902988201);
+        let bounds = EquivalenceBounds::superiority(0.5, BoundScale::Raw).unwrap();
+        let test = EquivalenceTTest::paired(&y, &x, bounds).unwrap();
+        assert_eq!(test.p_value(), 0.11372640902988179);
+    }
+
+    #[test]
+    fn bounds() {
+        assert!(EquivalenceBounds::new(1.0, -1.0, BoundScale::Raw).is_err());
+        assert!(EquivalenceBounds::non_inferiority(-1.0, BoundScale::Raw).is_err());
+    }
+}
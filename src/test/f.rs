@@ -1,3 +1,4 @@
+use crate::effect_size::{EffectSize, CONFIDENCE_LEVEL};
 use crate::statistics::StatisticsExt;
 use statrs::distribution::{ContinuousCDF, FisherSnedecor};
 
@@ -8,11 +9,14 @@ use super::StatisticalTest;
 pub struct FTest {
     df: (f64, f64),
     estimate: f64,
+    effect_size: EffectSize,
     p_value: f64,
 }
 
 impl FTest {
     /// Carry out the F-test of equality of variances on the samples `x` and `y`.
+    ///
+    /// The effect size is the ratio of the variances, with a 95% confidence interval.
     pub fn new(x: &[f64], y: &[f64]) -> statrs::Result<FTest> {
         let f = x.variance_ratio(y);
         let df = (x.df(), y.df());
@@ -24,10 +28,12 @@ impl FTest {
         } else {
             probability
         };
+        let effect_size = EffectSize::variance_ratio(x, y, CONFIDENCE_LEVEL)?;
 
         Ok(FTest {
             df,
             estimate: f,
+            effect_size,
             p_value,
         })
     }
@@ -35,6 +41,7 @@ impl FTest {
 
 impl StatisticalTest for FTest {
     type Estimate = f64;
+    type EffectSize = EffectSize;
 
     fn estimate(&self) -> f64 {
         self.estimate
@@ -44,8 +51,8 @@ impl StatisticalTest for FTest {
         self.p_value
     }
 
-    fn effect_size(&self) -> f64 {
-        self.estimate
+    fn effect_size(&self) -> EffectSize {
+        self.effect_size
     }
 }
 
@@ -63,6 +70,11 @@ mod tests {
         assert_eq!(result.df, (11.0, 6.0));
         assert_eq!(result.estimate(), 1.0755200911940725);
         assert_eq!(result.p_value(), 0.4893961256182331);
+        assert_eq!(result.effect_size().estimate(), 1.0755200911940725);
+        assert_eq!(
+            result.effect_size().confidence_interval(),
+            (0.1988110169857296, 4.173718299078523)
+        );
     }
 
     #[test]
@@ -0,0 +1,693 @@
+use crate::distribution::brent;
+use crate::effect_size::{EffectSize, EffectSizeKind, CONFIDENCE_LEVEL};
+use crate::permutation::{PermutationOptions, PermutationTest, RandomizationDesign};
+use crate::statistics::ContingencyTable;
+use statrs::function::factorial::{ln_binomial, ln_factorial};
+use statrs::{Result, StatsError};
+use std::collections::BTreeMap;
+
+use super::{AlternativeHypothesis, StatisticalTest};
+
+/// Relative tolerance when comparing the probabilities of tables to the probability of the
+/// observed table, so that rounding errors do not break ties.
+pub(super) static TOLERANCE: f64 = 1E-7;
+
+/// Implements [Fisher's exact test](https://en.wikipedia.org/wiki/Fisher%27s_exact_test) of
+/// independence in a 2×2 contingency table (Fisher, 1935; Agresti, 2013).
+///
+/// # References
+///
+/// Agresti, A. (2013). Categorical Data Analysis (3rd ed.). John Wiley & Sons.
+///
+/// Fisher, R. A. (1935). The Logic of Inductive Inference. Journal of the Royal Statistical
+///     Society, 98(1), 39–82. <https://doi.org/10.2307/2342435>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FisherExactTest {
+    estimate: f64,
+    effect_size: EffectSize,
+    p_value: f64,
+}
+
+impl FisherExactTest {
+    /// Run Fisher's exact test on the 2×2 `table`. The `alternative` hypothesis refers to the
+    /// odds ratio compared to 1, where `Greater` means that the counts concentrate on the main
+    /// diagonal. Two-sided p-values sum the probabilities of all tables that are at most as
+    /// probable as the observed table.
+    ///
+    /// The estimate is the conditional maximum likelihood estimate of the odds ratio, and the
+    /// effect size is the same odds ratio with an exact 95% confidence interval, which is
+    /// one-sided for one-sided alternatives.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::statistics::ContingencyTable;
+    /// use stattest::test::{AlternativeHypothesis, FisherExactTest, StatisticalTest};
+    ///
+    /// let table = ContingencyTable::new(vec![vec![3, 1], vec![1, 3]]).unwrap();
+    /// let test = FisherExactTest::new(&table, AlternativeHypothesis::Greater).unwrap();
+    /// assert!(test.p_value() > 0.05);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the table is not 2×2 or if a row or column is empty.
+    pub fn new(
+        table: &ContingencyTable,
+        alternative: AlternativeHypothesis,
+    ) -> Result<FisherExactTest> {
+        if table.rows() != 2 || table.columns() != 2 {
+            return Err(StatsError::BadParams);
+        }
+        let rows = table.row_totals();
+        let columns = table.column_totals();
+        if rows.contains(&0) || columns.contains(&0) {
+            return Err(StatsError::BadParams);
+        }
+
+        let hypergeometric = NoncentralHypergeometric::new(rows[0], rows[1], columns[0]);
+        let x = table.get(0, 0);
+        let p_value = hypergeometric.p_value(x, alternative);
+
+        let estimate = hypergeometric.mle(x);
+        let alpha = 1.0 - CONFIDENCE_LEVEL;
+        let confidence_interval = match alternative {
+            AlternativeHypothesis::Less => (0.0, hypergeometric.upper(x, alpha)),
+            AlternativeHypothesis::Greater => (hypergeometric.lower(x, alpha), f64::INFINITY),
+            AlternativeHypothesis::Different => (
+                hypergeometric.lower(x, alpha / 2.0),
+                hypergeometric.upper(x, alpha / 2.0),
+            ),
+        };
+        let effect_size = EffectSize::new(
+            EffectSizeKind::OddsRatio,
+            estimate,
+            confidence_interval,
+            CONFIDENCE_LEVEL,
+        )
+        .with_sample_sizes(rows[0], rows[1]);
+
+        Ok(FisherExactTest {
+            estimate,
+            effect_size,
+            p_value,
+        })
+    }
+}
+
+impl StatisticalTest for FisherExactTest {
+    type Estimate = f64;
+    type EffectSize = EffectSize;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> EffectSize {
+        self.effect_size
+    }
+}
+
+/// Implements the Freeman-Halton extension of Fisher's exact test to r×c contingency tables
+/// (Freeman & Halton, 1951), computed with the network algorithm of Mehta and Patel (1983).
+///
+/// # References
+///
+/// Freeman, G. H., & Halton, J. H. (1951). Note on an Exact Treatment of Contingency, Goodness
+///     of Fit and Other Problems of Significance. Biometrika, 38(1/2), 141–149.
+///     <https://doi.org/10.2307/2332323>
+///
+/// Mehta, C. R., & Patel, N. R. (1983). A Network Algorithm for Performing Fisher's Exact Test
+///     in r × c Contingency Tables. Journal of the American Statistical Association, 78(382),
+///     427–434. <https://doi.org/10.1080/01621459.1983.10477989>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FreemanHaltonTest {
+    estimate: f64,
+    effect_size: EffectSize,
+    p_value: f64,
+    exact: bool,
+}
+
+impl FreemanHaltonTest {
+    /// Run the exact test of independence of the rows and columns of the `table`, summing the
+    /// probabilities of all tables with the same row and column totals that are at most as
+    /// probable as the observed table. Empty rows and columns are ignored.
+    ///
+    /// When the network has more than `max_exact` arcs, as set in the `options`, the p-value
+    /// is instead estimated from random tables with the given number of resamples and seed.
+    ///
+    /// The estimate is the probability of the observed table, and the effect size is Cramér's
+    /// V, with a 95% confidence interval based on the noncentral chi-squared distribution.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if fewer than two rows or columns are not empty.
+    pub fn new(table: &ContingencyTable, options: PermutationOptions) -> Result<FreemanHaltonTest> {
+        let table = without_empty(table)?;
+        // The network has a node for every arrangement of the remaining row totals, so put the
+        // smaller dimension in the rows.
+        let table = if table.rows() > table.columns() {
+            table.transpose()
+        } else {
+            table
+        };
+        let rows = table.row_totals();
+        let columns = table.column_totals();
+
+        let ln_observed = -table
+            .counts()
+            .iter()
+            .flatten()
+            .map(|count| ln_factorial(*count as u64))
+            .sum::<f64>();
+        let ln_constant = rows
+            .iter()
+            .chain(&columns)
+            .map(|total| ln_factorial(*total as u64))
+            .sum::<f64>()
+            - ln_factorial(table.total() as u64);
+
+        let network = Network {
+            columns: &columns,
+            bounds: BTreeMap::new(),
+            arcs: 0,
+            max_arcs: options.max_exact(),
+        };
+        let (p_value, exact) = match network.p_value(&rows, ln_observed, ln_constant) {
+            Some(p_value) => (p_value.min(1.0), true),
+            None => (monte_carlo(&table, options)?, false),
+        };
+
+        Ok(FreemanHaltonTest {
+            estimate: (ln_constant + ln_observed).exp(),
+            effect_size: EffectSize::cramers_v(&table, CONFIDENCE_LEVEL)?,
+            p_value,
+            exact,
+        })
+    }
+
+    /// Returns whether the p-value was computed exactly rather than estimated from random
+    /// tables.
+    pub fn is_exact(&self) -> bool {
+        self.exact
+    }
+}
+
+impl StatisticalTest for FreemanHaltonTest {
+    type Estimate = f64;
+    type EffectSize = EffectSize;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> EffectSize {
+        self.effect_size
+    }
+}
+
+/// Noncentral hypergeometric distribution of the top left count of a 2×2 table with fixed
+/// margins, parameterized by the odds ratio.
+pub(super) struct NoncentralHypergeometric {
+    low: usize,
+    high: usize,
+    /// Logarithm of the central probability of every count from `low` to `high`.
+    ln_central: Vec<f64>,
+}
+
+impl NoncentralHypergeometric {
+    /// Constructs the distribution for row totals `m` and `n` and first column total `k`.
+    pub(super) fn new(m: usize, n: usize, k: usize) -> NoncentralHypergeometric {
+        let low = k.saturating_sub(n);
+        let high = k.min(m);
+        let ln_total = ln_binomial((m + n) as u64, k as u64);
+        let ln_central = (low..=high)
+            .map(|x| {
+                ln_binomial(m as u64, x as u64) + ln_binomial(n as u64, (k - x) as u64) - ln_total
+            })
+            .collect();
+        NoncentralHypergeometric {
+            low,
+            high,
+            ln_central,
+        }
+    }
+
+    /// Returns the p-value of Fisher's exact test of an odds ratio of 1 given the count `x`.
+    pub(super) fn p_value(&self, x: usize, alternative: AlternativeHypothesis) -> f64 {
+        let p_value = match alternative {
+            AlternativeHypothesis::Less => self.cdf(x, 1.0),
+            AlternativeHypothesis::Greater => self.sf(x, 1.0),
+            AlternativeHypothesis::Different => {
+                let probabilities = self.pmf(1.0);
+                let observed = probabilities[x - self.low] * (1.0 + TOLERANCE);
+                probabilities.iter().filter(|p| **p <= observed).sum()
+            }
+        };
+        p_value.min(1.0)
+    }
+
+    /// Returns the probability of every count given the odds ratio.
+    fn pmf(&self, odds_ratio: f64) -> Vec<f64> {
+        if odds_ratio == 0.0 || odds_ratio.is_infinite() {
+            let mut pmf = vec![0.0; self.ln_central.len()];
+            let i = if odds_ratio == 0.0 { 0 } else { pmf.len() - 1 };
+            pmf[i] = 1.0;
+            return pmf;
+        }
+        let ln_odds_ratio = odds_ratio.ln();
+        let weights: Vec<f64> = self
+            .ln_central
+            .iter()
+            .enumerate()
+            .map(|(i, ln_p)| ln_p + ln_odds_ratio * (self.low + i) as f64)
+            .collect();
+        let max = weights.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let weights: Vec<f64> = weights.iter().map(|w| (w - max).exp()).collect();
+        let total = weights.iter().sum::<f64>();
+        weights.iter().map(|w| w / total).collect()
+    }
+
+    /// Returns the mean count given the odds ratio.
+    fn mean(&self, odds_ratio: f64) -> f64 {
+        self.pmf(odds_ratio)
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (self.low + i) as f64 * p)
+            .sum()
+    }
+
+    /// Returns the probability of a count of at most `x` given the odds ratio.
+    fn cdf(&self, x: usize, odds_ratio: f64) -> f64 {
+        self.pmf(odds_ratio)[..=x - self.low].iter().sum()
+    }
+
+    /// Returns the probability of a count of at least `x` given the odds ratio.
+    fn sf(&self, x: usize, odds_ratio: f64) -> f64 {
+        self.pmf(odds_ratio)[x - self.low..].iter().sum()
+    }
+
+    /// Returns the conditional maximum likelihood estimate of the odds ratio given the count
+    /// `x`, which equates the mean count to `x`.
+    fn mle(&self, x: usize) -> f64 {
+        if x == self.low {
+            return 0.0;
+        } else if x == self.high {
+            return f64::INFINITY;
+        }
+        solve(|odds_ratio| self.mean(odds_ratio), x as f64, self.mean(1.0))
+    }
+
+    /// Returns the lower confidence bound of the odds ratio at level `1 - alpha` given the
+    /// count `x`.
+    fn lower(&self, x: usize, alpha: f64) -> f64 {
+        if x == self.low {
+            return 0.0;
+        }
+        solve(|odds_ratio| self.sf(x, odds_ratio), alpha, self.sf(x, 1.0))
+    }
+
+    /// Returns the upper confidence bound of the odds ratio at level `1 - alpha` given the
+    /// count `x`.
+    fn upper(&self, x: usize, alpha: f64) -> f64 {
+        if x == self.high {
+            return f64::INFINITY;
+        }
+        // The lower tail decreases with the odds ratio, so solve for its complement.
+        solve(
+            |odds_ratio| 1.0 - self.cdf(x, odds_ratio),
+            1.0 - alpha,
+            1.0 - self.cdf(x, 1.0),
+        )
+    }
+
+    /// Returns the confidence interval of the odds ratio at level `1 - alpha` given the count
+    /// `x`, which contains the odds ratios where the two-sided test that orders counts by
+    /// their probability is not rejected (Baptista & Pike, 1977).
+    ///
+    /// # References
+    ///
+    /// Baptista, J., & Pike, M. C. (1977). Exact Two-Sided Confidence Limits for the Odds
+    ///     Ratio in a 2 × 2 Table. Journal of the Royal Statistical Society. Series C (Applied
+    ///     Statistics), 26(2), 214–220. <https://doi.org/10.2307/2347041>
+    pub(super) fn baptista_pike(&self, x: usize, alpha: f64) -> (f64, f64) {
+        let accepted = |ln_odds_ratio: f64| {
+            let probabilities = self.pmf(ln_odds_ratio.exp());
+            let observed = probabilities[x - self.low] * (1.0 + TOLERANCE);
+            probabilities
+                .iter()
+                .filter(|p| **p <= observed)
+                .sum::<f64>()
+                > alpha
+        };
+        // Walks from an accepted logarithm of the odds ratio in the direction of `step` until
+        // the test is rejected, and bisects the last step.
+        let boundary = |start: f64, mut step: f64| {
+            let (mut inside, mut outside) = (start, start + step);
+            while accepted(outside) {
+                inside = outside;
+                step *= 2.0;
+                outside += step;
+            }
+            for _ in 0..100 {
+                let middle = (inside + outside) / 2.0;
+                if accepted(middle) {
+                    inside = middle;
+                } else {
+                    outside = middle;
+                }
+            }
+            ((inside + outside) / 2.0).exp()
+        };
+
+        if self.low == self.high {
+            return (0.0, f64::INFINITY);
+        }
+        let mut start = if x == self.low {
+            self.upper(x, alpha / 2.0).ln()
+        } else if x == self.high {
+            self.lower(x, alpha / 2.0).ln()
+        } else {
+            self.mle(x).ln()
+        };
+        let direction = if x == self.high { 1.0 } else { -1.0 };
+        while !accepted(start) {
+            start += direction;
+        }
+
+        let lower = if x == self.low {
+            0.0
+        } else {
+            boundary(start, -1.0)
+        };
+        let upper = if x == self.high {
+            f64::INFINITY
+        } else {
+            boundary(start, 1.0)
+        };
+        (lower, upper)
+    }
+}
+
+/// Returns the odds ratio at which the increasing function `f` equals `target`, searching
+/// below or above 1 depending on `at_one`, the value of `f` at 1.
+fn solve<F>(f: F, target: f64, at_one: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    if at_one > target {
+        brent(|t| f(t) - target, 0.0, 1.0)
+    } else if at_one < target {
+        1.0 / brent(|t| f(1.0 / t) - target, f64::EPSILON, 1.0)
+    } else {
+        1.0
+    }
+}
+
+/// Network of partially filled tables, which are filled column by column. A node is the
+/// stage, meaning the number of filled columns, together with the sorted totals of the rows
+/// that remain to be filled, and the length of an arc is the logarithm of the reciprocal of
+/// the product of the factorials of the counts in the column.
+struct Network<'a> {
+    columns: &'a [usize],
+    /// The longest and shortest path from every node to the end of the network.
+    bounds: BTreeMap<(usize, Vec<usize>), (f64, f64)>,
+    arcs: usize,
+    max_arcs: usize,
+}
+
+impl Network<'_> {
+    /// Returns the probability of the tables with the given row totals that are at most as
+    /// probable as the observed table, or `None` if the network has too many arcs.
+    fn p_value(mut self, rows: &[usize], ln_observed: f64, ln_constant: f64) -> Option<f64> {
+        let threshold = ln_observed + TOLERANCE.ln_1p();
+        let mut root = rows.to_vec();
+        root.sort_unstable_by(|a, b| b.cmp(a));
+
+        // Every node keeps the distinct lengths of the paths that lead to it, with the number
+        // of paths of each length.
+        let mut nodes: BTreeMap<Vec<usize>, Vec<(f64, f64)>> = BTreeMap::new();
+        nodes.insert(root, vec![(0.0, 1.0)]);
+        let mut p_value = 0.0;
+
+        for stage in 0..self.columns.len() {
+            let mut next: BTreeMap<Vec<usize>, Vec<(f64, f64)>> = BTreeMap::new();
+            for (remaining, paths) in nodes {
+                let (longest, shortest) = self.bounds(stage, &remaining)?;
+                let ln_total = self.ln_total(stage, &remaining);
+
+                let mut open = Vec::new();
+                for (length, count) in paths {
+                    if length + longest <= threshold {
+                        // Every completion is at most as probable as the observed table.
+                        p_value += count * (ln_constant + length + ln_total).exp();
+                    } else if length + shortest <= threshold {
+                        open.push((length, count));
+                    }
+                }
+                if open.is_empty() {
+                    continue;
+                }
+
+                for column in compositions(self.columns[stage], &remaining) {
+                    let (child, arc) = child(&remaining, &column);
+                    next.entry(child)
+                        .or_default()
+                        .extend(open.iter().map(|(length, count)| (length + arc, *count)));
+                }
+            }
+
+            for paths in next.values_mut() {
+                merge(paths);
+            }
+            nodes = next;
+        }
+
+        Some(p_value)
+    }
+
+    /// Returns the longest and shortest path from the node to the end of the network, or
+    /// `None` if the network has too many arcs.
+    fn bounds(&mut self, stage: usize, remaining: &[usize]) -> Option<(f64, f64)> {
+        if stage == self.columns.len() {
+            return Some((0.0, 0.0));
+        }
+        if let Some(bounds) = self.bounds.get(&(stage, remaining.to_vec())) {
+            return Some(*bounds);
+        }
+
+        let mut bounds = (f64::NEG_INFINITY, f64::INFINITY);
+        for column in compositions(self.columns[stage], remaining) {
+            self.arcs += 1;
+            if self.arcs > self.max_arcs {
+                return None;
+            }
+            let (child, arc) = child(remaining, &column);
+            let (longest, shortest) = self.bounds(stage + 1, &child)?;
+            bounds.0 = bounds.0.max(arc + longest);
+            bounds.1 = bounds.1.min(arc + shortest);
+        }
+
+        self.bounds.insert((stage, remaining.to_vec()), bounds);
+        Some(bounds)
+    }
+
+    /// Returns the logarithm of the total length of all paths from the node to the end of the
+    /// network, as a sum of products.
+    fn ln_total(&self, stage: usize, remaining: &[usize]) -> f64 {
+        ln_factorial(remaining.iter().sum::<usize>() as u64)
+            - remaining
+                .iter()
+                .chain(&self.columns[stage..])
+                .map(|total| ln_factorial(*total as u64))
+                .sum::<f64>()
+    }
+}
+
+/// Returns the node reached by filling the `column` from the `remaining` row totals, with the
+/// length of the arc.
+fn child(remaining: &[usize], column: &[usize]) -> (Vec<usize>, f64) {
+    let mut child: Vec<usize> = remaining.iter().zip(column).map(|(r, x)| r - x).collect();
+    child.sort_unstable_by(|a, b| b.cmp(a));
+    let arc = -column.iter().map(|x| ln_factorial(*x as u64)).sum::<f64>();
+    (child, arc)
+}
+
+/// Returns every way of distributing `total` over cells bounded by `bounds`.
+fn compositions(total: usize, bounds: &[usize]) -> Vec<Vec<usize>> {
+    fn fill(
+        total: usize,
+        bounds: &[usize],
+        capacity: &[usize],
+        column: &mut Vec<usize>,
+        result: &mut Vec<Vec<usize>>,
+    ) {
+        let i = column.len();
+        if i == bounds.len() {
+            if total == 0 {
+                result.push(column.clone());
+            }
+            return;
+        }
+        let low = total.saturating_sub(capacity[i + 1]);
+        for x in low..=total.min(bounds[i]) {
+            column.push(x);
+            fill(total - x, bounds, capacity, column, result);
+            column.pop();
+        }
+    }
+
+    // The capacity of the cells from every index to the end.
+    let mut capacity = vec![0; bounds.len() + 1];
+    for i in (0..bounds.len()).rev() {
+        capacity[i] = capacity[i + 1] + bounds[i];
+    }
+    let mut result = Vec::new();
+    fill(total, bounds, &capacity, &mut Vec::new(), &mut result);
+    result
+}
+
+/// Combines paths whose lengths are equal up to rounding errors.
+fn merge(paths: &mut Vec<(f64, f64)>) {
+    paths.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    let mut merged: Vec<(f64, f64)> = Vec::with_capacity(paths.len());
+    for (length, count) in paths.drain(..) {
+        match merged.last_mut() {
+            Some(last) if length - last.0 <= 1E-9 * length.abs().max(1.0) => last.1 += count,
+            _ => merged.push((length, count)),
+        }
+    }
+    *paths = merged;
+}
+
+/// Returns the table without empty rows and columns.
+fn without_empty(table: &ContingencyTable) -> Result<ContingencyTable> {
+    let columns = table.column_totals();
+    let counts: Vec<Vec<usize>> = table
+        .counts()
+        .iter()
+        .filter(|row| row.iter().sum::<usize>() > 0)
+        .map(|row| {
+            row.iter()
+                .zip(&columns)
+                .filter(|(_, total)| **total > 0)
+                .map(|(count, _)| *count)
+                .collect()
+        })
+        .collect();
+    if counts.len() < 2 || counts[0].len() < 2 {
+        return Err(StatsError::BadParams);
+    }
+    ContingencyTable::new(counts)
+}
+
+/// Returns the Monte Carlo p-value of the exact test, from random tables with the same row
+/// and column totals as the `table`.
+fn monte_carlo(table: &ContingencyTable, options: PermutationOptions) -> Result<f64> {
+    let mut rows = Vec::with_capacity(table.total());
+    let mut columns = Vec::with_capacity(table.total());
+    for (i, row) in table.counts().iter().enumerate() {
+        for (j, count) in row.iter().enumerate() {
+            rows.extend(std::iter::repeat_n(i, *count));
+            columns.extend(std::iter::repeat_n(j as f64, *count));
+        }
+    }
+
+    // Tables are less probable the larger the sum of the logarithms of the factorials of
+    // their counts.
+    let statistic = |groups: &[&[f64]]| {
+        groups
+            .iter()
+            .map(|group| {
+                let mut counts = vec![0; table.columns()];
+                for column in group.iter() {
+                    counts[*column as usize] += 1;
+                }
+                counts.iter().map(|count| ln_factorial(*count)).sum::<f64>()
+            })
+            .sum()
+    };
+    let permutation = PermutationTest::randomization(
+        &columns,
+        RandomizationDesign::new(&rows),
+        statistic,
+        options.with_alternative(AlternativeHypothesis::Greater),
+    )?;
+    Ok(permutation.p_value())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fisher_exact() {
+        // Fisher's tea tasting experiment.
+        let table = ContingencyTable::new(vec![vec![3, 1], vec![1, 3]]).unwrap();
+        let test = FisherExactTest::new(&table, AlternativeHypothesis::Greater).unwrap();
+        assert_eq!(test.p_value(), 0.24285714285714294);
+        assert_eq!(test.estimate(), 6.408319658197707);
+        assert_eq!(
+            test.effect_size().confidence_interval(),
+            (0.3135737675049852, f64::INFINITY)
+        );
+
+        let test = FisherExactTest::new(&table, AlternativeHypothesis::Different).unwrap();
+        assert_eq!(test.p_value(), 0.4857142857142859);
+        assert_eq!(
+            test.effect_size().confidence_interval(),
+            (0.21173559544657858, 626.2435306863342)
+        );
+
+        let test = FisherExactTest::new(&table, AlternativeHypothesis::Less).unwrap();
+        assert_eq!(test.p_value(), 0.9857142857142858);
+        assert_eq!(
+            test.effect_size().confidence_interval(),
+            (0.0, 306.23680785862246)
+        );
+    }
+
+    #[test]
+    fn fisher_exact_boundary() {
+        let table = ContingencyTable::new(vec![vec![5, 0], vec![1, 4]]).unwrap();
+        let test = FisherExactTest::new(&table, AlternativeHypothesis::Different).unwrap();
+        assert_eq!(test.p_value(), 0.04761904761904764);
+        assert_eq!(test.estimate(), f64::INFINITY);
+        assert_eq!(
+            test.effect_size().confidence_interval(),
+            (1.0247975520470554, f64::INFINITY)
+        );
+
+        let table = ContingencyTable::new(vec![vec![5, 0], vec![0, 0]]).unwrap();
+        assert!(FisherExactTest::new(&table, AlternativeHypothesis::Different).is_err());
+    }
+
+    #[test]
+    fn freeman_halton() {
+        let table = ContingencyTable::new(vec![vec![3, 1], vec![1, 3]]).unwrap();
+        let test = FreemanHaltonTest::new(&table, PermutationOptions::default()).unwrap();
+        let fisher = FisherExactTest::new(&table, AlternativeHypothesis::Different).unwrap();
+        assert!((test.p_value() - fisher.p_value()).abs() < 1E-12);
+
+        let table =
+            ContingencyTable::new(vec![vec![2, 3, 0, 4], vec![5, 0, 1, 2], vec![0, 4, 3, 1]])
+                .unwrap();
+        let test = FreemanHaltonTest::new(&table, PermutationOptions::default()).unwrap();
+        assert!(test.is_exact());
+        assert_eq!(test.estimate(), 1.1740728098001242e-5);
+        assert_eq!(test.p_value(), 0.013529623246139664);
+
+        let options = PermutationOptions::default().with_max_exact(10);
+        let test = FreemanHaltonTest::new(&table, options).unwrap();
+        assert!(!test.is_exact());
+        assert_eq!(test.p_value(), 0.014);
+    }
+}
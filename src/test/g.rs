@@ -0,0 +1,355 @@
+use crate::effect_size::{EffectSize, CONFIDENCE_LEVEL};
+use crate::statistics::ContingencyTable;
+use statrs::distribution::{ChiSquared, ContinuousCDF};
+use statrs::{Result, StatsError};
+
+use super::chi_squared::goodness_of_fit_expected;
+use super::StatisticalTest;
+
+/// Implements the [G-test](https://en.wikipedia.org/wiki/G-test), or likelihood-ratio
+/// chi-squared test, of goodness of fit and independence (Sokal & Rohlf, 2012).
+///
+/// # References
+///
+/// Sokal, R. R., & Rohlf, F. J. (2012). Biometry: The Principles and Practice of Statistics in
+///     Biological Research (4th ed.). W. H. Freeman.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GTest {
+    df: f64,
+    estimate: f64,
+    effect_size: EffectSize,
+    p_value: f64,
+}
+
+/// Correction of the G statistic for small samples.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GCorrection {
+    /// No correction.
+    None,
+    /// Williams's correction, which divides the G statistic by a factor that depends on the
+    /// number of categories and the sample size (Williams, 1976).
+    ///
+    /// Williams, D. A. (1976). Improved Likelihood Ratio Tests for Complete Contingency
+    ///     Tables. Biometrika, 63(1), 33–37. <https://doi.org/10.2307/2335081>
+    Williams,
+    /// Yates's continuity correction, which moves every observed count towards its expected
+    /// count by at most 0.5. Only applies to two categories or 2×2 tables.
+    Yates,
+}
+
+impl GTest {
+    /// Run the G-test of independence of the rows and columns of the `table`, with the
+    /// given `correction`.
+    ///
+    /// The effect size is the phi coefficient for 2×2 tables and Cramér's V otherwise, with a
+    /// 95% confidence interval based on the noncentral chi-squared distribution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::statistics::ContingencyTable;
+    /// use stattest::test::{GCorrection, GTest, StatisticalTest};
+    ///
+    /// let table = ContingencyTable::new(vec![vec![20, 15, 5], vec![10, 25, 25]]).unwrap();
+    /// let test = GTest::independence(&table, GCorrection::Williams).unwrap();
+    /// assert!(test.p_value() < 0.01);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a row or column is empty, or if Yates's correction is requested for
+    /// a table that is not 2×2.
+    pub fn independence(table: &ContingencyTable, correction: GCorrection) -> Result<GTest> {
+        let (rows, columns) = (table.rows(), table.columns());
+        if correction == GCorrection::Yates && (rows != 2 || columns != 2) {
+            return Err(StatsError::BadParams);
+        }
+        let expected = table.expected();
+        if expected.iter().flatten().any(|e| *e <= 0.0) {
+            return Err(StatsError::BadParams);
+        }
+
+        let g = table
+            .counts()
+            .iter()
+            .zip(&expected)
+            .map(|(row, expected)| {
+                let counts: Vec<f64> = row.iter().map(|o| *o as f64).collect();
+                g(&counts, expected, correction == GCorrection::Yates)
+            })
+            .sum::<f64>();
+
+        let df = ((rows - 1) * (columns - 1)) as f64;
+        let estimate = if correction == GCorrection::Williams {
+            let n = table.total() as f64;
+            let reciprocals =
+                |totals: Vec<usize>| totals.iter().map(|t| 1.0 / *t as f64).sum::<f64>();
+            let q = 1.0
+                + (n * reciprocals(table.row_totals()) - 1.0)
+                    * (n * reciprocals(table.column_totals()) - 1.0)
+                    / (6.0 * n * df);
+            g / q
+        } else {
+            g
+        };
+
+        let effect_size = if rows == 2 && columns == 2 {
+            EffectSize::phi(table, CONFIDENCE_LEVEL)?
+        } else {
+            EffectSize::cramers_v(table, CONFIDENCE_LEVEL)?
+        };
+        GTest::new(df, estimate, effect_size)
+    }
+
+    /// Run the G-test of goodness of fit of the `observed` counts against the given
+    /// `probabilities` of the categories, which are rescaled to sum to one, with the given
+    /// `correction`.
+    ///
+    /// The effect size is Cohen's w, with a 95% confidence interval based on the noncentral
+    /// chi-squared distribution.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are fewer than two categories, if a probability is not
+    /// positive, if there are no observations, or if Yates's correction is requested for more
+    /// than two categories.
+    pub fn goodness_of_fit(
+        observed: &[usize],
+        probabilities: &[f64],
+        correction: GCorrection,
+    ) -> Result<GTest> {
+        let expected = goodness_of_fit_expected(observed, probabilities)?;
+        if correction == GCorrection::Yates && observed.len() != 2 {
+            return Err(StatsError::BadParams);
+        }
+
+        let counts: Vec<f64> = observed.iter().map(|o| *o as f64).collect();
+        let g = g(&counts, &expected, correction == GCorrection::Yates);
+
+        let k = observed.len() as f64;
+        let df = k - 1.0;
+        let estimate = if correction == GCorrection::Williams {
+            let n = counts.iter().sum::<f64>();
+            g / (1.0 + (k * k - 1.0) / (6.0 * n * df))
+        } else {
+            g
+        };
+
+        let effect_size = EffectSize::cohens_w(observed, &expected, CONFIDENCE_LEVEL)?;
+        GTest::new(df, estimate, effect_size)
+    }
+
+    /// Run replicated G-tests of goodness of fit of the counts of every replicate in
+    /// `replicates` against the given `probabilities` of the categories, without correction.
+    ///
+    /// The total G statistic of the replicates is split into the pooled G statistic of the
+    /// summed counts and the heterogeneity G statistic, which tests whether the replicates
+    /// differ from each other.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are fewer than two replicates, if the replicates differ in
+    /// the number of categories, or if the G-test of goodness of fit of a replicate fails.
+    pub fn heterogeneity(
+        replicates: &[Vec<usize>],
+        probabilities: &[f64],
+    ) -> Result<HeterogeneityGTest> {
+        if replicates.len() < 2 {
+            return Err(StatsError::BadParams);
+        }
+        let table = ContingencyTable::new(replicates.to_vec())?;
+
+        let individual = replicates
+            .iter()
+            .map(|counts| GTest::goodness_of_fit(counts, probabilities, GCorrection::None))
+            .collect::<Result<Vec<GTest>>>()?;
+        let pooled =
+            GTest::goodness_of_fit(&table.column_totals(), probabilities, GCorrection::None)?;
+
+        let df = individual.iter().map(|test| test.df).sum();
+        let estimate = individual.iter().map(|test| test.estimate).sum();
+        let total = GTest::new(df, estimate, pooled.effect_size)?;
+
+        let df = total.df - pooled.df;
+        let estimate = (total.estimate - pooled.estimate).max(0.0);
+        let heterogeneity = GTest::new(
+            df,
+            estimate,
+            EffectSize::cramers_v(&table, CONFIDENCE_LEVEL)?,
+        )?;
+
+        Ok(HeterogeneityGTest {
+            individual,
+            pooled,
+            total,
+            heterogeneity,
+        })
+    }
+
+    fn new(df: f64, estimate: f64, effect_size: EffectSize) -> Result<GTest> {
+        Ok(GTest {
+            df,
+            estimate,
+            effect_size,
+            p_value: ChiSquared::new(df)?.sf(estimate),
+        })
+    }
+
+    /// Returns the degrees of freedom.
+    pub fn df(&self) -> f64 {
+        self.df
+    }
+}
+
+impl StatisticalTest for GTest {
+    type Estimate = f64;
+    type EffectSize = EffectSize;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> EffectSize {
+        self.effect_size
+    }
+}
+
+/// Replicated G-tests of goodness of fit, with the heterogeneity G-test of whether the
+/// replicates differ from each other.
+///
+/// As a [`StatisticalTest`], it reports the heterogeneity G statistic and its p-value, with
+/// Cramér's V of the association between replicates and categories as the effect size.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeterogeneityGTest {
+    individual: Vec<GTest>,
+    pooled: GTest,
+    total: GTest,
+    heterogeneity: GTest,
+}
+
+impl HeterogeneityGTest {
+    /// Returns the G-test of goodness of fit of every replicate.
+    pub fn individual(&self) -> &[GTest] {
+        &self.individual
+    }
+
+    /// Returns the G-test of goodness of fit of the summed counts of the replicates.
+    pub fn pooled(&self) -> GTest {
+        self.pooled
+    }
+
+    /// Returns the G-test with the sum of the G statistics and degrees of freedom of the
+    /// replicates.
+    pub fn total(&self) -> GTest {
+        self.total
+    }
+
+    /// Returns the G-test of heterogeneity, with the difference of the total and pooled G
+    /// statistics and degrees of freedom.
+    pub fn heterogeneity(&self) -> GTest {
+        self.heterogeneity
+    }
+}
+
+impl StatisticalTest for HeterogeneityGTest {
+    type Estimate = f64;
+    type EffectSize = EffectSize;
+
+    fn estimate(&self) -> f64 {
+        self.heterogeneity.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.heterogeneity.p_value
+    }
+
+    fn effect_size(&self) -> EffectSize {
+        self.heterogeneity.effect_size
+    }
+}
+
+/// Returns the G statistic of the `observed` counts, where empty cells contribute nothing,
+/// optionally with Yates's continuity correction.
+fn g(observed: &[f64], expected: &[f64], yates: bool) -> f64 {
+    2.0 * observed
+        .iter()
+        .zip(expected)
+        .map(|(o, e)| {
+            let o = if yates {
+                let deviation = o - e;
+                o - deviation.signum() * deviation.abs().min(0.5)
+            } else {
+                *o
+            };
+            if o > 0.0 {
+                o * (o / e).ln()
+            } else {
+                0.0
+            }
+        })
+        .sum::<f64>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effect_size::EffectSizeKind;
+
+    #[test]
+    fn independence() {
+        let table = ContingencyTable::new(vec![vec![20, 15, 5], vec![10, 25, 25]]).unwrap();
+        let test = GTest::independence(&table, GCorrection::None).unwrap();
+        assert_eq!(test.df(), 2.0);
+        assert_eq!(test.estimate(), 16.452751719545674);
+        assert_eq!(test.p_value(), 0.0002675040478019386);
+        assert_eq!(test.effect_size().kind(), EffectSizeKind::CramersV);
+
+        let test = GTest::independence(&table, GCorrection::Williams).unwrap();
+        assert_eq!(test.estimate(), 16.10565983740167);
+        assert_eq!(test.p_value(), 0.0003182001665331169);
+
+        assert!(GTest::independence(&table, GCorrection::Yates).is_err());
+        let table = ContingencyTable::new(vec![vec![3, 12], vec![9, 4]]).unwrap();
+        let test = GTest::independence(&table, GCorrection::Yates).unwrap();
+        assert_eq!(test.estimate(), 5.173808479542505);
+        assert_eq!(test.effect_size().kind(), EffectSizeKind::Phi);
+    }
+
+    #[test]
+    fn goodness_of_fit() {
+        let observed = vec![423, 133];
+        let test = GTest::goodness_of_fit(&observed, &[3.0, 1.0], GCorrection::None).unwrap();
+        assert_eq!(test.df(), 1.0);
+        assert_eq!(test.estimate(), 0.34872172558756986);
+        assert_eq!(test.p_value(), 0.5548376236131944);
+
+        let test = GTest::goodness_of_fit(&observed, &[3.0, 1.0], GCorrection::Williams).unwrap();
+        assert_eq!(test.estimate(), 0.34840840867329526);
+
+        let test = GTest::goodness_of_fit(&observed, &[3.0, 1.0], GCorrection::Yates).unwrap();
+        assert_eq!(test.estimate(), 0.2927795611373565);
+
+        let observed = vec![10, 20, 30];
+        assert!(GTest::goodness_of_fit(&observed, &[1.0, 1.0, 1.0], GCorrection::Yates).is_err());
+    }
+
+    #[test]
+    fn heterogeneity() {
+        let replicates = vec![vec![15, 10], vec![20, 5], vec![9, 16]];
+        let test = GTest::heterogeneity(&replicates, &[1.0, 1.0]).unwrap();
+        assert_eq!(test.individual().len(), 3);
+        assert_eq!(test.pooled().df(), 1.0);
+        assert_eq!(test.total().df(), 3.0);
+        assert_eq!(test.heterogeneity().df(), 2.0);
+        assert_eq!(test.estimate(), 10.365708300503213);
+        assert_eq!(test.p_value(), 0.0056119661400458026);
+
+        let table = ContingencyTable::new(replicates).unwrap();
+        let independence = GTest::independence(&table, GCorrection::None).unwrap();
+        assert!((test.estimate() - independence.estimate()).abs() < 1E-10);
+    }
+}
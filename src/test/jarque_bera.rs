@@ -0,0 +1,151 @@
+use statrs::distribution::{ChiSquared, ContinuousCDF};
+use statrs::{Result, StatsError};
+
+use super::dagostino_pearson::Moments;
+use super::StatisticalTest;
+
+/// Implements the [Jarque-Bera test](https://en.wikipedia.org/wiki/Jarque%E2%80%93Bera_test)
+/// of normality (Jarque & Bera, 1980), which compares the sample skewness and kurtosis to
+/// those of a normal distribution.
+///
+/// # References
+///
+/// Jarque, C. M., & Bera, A. K. (1980). Efficient Tests for Normality, Homoscedasticity and
+///     Serial Independence of Regression Residuals. Economics Letters, 6(3), 255–259.
+///     <https://doi.org/10.1016/0165-1765(80)90024-5>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct JarqueBeraTest {
+    estimate: f64,
+    p_value: f64,
+    skewness: f64,
+    excess_kurtosis: f64,
+}
+
+impl JarqueBeraTest {
+    /// Run the Jarque-Bera test on the sample `x`. The p-value uses the asymptotic
+    /// χ²-distribution with two degrees of freedom, which rejects too rarely in small samples.
+    /// The estimate and the effect size are the statistic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::test::{JarqueBeraTest, StatisticalTest};
+    ///
+    /// let x = vec![
+    ///     0.1, 0.1, 0.2, 0.2, 0.3, 0.3, 0.4, 0.5, 0.6, 0.8, 1.0, 1.3, 1.9, 2.8, 4.5, 7.2,
+    /// ];
+    /// let test = JarqueBeraTest::new(&x).unwrap();
+    /// assert!(test.p_value() < 0.05);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample has fewer than four observations or no variation, or if
+    /// it contains NaN.
+    pub fn new(x: &[f64]) -> Result<JarqueBeraTest> {
+        let moments = moments(x)?;
+        let n = moments.n;
+        let excess_kurtosis = moments.kurtosis - 3.0;
+        let estimate = n / 6.0 * (moments.skewness.powi(2) + excess_kurtosis.powi(2) / 4.0);
+        JarqueBeraTest::with_statistic(estimate, &moments)
+    }
+
+    /// Run the Jarque-Bera test on the sample `x` with the adjustment of Urzúa (1996), which
+    /// standardizes the skewness and the kurtosis with their exact means and variances under
+    /// normality, so that the χ²-distribution holds better in small samples. The estimate and
+    /// the effect size are the adjusted statistic.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample has fewer than four observations or no variation, or if
+    /// it contains NaN.
+    ///
+    /// # References
+    ///
+    /// Urzúa, C. M. (1996). On the Correct Use of Omnibus Tests for Normality. Economics
+    ///     Letters, 53(3), 247–251. <https://doi.org/10.1016/S0165-1765(96)00923-8>
+    pub fn adjusted(x: &[f64]) -> Result<JarqueBeraTest> {
+        let moments = moments(x)?;
+        let n = moments.n;
+        let skewness_variance = 6.0 * (n - 2.0) / ((n + 1.0) * (n + 3.0));
+        let kurtosis_mean = 3.0 * (n - 1.0) / (n + 1.0);
+        let kurtosis_variance =
+            24.0 * n * (n - 2.0) * (n - 3.0) / ((n + 1.0).powi(2) * (n + 3.0) * (n + 5.0));
+        let estimate = moments.skewness.powi(2) / skewness_variance
+            + (moments.kurtosis - kurtosis_mean).powi(2) / kurtosis_variance;
+        JarqueBeraTest::with_statistic(estimate, &moments)
+    }
+
+    /// Returns the sample skewness g₁.
+    pub fn skewness(&self) -> f64 {
+        self.skewness
+    }
+
+    /// Returns the sample excess kurtosis b₂ − 3.
+    pub fn excess_kurtosis(&self) -> f64 {
+        self.excess_kurtosis
+    }
+
+    fn with_statistic(estimate: f64, moments: &Moments) -> Result<JarqueBeraTest> {
+        Ok(JarqueBeraTest {
+            estimate,
+            p_value: ChiSquared::new(2.0)?.sf(estimate),
+            skewness: moments.skewness,
+            excess_kurtosis: moments.kurtosis - 3.0,
+        })
+    }
+}
+
+impl StatisticalTest for JarqueBeraTest {
+    type Estimate = f64;
+    type EffectSize = f64;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> f64 {
+        self.estimate
+    }
+}
+
+fn moments(x: &[f64]) -> Result<Moments> {
+    let moments = Moments::new(x)?;
+    if moments.n < 4.0 {
+        return Err(StatsError::BadParams);
+    }
+    Ok(moments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<f64> {
+        vec![
+            148.0, 154.0, 158.0, 160.0, 161.0, 162.0, 166.0, 170.0, 182.0, 195.0, 236.0, 149.0,
+            155.0, 158.0, 160.0, 161.0, 163.0, 167.0, 171.0, 184.0, 196.0, 239.0,
+        ]
+    }
+
+    #[test]
+    fn jarque_bera() {
+        let test = JarqueBeraTest::new(&sample()).unwrap();
+        assert_eq!(test.estimate(), 14.181511448483395);
+        assert_eq!(test.p_value(), 0.0008327677849391901);
+        assert_eq!(test.skewness(), 1.687567057289822);
+        assert_eq!(test.excess_kurtosis(), 2.0197052930025627);
+        assert!(JarqueBeraTest::new(&sample()[..3]).is_err());
+    }
+
+    #[test]
+    fn adjusted() {
+        let test = JarqueBeraTest::adjusted(&sample()).unwrap();
+        assert_eq!(test.estimate(), 22.90225838981496);
+        assert_eq!(test.p_value(), 1.0637456058251553e-5);
+    }
+}
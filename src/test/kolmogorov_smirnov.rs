@@ -0,0 +1,538 @@
+use statrs::distribution::ContinuousCDF;
+use statrs::function::factorial::ln_binomial;
+use statrs::{Result, StatsError};
+use std::f64::consts::PI;
+
+use super::{AlternativeHypothesis, StatisticalTest};
+
+/// Largest sample size for which p-values are computed exactly.
+static MAX_EXACT: usize = 100;
+/// Product of the sample sizes of two samples below which p-values are computed exactly.
+static MAX_EXACT_PRODUCT: usize = 10_000;
+
+/// Implements the [Kolmogorov-Smirnov test](https://en.wikipedia.org/wiki/Kolmogorov%E2%80%93Smirnov_test)
+/// of whether a sample comes from a fully specified continuous distribution (Kolmogorov, 1933;
+/// Smirnov, 1948).
+///
+/// # References
+///
+/// Kolmogorov, A. N. (1933). Sulla determinazione empirica di una legge di distribuzione.
+///     Giornale dell'Istituto Italiano degli Attuari, 4, 83–91.
+///
+/// Smirnov, N. (1948). Table for Estimating the Goodness of Fit of Empirical Distributions.
+///     The Annals of Mathematical Statistics, 19(2), 279–281.
+///     <https://doi.org/10.1214/aoms/1177730256>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct KolmogorovSmirnovTest {
+    estimate: f64,
+    p_value: f64,
+    exact: bool,
+}
+
+impl KolmogorovSmirnovTest {
+    /// Run the Kolmogorov-Smirnov test of whether the sample `x` comes from the continuous
+    /// `distribution`, whose parameters must not be estimated from the sample. The
+    /// `alternative` hypothesis refers to the empirical distribution function of `x` compared
+    /// to that of the `distribution`, where `Greater` uses the largest deviation D⁺ above it,
+    /// `Less` uses the largest deviation D⁻ below it, and `Different` uses the largest
+    /// absolute deviation D.
+    ///
+    /// For samples of up to 100 observations, the two-sided p-value is exact (Marsaglia et
+    /// al., 2003), as are one-sided p-values (Birnbaum & Tingey, 1951). Larger samples use the
+    /// asymptotic distributions. The estimate and the effect size are the statistic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Normal;
+    /// use stattest::test::{AlternativeHypothesis, KolmogorovSmirnovTest, StatisticalTest};
+    ///
+    /// let x = vec![-1.2, -0.4, 0.1, 0.3, 0.8, 1.1, -0.7, 0.5, 2.1, -0.2];
+    /// let normal = Normal::new(0.0, 1.0).unwrap();
+    /// let test = KolmogorovSmirnovTest::new(&x, &normal, AlternativeHypothesis::Different)
+    ///     .unwrap();
+    /// assert!(test.p_value() > 0.05);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample is empty or contains NaN.
+    ///
+    /// # References
+    ///
+    /// Birnbaum, Z. W., & Tingey, F. H. (1951). One-Sided Confidence Contours for Probability
+    ///     Distribution Functions. The Annals of Mathematical Statistics, 22(4), 592–596.
+    ///     <https://doi.org/10.1214/aoms/1177729550>
+    ///
+    /// Marsaglia, G., Tsang, W. W., & Wang, J. (2003). Evaluating Kolmogorov's Distribution.
+    ///     Journal of Statistical Software, 8(18), 1–4. <https://doi.org/10.18637/jss.v008.i18>
+    pub fn new<D>(
+        x: &[f64],
+        distribution: &D,
+        alternative: AlternativeHypothesis,
+    ) -> Result<KolmogorovSmirnovTest>
+    where
+        D: ContinuousCDF<f64, f64>,
+    {
+        let (above, below) = one_sample_deviations(x, distribution)?;
+        let n = x.len();
+        let exact = n <= MAX_EXACT;
+        let (estimate, p_value) = match alternative {
+            AlternativeHypothesis::Greater => (above, one_sided_sf(n, above, exact)),
+            AlternativeHypothesis::Less => (below, one_sided_sf(n, below, exact)),
+            AlternativeHypothesis::Different => {
+                let d = above.max(below);
+                let p_value = if exact {
+                    1.0 - kolmogorov_cdf(n, d)
+                } else {
+                    kolmogorov_asymptotic_sf(d * (n as f64).sqrt())
+                };
+                (d, p_value)
+            }
+        };
+
+        Ok(KolmogorovSmirnovTest {
+            estimate,
+            p_value: p_value.clamp(0.0, 1.0),
+            exact,
+        })
+    }
+
+    /// Run the two-sample Kolmogorov-Smirnov test of whether the samples `x` and `y` come from
+    /// the same continuous distribution. Unlike the Mann-Whitney U test, it detects any
+    /// difference between the distributions, not only shifts in location. The `alternative`
+    /// hypothesis refers to the empirical distribution function of `x` compared to that of
+    /// `y`, where `Greater` uses the largest deviation D⁺ above it, `Less` uses the largest
+    /// deviation D⁻ below it, and `Different` uses the largest absolute deviation D.
+    ///
+    /// When the product of the sample sizes is below 10,000, the p-value is exact, and
+    /// accounts for ties by only comparing the distribution functions after each distinct
+    /// value (Schröer & Trenkler, 1995). Larger samples use the asymptotic distributions. The
+    /// estimate and the effect size are the statistic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::test::{AlternativeHypothesis, KolmogorovSmirnovTest, StatisticalTest};
+    ///
+    /// let x = vec![0.8, 1.1, 1.3, 1.4, 1.9, 2.2, 2.4, 2.8];
+    /// let y = vec![1.5, 2.6, 2.9, 3.1, 3.3, 3.6, 4.0, 4.2];
+    /// let test = KolmogorovSmirnovTest::two_sample(&x, &y, AlternativeHypothesis::Different)
+    ///     .unwrap();
+    /// assert!(test.p_value() < 0.05);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either sample is empty or contains NaN.
+    ///
+    /// # References
+    ///
+    /// Schröer, G., & Trenkler, D. (1995). Exact and Randomization Distributions of
+    ///     Kolmogorov-Smirnov Tests Two or Three Samples. Computational Statistics & Data
+    ///     Analysis, 20(2), 185–202. <https://doi.org/10.1016/0167-9473(94)00040-P>
+    pub fn two_sample(
+        x: &[f64],
+        y: &[f64],
+        alternative: AlternativeHypothesis,
+    ) -> Result<KolmogorovSmirnovTest> {
+        let steps = Steps::new(x, y)?;
+        let (above, below) = steps.deviations();
+        let (m, n) = (steps.m as f64, steps.n as f64);
+        let scale = m * n;
+        let exact = steps.m * steps.n < MAX_EXACT_PRODUCT;
+
+        let (deviation, inside): (i64, fn(i64, i64) -> bool) = match alternative {
+            AlternativeHypothesis::Greater => (above, |d, limit| d < limit),
+            AlternativeHypothesis::Less => (below, |d, limit| -d < limit),
+            AlternativeHypothesis::Different => (above.max(below), |d, limit| d.abs() < limit),
+        };
+        let estimate = deviation as f64 / scale;
+        let p_value = if exact {
+            1.0 - steps.probability_inside(|d| inside(d, deviation))
+        } else {
+            let effective = m * n / (m + n);
+            match alternative {
+                AlternativeHypothesis::Different => {
+                    kolmogorov_asymptotic_sf(estimate * effective.sqrt())
+                }
+                _ => (-2.0 * effective * estimate * estimate).exp(),
+            }
+        };
+
+        Ok(KolmogorovSmirnovTest {
+            estimate,
+            p_value: p_value.clamp(0.0, 1.0),
+            exact,
+        })
+    }
+
+    /// Returns whether the p-value was computed exactly rather than from the asymptotic
+    /// distribution.
+    pub fn is_exact(&self) -> bool {
+        self.exact
+    }
+}
+
+impl StatisticalTest for KolmogorovSmirnovTest {
+    type Estimate = f64;
+    type EffectSize = f64;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> f64 {
+        self.estimate
+    }
+}
+
+/// Returns the largest deviations of the empirical distribution function of the sample `x`
+/// above and below the `distribution` function.
+pub(super) fn one_sample_deviations<D>(x: &[f64], distribution: &D) -> Result<(f64, f64)>
+where
+    D: ContinuousCDF<f64, f64>,
+{
+    if x.is_empty() || x.iter().any(|x| x.is_nan()) {
+        return Err(StatsError::BadParams);
+    }
+    let mut sorted = x.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let n = sorted.len() as f64;
+    let (mut above, mut below) = (0.0_f64, 0.0_f64);
+    for (i, x) in sorted.iter().enumerate() {
+        let cdf = distribution.cdf(*x);
+        above = above.max((i + 1) as f64 / n - cdf);
+        below = below.max(cdf - i as f64 / n);
+    }
+    Ok((above, below))
+}
+
+/// The path of the pooled samples through the lattice of the counts of observations from
+/// `x` and `y`, where the empirical distribution functions are compared after each distinct
+/// value.
+pub(super) struct Steps {
+    pub(super) m: usize,
+    pub(super) n: usize,
+    /// Whether the pooled observation with the given rank, counting from 1, is the last of
+    /// its value.
+    last_of_value: Vec<bool>,
+    /// Counts of observations from `x` and `y` after each distinct value.
+    counts: Vec<(usize, usize)>,
+}
+
+impl Steps {
+    pub(super) fn new(x: &[f64], y: &[f64]) -> Result<Steps> {
+        if x.is_empty() || y.is_empty() || x.iter().chain(y).any(|x| x.is_nan()) {
+            return Err(StatsError::BadParams);
+        }
+        let mut pooled: Vec<(f64, bool)> = x
+            .iter()
+            .map(|x| (*x, true))
+            .chain(y.iter().map(|y| (*y, false)))
+            .collect();
+        pooled.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        let mut last_of_value = vec![false; pooled.len() + 1];
+        let mut counts = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        for (k, (value, from_x)) in pooled.iter().enumerate() {
+            if *from_x {
+                i += 1;
+            } else {
+                j += 1;
+            }
+            if pooled.get(k + 1).is_none_or(|next| next.0 != *value) {
+                last_of_value[k + 1] = true;
+                counts.push((i, j));
+            }
+        }
+
+        Ok(Steps {
+            m: x.len(),
+            n: y.len(),
+            last_of_value,
+            counts,
+        })
+    }
+
+    /// Returns the difference of the empirical distribution functions of `x` and `y` after
+    /// `i` and `j` observations, scaled by the product of the sample sizes.
+    fn difference(&self, i: usize, j: usize) -> i64 {
+        (i * self.n) as i64 - (j * self.m) as i64
+    }
+
+    /// Returns the largest scaled deviations of the empirical distribution function of `x`
+    /// above and below that of `y`.
+    pub(super) fn deviations(&self) -> (i64, i64) {
+        self.counts.iter().fold((0, 0), |(above, below), (i, j)| {
+            let d = self.difference(*i, *j);
+            (above.max(d), below.max(-d))
+        })
+    }
+
+    /// Returns the probability under the null hypothesis that every scaled difference of the
+    /// empirical distribution functions after a distinct value is `inside` the acceptance
+    /// region, by counting the paths through the lattice (Schröer & Trenkler, 1995).
+    fn probability_inside<F>(&self, inside: F) -> f64
+    where
+        F: Fn(i64) -> bool,
+    {
+        // The probability of reaching a point is the number of paths to it divided by the
+        // number of all paths to it, so that the recursion stays between 0 and 1.
+        let mut probabilities = vec![0.0; self.n + 1];
+        for i in 0..=self.m {
+            for j in 0..=self.n {
+                let probability = if i == 0 && j == 0 {
+                    1.0
+                } else {
+                    let (i_f, j_f) = (i as f64, j as f64);
+                    let from_x = if i > 0 { probabilities[j] * i_f } else { 0.0 };
+                    let from_y = if j > 0 {
+                        probabilities[j - 1] * j_f
+                    } else {
+                        0.0
+                    };
+                    (from_x + from_y) / (i_f + j_f)
+                };
+                probabilities[j] = if self.last_of_value[i + j] && !inside(self.difference(i, j)) {
+                    0.0
+                } else {
+                    probability
+                };
+            }
+        }
+        probabilities[self.n]
+    }
+}
+
+/// Returns the probability that the one-sided statistic of a sample of size `n` is at least
+/// `d`, either exactly (Birnbaum & Tingey, 1951) or from the asymptotic distribution.
+fn one_sided_sf(n: usize, d: f64, exact: bool) -> f64 {
+    if d <= 0.0 {
+        return 1.0;
+    }
+    if d >= 1.0 {
+        return 0.0;
+    }
+    let n_f = n as f64;
+    if !exact {
+        return (-2.0 * n_f * d * d).exp();
+    }
+    let terms = (n_f * (1.0 - d)).floor() as usize;
+    d * (0..=terms)
+        .map(|j| {
+            let j_f = j as f64;
+            let base = 1.0 - d - j_f / n_f;
+            if base <= 0.0 {
+                // Only the last term can reach zero, and j < n, so it vanishes.
+                return 0.0;
+            }
+            (ln_binomial(n as u64, j as u64)
+                + (n - j) as f64 * base.ln()
+                + (j_f - 1.0) * (d + j_f / n_f).ln())
+            .exp()
+        })
+        .sum::<f64>()
+}
+
+/// Returns the probability that the two-sided statistic of a sample of size `n` is less than
+/// `d`, computed exactly as an element of a power of a matrix (Marsaglia et al., 2003).
+fn kolmogorov_cdf(n: usize, d: f64) -> f64 {
+    let n_f = n as f64;
+    if d <= 0.5 / n_f {
+        return 0.0;
+    }
+    if d >= 1.0 {
+        return 1.0;
+    }
+    let k = (n_f * d) as usize + 1;
+    let m = 2 * k - 1;
+    let h = k as f64 - n_f * d;
+
+    let mut matrix = vec![vec![0.0; m]; m];
+    for (i, row) in matrix.iter_mut().enumerate() {
+        for (j, entry) in row.iter_mut().enumerate() {
+            if i + 1 >= j {
+                *entry = 1.0;
+            }
+            if j == 0 {
+                *entry -= h.powi(i as i32 + 1);
+            }
+            if i == m - 1 {
+                *entry -= h.powi((m - j) as i32);
+            }
+        }
+    }
+    if 2.0 * h > 1.0 {
+        matrix[m - 1][0] += (2.0 * h - 1.0).powi(m as i32);
+    }
+    for (i, row) in matrix.iter_mut().enumerate() {
+        for (j, entry) in row.iter_mut().enumerate() {
+            if i + 1 > j {
+                for g in 1..=(i + 1 - j) {
+                    *entry /= g as f64;
+                }
+            }
+        }
+    }
+
+    let (power, mut exponent) = matrix_power(&matrix, n);
+    // Multiply by n! / n^n in steps, rescaling by powers of 10 to avoid underflow.
+    let mut s = power[k - 1][k - 1];
+    for i in 1..=n {
+        s *= i as f64 / n_f;
+        if s < 1E-140 {
+            s *= 1E140;
+            exponent -= 140;
+        }
+    }
+    s * 10_f64.powi(exponent)
+}
+
+/// Returns the `n`th power of the square `matrix` as a matrix and a decimal exponent, by
+/// which it must be scaled, so that its entries do not overflow.
+fn matrix_power(matrix: &[Vec<f64>], n: usize) -> (Vec<Vec<f64>>, i32) {
+    if n == 1 {
+        return (matrix.to_vec(), 0);
+    }
+    let (half, exponent) = matrix_power(matrix, n / 2);
+    let mut power = multiply(&half, &half);
+    let mut exponent = 2 * exponent;
+    if n % 2 == 1 {
+        power = multiply(matrix, &power);
+    }
+    let m = matrix.len();
+    if power[m / 2][m / 2] > 1E140 {
+        for entry in power.iter_mut().flatten() {
+            *entry *= 1E-140;
+        }
+        exponent += 140;
+    }
+    (power, exponent)
+}
+
+fn multiply(a: &[Vec<f64>], b: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    a.iter()
+        .map(|row| {
+            (0..b.len())
+                .map(|j| row.iter().zip(b).map(|(a, b)| a * b[j]).sum())
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the probability that the limiting Kolmogorov distribution exceeds `t`, the
+/// two-sided statistic scaled by the square root of the sample size.
+pub(super) fn kolmogorov_asymptotic_sf(t: f64) -> f64 {
+    if t <= 0.0 {
+        return 1.0;
+    }
+    if t < 1.0 {
+        // The alternating series converges slowly for small values, so use the series of the
+        // distribution function instead.
+        let z = -PI * PI / (8.0 * t * t);
+        let cdf = (2.0 * PI).sqrt() / t
+            * (1..=20)
+                .map(|k| (z * ((2 * k - 1) as f64).powi(2)).exp())
+                .sum::<f64>();
+        1.0 - cdf
+    } else {
+        2.0 * (1..=20)
+            .map(|k| {
+                let sign = if k % 2 == 1 { 1.0 } else { -1.0 };
+                sign * (-2.0 * (k as f64 * t).powi(2)).exp()
+            })
+            .sum::<f64>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use statrs::distribution::{Exp, Normal, Uniform};
+
+    fn sample() -> Vec<f64> {
+        vec![
+            0.61, 0.29, 0.06, 0.59, -1.73, -0.74, 0.51, -0.56, 0.39, 1.64, 0.05, -0.06, 0.64,
+            -0.82, 0.37, 1.77, 1.09, -1.28, 2.36, 1.31, 1.05, -0.32, -0.4, 1.06, -2.47,
+        ]
+    }
+
+    #[test]
+    fn kolmogorov_smirnov() {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let test = KolmogorovSmirnovTest::new(&sample(), &normal, AlternativeHypothesis::Different)
+            .unwrap();
+        assert!(test.is_exact());
+        assert_eq!(test.estimate(), 0.17409188119887736);
+        assert_eq!(test.p_value(), 0.389741171867918);
+
+        let uniform = Uniform::new(-3.0, 3.0).unwrap();
+        let test = KolmogorovSmirnovTest::new(&sample(), &uniform, AlternativeHypothesis::Greater)
+            .unwrap();
+        assert_eq!(test.estimate(), 0.16500000000000004);
+        assert_eq!(test.p_value(), 0.2304460459427184);
+        let test =
+            KolmogorovSmirnovTest::new(&sample(), &uniform, AlternativeHypothesis::Less).unwrap();
+        assert_eq!(test.estimate(), 0.24333333333333335);
+        assert_eq!(test.p_value(), 0.04336513370874217);
+
+        assert!(KolmogorovSmirnovTest::new(&[], &normal, AlternativeHypothesis::Less).is_err());
+    }
+
+    #[test]
+    fn asymptotic() {
+        let x: Vec<f64> = (0..200).map(|i| (i as f64 + 0.5) / 200.0).collect();
+        let exponential = Exp::new(1.0).unwrap();
+        let test =
+            KolmogorovSmirnovTest::new(&x, &exponential, AlternativeHypothesis::Different).unwrap();
+        assert!(!test.is_exact());
+        assert_eq!(test.estimate(), 0.36880029035624307);
+        assert_eq!(test.p_value(), 4.710187639064501e-24);
+    }
+
+    #[test]
+    fn two_sample() {
+        let x = vec![
+            0.61, 0.29, 0.06, 0.59, -1.73, -0.74, 0.51, -0.56, 0.39, 1.64, 0.05, -0.06,
+        ];
+        let y = vec![0.64, -0.82, 0.37, 1.77, 1.09, 1.28, 2.36, 1.31, 1.05, 0.32];
+        let test =
+            KolmogorovSmirnovTest::two_sample(&x, &y, AlternativeHypothesis::Different).unwrap();
+        assert!(test.is_exact());
+        assert_eq!(test.estimate(), 0.6166666666666667);
+        assert_eq!(test.p_value(), 0.019809911450778372);
+        let test =
+            KolmogorovSmirnovTest::two_sample(&x, &y, AlternativeHypothesis::Greater).unwrap();
+        assert_eq!(test.estimate(), 0.6166666666666667);
+        assert_eq!(test.p_value(), 0.009904955725389186);
+
+        let x = vec![1.0, 2.0, 2.0, 3.0, 3.0, 3.0, 4.0];
+        let y = vec![2.0, 3.0, 4.0, 4.0, 5.0, 5.0];
+        let test =
+            KolmogorovSmirnovTest::two_sample(&x, &y, AlternativeHypothesis::Different).unwrap();
+        assert_eq!(test.estimate(), 0.5238095238095238);
+        assert_eq!(test.p_value(), 0.14918414918414935);
+
+        let x: Vec<f64> = (0..120).map(|i| i as f64).collect();
+        let y: Vec<f64> = (0..100).map(|i| i as f64 * 1.5).collect();
+        let test =
+            KolmogorovSmirnovTest::two_sample(&x, &y, AlternativeHypothesis::Different).unwrap();
+        assert!(!test.is_exact());
+        assert_eq!(test.p_value(), 0.02367060859941777);
+    }
+
+    #[test]
+    fn distribution() {
+        assert_eq!(kolmogorov_cdf(10, 0.274), 0.6284796154565044);
+        assert_eq!(kolmogorov_asymptotic_sf(1.36), 0.049485876755377876);
+        assert_eq!(kolmogorov_asymptotic_sf(0.5), 0.9639452436648751);
+        assert_eq!(one_sided_sf(5, 0.3, true), 0.3428199999999998);
+    }
+}
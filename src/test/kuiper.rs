@@ -0,0 +1,150 @@
+use statrs::distribution::ContinuousCDF;
+use statrs::Result;
+
+use super::kolmogorov_smirnov::{one_sample_deviations, Steps};
+use super::StatisticalTest;
+
+/// Implements [Kuiper's test](https://en.wikipedia.org/wiki/Kuiper%27s_test) of whether
+/// samples come from the same continuous distribution (Kuiper, 1960). Its statistic V adds
+/// the largest deviations of the distribution functions in both directions, which makes it
+/// as sensitive in the tails as near the median, and invariant to the choice of origin of
+/// circular data such as angles or times of day.
+///
+/// P-values use the asymptotic distribution with the correction for finite samples of
+/// Stephens (1970).
+///
+/// # References
+///
+/// Kuiper, N. H. (1960). Tests Concerning Random Points on a Circle. Proceedings of the
+///     Koninklijke Nederlandse Akademie van Wetenschappen, Series A, 63, 38–47.
+///     <https://doi.org/10.1016/S1385-7258(60)50006-0>
+///
+/// Stephens, M. A. (1970). Use of the Kolmogorov-Smirnov, Cramer-Von Mises and Related
+///     Statistics Without Extensive Tables. Journal of the Royal Statistical Society. Series B
+///     (Methodological), 32(1), 115–122. <https://doi.org/10.1111/j.2517-6161.1970.tb00821.x>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct KuiperTest {
+    estimate: f64,
+    p_value: f64,
+}
+
+impl KuiperTest {
+    /// Run Kuiper's test of whether the sample `x` comes from the continuous `distribution`,
+    /// whose parameters must not be estimated from the sample. The estimate and the effect
+    /// size are the statistic V.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Uniform;
+    /// use stattest::test::{KuiperTest, StatisticalTest};
+    ///
+    /// // Hours of the day at which incidents were reported.
+    /// let x = vec![0.5, 1.2, 2.8, 3.1, 4.4, 5.0, 6.2, 22.9, 23.3, 23.8, 1.9, 2.2];
+    /// let uniform = Uniform::new(0.0, 24.0).unwrap();
+    /// let test = KuiperTest::new(&x, &uniform).unwrap();
+    /// assert!(test.p_value() < 0.05);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample is empty or contains NaN.
+    pub fn new<D>(x: &[f64], distribution: &D) -> Result<KuiperTest>
+    where
+        D: ContinuousCDF<f64, f64>,
+    {
+        let (above, below) = one_sample_deviations(x, distribution)?;
+        let estimate = above + below;
+        Ok(KuiperTest {
+            estimate,
+            p_value: kuiper_sf(estimate, x.len() as f64),
+        })
+    }
+
+    /// Run Kuiper's two-sample test of whether the samples `x` and `y` come from the same
+    /// continuous distribution. The estimate and the effect size are the statistic V.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either sample is empty or contains NaN.
+    pub fn two_sample(x: &[f64], y: &[f64]) -> Result<KuiperTest> {
+        let steps = Steps::new(x, y)?;
+        let (above, below) = steps.deviations();
+        let (m, n) = (x.len() as f64, y.len() as f64);
+        let estimate = (above + below) as f64 / (m * n);
+        Ok(KuiperTest {
+            estimate,
+            p_value: kuiper_sf(estimate, m * n / (m + n)),
+        })
+    }
+}
+
+impl StatisticalTest for KuiperTest {
+    type Estimate = f64;
+    type EffectSize = f64;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> f64 {
+        self.estimate
+    }
+}
+
+/// Returns the probability that the statistic `v` of samples with the `effective` size is
+/// exceeded, from the asymptotic distribution with the correction of Stephens (1970).
+fn kuiper_sf(v: f64, effective: f64) -> f64 {
+    let root = effective.sqrt();
+    let lambda = (root + 0.155 + 0.24 / root) * v;
+    // The series does not converge for small values, where the probability is close to 1.
+    if lambda < 0.4 {
+        return 1.0;
+    }
+    let sum = (1..=100)
+        .map(|k| {
+            let term = (k as f64 * lambda).powi(2);
+            (4.0 * term - 1.0) * (-2.0 * term).exp()
+        })
+        .sum::<f64>();
+    (2.0 * sum).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use statrs::distribution::Uniform;
+
+    #[test]
+    fn kuiper() {
+        let x = vec![
+            0.61, 0.29, 0.06, 0.59, -1.73, -0.74, 0.51, -0.56, 0.39, 1.64, 0.05, -0.06, 0.64,
+            -0.82, 0.37, 1.77, 1.09, -1.28, 2.36, 1.31, 1.05, -0.32, -0.4, 1.06, -2.47,
+        ];
+        let uniform = Uniform::new(-3.0, 3.0).unwrap();
+        let test = KuiperTest::new(&x, &uniform).unwrap();
+        assert_eq!(test.estimate(), 0.4083333333333334);
+        assert_eq!(test.p_value(), 0.004095337817764203);
+
+        // Shifting circular data by a constant does not change the statistic.
+        let shifted: Vec<f64> = x.iter().map(|x| (x + 4.0).rem_euclid(6.0) - 3.0).collect();
+        let test = KuiperTest::new(&shifted, &uniform).unwrap();
+        assert!((test.estimate() - 0.4083333333333334).abs() < 1E-12);
+    }
+
+    #[test]
+    fn two_sample() {
+        let x = vec![
+            0.61, 0.29, 0.06, 0.59, -1.73, -0.74, 0.51, -0.56, 0.39, 1.64, 0.05, -0.06,
+        ];
+        let y = vec![0.64, -0.82, 0.37, 1.77, 1.09, 1.28, 2.36, 1.31, 1.05, 0.32];
+        let test = KuiperTest::two_sample(&x, &y).unwrap();
+        assert_eq!(test.estimate(), 0.6333333333333333);
+        assert_eq!(test.p_value(), 0.08888243076314115);
+        assert!(KuiperTest::two_sample(&x, &[]).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::effect_size::{EffectSize, CONFIDENCE_LEVEL};
 use crate::statistics::StatisticsExt;
 use statrs::distribution::{ContinuousCDF, FisherSnedecor};
 use statrs::statistics::Statistics;
@@ -14,11 +15,14 @@ use super::StatisticalTest;
 pub struct LevenesTest {
     df: f64,
     estimate: f64,
+    effect_size: EffectSize,
     p_value: f64,
 }
 
 impl LevenesTest {
     /// Run Levene's test on the samples `x` and `y`.
+    ///
+    /// The effect size is the ratio of the variances, with a 95% confidence interval.
     pub fn new(x: &[f64], y: &[f64]) -> statrs::Result<LevenesTest> {
         let n_x = x.n();
         let n_y = y.n();
@@ -41,10 +45,12 @@ impl LevenesTest {
         let estimate = df * a / b;
         let distribution = FisherSnedecor::new(1.0, df)?;
         let p_value = 1.0 - distribution.cdf(estimate);
+        let effect_size = EffectSize::variance_ratio(x, y, CONFIDENCE_LEVEL)?;
 
         Ok(LevenesTest {
             df,
             estimate,
+            effect_size,
             p_value,
         })
     }
@@ -52,6 +58,7 @@ impl LevenesTest {
 
 impl StatisticalTest for LevenesTest {
     type Estimate = f64;
+    type EffectSize = EffectSize;
 
     fn estimate(&self) -> f64 {
         self.estimate
@@ -61,8 +68,8 @@ impl StatisticalTest for LevenesTest {
         self.p_value
     }
 
-    fn effect_size(&self) -> f64 {
-        self.estimate
+    fn effect_size(&self) -> EffectSize {
+        self.effect_size
     }
 }
 
@@ -80,6 +87,6 @@ mod tests {
         assert_eq!(result.df, 17.0);
         assert_eq!(result.estimate(), 0.014721055064513417);
         assert_eq!(result.p_value(), 0.9048519802923365);
-        assert_eq!(result.effect_size(), 0.014721055064513417);
+        assert_eq!(result.effect_size().estimate(), 1.0755200911940725);
     }
 }
@@ -0,0 +1,206 @@
+use crate::bootstrap::BootstrapOptions;
+use rand::distributions::Distribution;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use statrs::distribution::Normal;
+use statrs::statistics::Statistics;
+use statrs::{Result, StatsError};
+
+use super::kolmogorov_smirnov::one_sample_deviations;
+use super::StatisticalTest;
+
+/// Implements the [Lilliefors test](https://en.wikipedia.org/wiki/Lilliefors_test) of
+/// normality (Lilliefors, 1967), the Kolmogorov-Smirnov test against the normal distribution
+/// with the mean and the standard deviation estimated from the sample. Unlike
+/// [`ShapiroWilkTest`](super::ShapiroWilkTest), it has no limit on the sample size.
+///
+/// # References
+///
+/// Lilliefors, H. W. (1967). On the Kolmogorov-Smirnov Test for Normality with Mean and
+///     Variance Unknown. Journal of the American Statistical Association, 62(318), 399–402.
+///     <https://doi.org/10.1080/01621459.1967.10482916>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LillieforsTest {
+    estimate: f64,
+    p_value: f64,
+}
+
+impl LillieforsTest {
+    /// Run the Lilliefors test on the sample `x`. The p-value uses the approximation of
+    /// Dallal and Wilkinson (1986) where it is below 0.1, and the approximation of Stephens
+    /// (1974) for the statistic modified for the sample size otherwise. The estimate and the
+    /// effect size are the statistic D.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::test::{LillieforsTest, StatisticalTest};
+    ///
+    /// let x = vec![
+    ///     0.1, 0.1, 0.2, 0.2, 0.3, 0.3, 0.4, 0.5, 0.6, 0.8, 1.0, 1.3, 1.9, 2.8, 4.5, 7.2,
+    /// ];
+    /// let test = LillieforsTest::new(&x).unwrap();
+    /// assert!(test.p_value() < 0.05);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample has fewer than five observations or no variation, or if
+    /// it contains NaN.
+    ///
+    /// # References
+    ///
+    /// Dallal, G. E., & Wilkinson, L. (1986). An Analytic Approximation to the Distribution of
+    ///     Lilliefors's Test Statistic for Normality. The American Statistician, 40(4),
+    ///     294–296. <https://doi.org/10.1080/00031305.1986.10475419>
+    ///
+    /// Stephens, M. A. (1974). EDF Statistics for Goodness of Fit and Some Comparisons.
+    ///     Journal of the American Statistical Association, 69(347), 730–737.
+    ///     <https://doi.org/10.1080/01621459.1974.10480196>
+    pub fn new(x: &[f64]) -> Result<LillieforsTest> {
+        let estimate = statistic(x)?;
+        Ok(LillieforsTest {
+            estimate,
+            p_value: dallal_wilkinson(estimate, x.len() as f64),
+        })
+    }
+
+    /// Run the Lilliefors test on the sample `x`, with the p-value estimated by simulating
+    /// normal samples of the same size as (1 + b) / (1 + m), where b of the m simulated
+    /// statistics are at least as large as the observed statistic. The number of simulated
+    /// samples and the seed are those of the resamples in the `options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample has fewer than five observations or no variation, if it
+    /// contains NaN, or if the number of resamples is zero.
+    pub fn simulated(x: &[f64], options: BootstrapOptions) -> Result<LillieforsTest> {
+        let estimate = statistic(x)?;
+        if options.resamples() == 0 {
+            return Err(StatsError::BadParams);
+        }
+
+        // The statistic does not depend on the mean and the standard deviation of the
+        // population, so simulate from the standard normal distribution.
+        let normal = Normal::new(0.0, 1.0)?;
+        let mut rng = StdRng::seed_from_u64(options.seed());
+        let mut sample = vec![0.0; x.len()];
+        let mut extreme = 0;
+        for _ in 0..options.resamples() {
+            for value in sample.iter_mut() {
+                *value = normal.sample(&mut rng);
+            }
+            if statistic(&sample)? >= estimate * (1.0 - 1E-12) {
+                extreme += 1;
+            }
+        }
+
+        Ok(LillieforsTest {
+            estimate,
+            p_value: (1 + extreme) as f64 / (1 + options.resamples()) as f64,
+        })
+    }
+}
+
+impl StatisticalTest for LillieforsTest {
+    type Estimate = f64;
+    type EffectSize = f64;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> f64 {
+        self.estimate
+    }
+}
+
+/// Returns the largest absolute deviation of the empirical distribution function of `x` from
+/// the normal distribution with its mean and standard deviation.
+fn statistic(x: &[f64]) -> Result<f64> {
+    if x.len() < 5 || x.iter().any(|x| x.is_nan()) {
+        return Err(StatsError::BadParams);
+    }
+    let normal = Normal::new(x.mean(), x.std_dev())?;
+    let (above, below) = one_sample_deviations(x, &normal)?;
+    Ok(above.max(below))
+}
+
+/// Returns the approximate p-value of the statistic `d` of a sample of size `n` (Dallal &
+/// Wilkinson, 1986; Stephens, 1974).
+fn dallal_wilkinson(d: f64, n: f64) -> f64 {
+    // The approximation was fitted up to 100 observations, beyond which the statistic is
+    // rescaled to a sample of 100.
+    let (d_100, n_100) = if n > 100.0 {
+        (d * (n / 100.0).powf(0.49), 100.0)
+    } else {
+        (d, n)
+    };
+    let p_value = (-7.01256 * d_100 * d_100 * (n_100 + 2.78019)
+        + 2.99587 * d_100 * (n_100 + 2.78019).sqrt()
+        - 0.122119
+        + 0.974598 / n_100.sqrt()
+        + 1.67997 / n_100)
+        .exp();
+    if p_value <= 0.1 {
+        return p_value;
+    }
+
+    let k = (n.sqrt() - 0.01 + 0.85 / n.sqrt()) * d;
+    let p_value = if k <= 0.302 {
+        1.0
+    } else if k <= 0.5 {
+        2.76773 - 19.828315 * k + 80.709644 * k.powi(2) - 138.55152 * k.powi(3)
+            + 81.218052 * k.powi(4)
+    } else if k <= 0.9 {
+        -4.901232 + 40.662806 * k - 97.490286 * k.powi(2) + 94.029866 * k.powi(3)
+            - 32.355711 * k.powi(4)
+    } else if k <= 1.31 {
+        6.198765 - 19.558097 * k + 23.186922 * k.powi(2) - 12.234627 * k.powi(3)
+            + 2.423045 * k.powi(4)
+    } else {
+        0.0
+    };
+    p_value.clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<f64> {
+        vec![
+            2.1, 2.4, 2.5, 2.6, 2.7, 2.9, 3.0, 3.1, 3.2, 3.4, 3.5, 3.8, 4.1, 4.6, 5.3, 6.9,
+        ]
+    }
+
+    #[test]
+    fn lilliefors() {
+        let test = LillieforsTest::new(&sample()).unwrap();
+        assert_eq!(test.estimate(), 0.18951580460683998);
+        assert_eq!(test.p_value(), 0.12940579061233315);
+
+        let x = vec![
+            0.61, 0.29, 0.06, 0.59, -1.73, -0.74, 0.51, -0.56, 0.39, 1.64, 0.05, -0.06, 0.64,
+            -0.82, 0.37, 1.77, 1.09, -1.28, 2.36, 1.31, 1.05, -0.32, -0.4, 1.06, -2.47,
+        ];
+        let test = LillieforsTest::new(&x).unwrap();
+        assert_eq!(test.estimate(), 0.08639642004468223);
+        assert_eq!(test.p_value(), 0.9008606791485665);
+
+        assert_eq!(dallal_wilkinson(0.1, 400.0), 2.630286516298189e-10);
+        assert!(LillieforsTest::new(&x[..4]).is_err());
+        assert!(LillieforsTest::new(&[1.0; 10]).is_err());
+    }
+
+    #[test]
+    fn simulated() {
+        let test = LillieforsTest::simulated(&sample(), BootstrapOptions::default()).unwrap();
+        assert_eq!(test.estimate(), 0.18951580460683998);
+        assert_eq!(test.p_value(), 0.12543728135932034);
+    }
+}
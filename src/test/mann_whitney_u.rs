@@ -1,3 +1,5 @@
+use crate::effect_size::{EffectSize, CONFIDENCE_LEVEL};
+use crate::permutation::{PermutationOptions, PermutationTest};
 use crate::statistics::*;
 use statrs::distribution::{ContinuousCDF, Normal};
 
@@ -8,12 +10,15 @@ use super::StatisticalTest;
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct MannWhitneyUTest {
     estimate: (f64, f64),
-    effect_size: f64,
+    effect_size: EffectSize,
     p_value: f64,
 }
 
 impl MannWhitneyUTest {
     /// Run Mann-Whitney U test/Wilcoxon rank-sum test on samples `x` and `y`.
+    ///
+    /// The effect size is the rank-biserial correlation, with a 95% confidence interval from
+    /// the Fisher z-transformation. Use [`EffectSize::rank_biserial`] for a bootstrap interval.
     pub fn independent(x: &[f64], y: &[f64]) -> statrs::Result<MannWhitneyUTest> {
         let (ranks, tie_correction) = x.iter().chain(y).ranks();
         let n_x = x.n();
@@ -35,7 +40,7 @@ impl MannWhitneyUTest {
 
         let normal = Normal::new(distribution_mean, distribution_var.sqrt())?;
         let p_value = 2.0 * normal.cdf(estimate_small);
-        let effect_size = 1.0 - (2.0 * estimate_small) / n_xy;
+        let effect_size = EffectSize::rank_biserial_fisher(x, y, CONFIDENCE_LEVEL)?;
 
         Ok(MannWhitneyUTest {
             effect_size,
@@ -43,10 +48,32 @@ impl MannWhitneyUTest {
             p_value,
         })
     }
+
+    /// Run Mann-Whitney U test/Wilcoxon rank-sum test on samples `x` and `y`, with a p-value
+    /// from a permutation test of the U statistic instead of the normal approximation.
+    pub fn independent_permutation(
+        x: &[f64],
+        y: &[f64],
+        options: PermutationOptions,
+    ) -> statrs::Result<MannWhitneyUTest> {
+        let test = MannWhitneyUTest::independent(x, y)?;
+        let u = |x: &[f64], y: &[f64]| {
+            let (ranks, _) = x.iter().chain(y).ranks();
+            let n_x = x.n();
+            ranks[0..x.len()].iter().sum::<f64>() - n_x * (n_x + 1.0) / 2.0 - n_x * y.n() / 2.0
+        };
+        let permutation = PermutationTest::two_sample(x, y, u, options)?;
+
+        Ok(MannWhitneyUTest {
+            p_value: permutation.p_value(),
+            ..test
+        })
+    }
 }
 
 impl StatisticalTest for MannWhitneyUTest {
     type Estimate = (f64, f64);
+    type EffectSize = EffectSize;
 
     fn estimate(&self) -> (f64, f64) {
         self.estimate
@@ -56,7 +83,7 @@ impl StatisticalTest for MannWhitneyUTest {
         self.p_value
     }
 
-    fn effect_size(&self) -> f64 {
+    fn effect_size(&self) -> EffectSize {
         self.effect_size
     }
 }
@@ -64,6 +91,7 @@ impl StatisticalTest for MannWhitneyUTest {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::effect_size::EffectSizeKind;
 
     #[test]
     fn mann_whitney_u() {
@@ -73,7 +101,8 @@ mod tests {
         let y = vec![70.0, 118.0, 101.0, 85.0, 107.0, 132.0, 94.0];
         let test = MannWhitneyUTest::independent(&x, &y).unwrap();
         assert_eq!(test.estimate(), (21.5, 62.5));
-        assert_eq!(test.effect_size(), 0.48809523809523814);
+        assert_eq!(test.effect_size().kind(), EffectSizeKind::RankBiserial);
+        assert_eq!(test.effect_size().estimate(), 0.48809523809523814);
         assert_eq!(test.p_value(), 0.08303763193135497);
     }
 
@@ -83,7 +112,21 @@ mod tests {
         let y = vec![60.0, 67.0, 61.0, 62.0, 67.0, 63.0, 56.0, 58.0];
         let test = MannWhitneyUTest::independent(&x, &y).unwrap();
         assert_eq!(test.estimate(), (9.0, 55.0));
-        assert_eq!(test.effect_size(), 0.71875);
+        assert_eq!(test.effect_size().estimate(), 0.71875);
+        assert_eq!(
+            test.effect_size().confidence_interval(),
+            (0.3111772660988028, 0.903004115891528)
+        );
         assert_eq!(test.p_value(), 0.01533316211294691);
     }
+
+    #[test]
+    fn permutation() {
+        let x = vec![68.0, 68.0, 59.0, 72.0, 64.0, 67.0, 70.0, 74.0];
+        let y = vec![60.0, 67.0, 61.0, 62.0, 67.0, 63.0, 56.0, 58.0];
+        let options = PermutationOptions::default();
+        let test = MannWhitneyUTest::independent_permutation(&x, &y, options).unwrap();
+        assert_eq!(test.estimate(), (9.0, 55.0));
+        assert_eq!(test.p_value(), 0.01351981351981352);
+    }
 }
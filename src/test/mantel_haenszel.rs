@@ -0,0 +1,450 @@
+use crate::effect_size::{EffectSize, EffectSizeKind, CONFIDENCE_LEVEL};
+use crate::statistics::{inverse_quadratic_form, ContingencyTable};
+use statrs::distribution::{ChiSquared, ContinuousCDF, Normal};
+use statrs::{Result, StatsError};
+
+use super::StatisticalTest;
+
+/// Implements the [Cochran-Mantel-Haenszel test](https://en.wikipedia.org/wiki/Cochran%E2%80%93Mantel%E2%80%93Haenszel_statistics)
+/// of conditional independence of the rows and columns of contingency tables in strata
+/// (Mantel & Haenszel, 1959; Agresti, 2013).
+///
+/// # References
+///
+/// Agresti, A. (2013). Categorical Data Analysis (3rd ed.). John Wiley & Sons.
+///
+/// Mantel, N., & Haenszel, W. (1959). Statistical Aspects of the Analysis of Data From
+///     Retrospective Studies of Disease. Journal of the National Cancer Institute, 22(4),
+///     719–748. <https://doi.org/10.1093/jnci/22.4.719>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MantelHaenszelTest {
+    df: f64,
+    estimate: f64,
+    effect_size: EffectSize,
+    p_value: f64,
+}
+
+impl MantelHaenszelTest {
+    /// Run the Cochran-Mantel-Haenszel test on the 2×2 tables of the `strata`, optionally with
+    /// a continuity correction of 0.5.
+    ///
+    /// The effect size is the Mantel-Haenszel estimate of the common odds ratio, with a 95%
+    /// confidence interval based on the variance of Robins, Breslow and Greenland (1986).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::statistics::ContingencyTable;
+    /// use stattest::test::{MantelHaenszelTest, StatisticalTest};
+    ///
+    /// let strata = vec![
+    ///     ContingencyTable::new(vec![vec![12, 8], vec![5, 15]]).unwrap(),
+    ///     ContingencyTable::new(vec![vec![9, 11], vec![4, 16]]).unwrap(),
+    /// ];
+    /// let test = MantelHaenszelTest::new(&strata, true).unwrap();
+    /// assert!(test.effect_size().estimate() > 1.0);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no strata, if a table is not 2×2 or has fewer than two
+    /// observations, or if the variance of the statistic is zero.
+    ///
+    /// # References
+    ///
+    /// Robins, J., Breslow, N., & Greenland, S. (1986). Estimators of the Mantel-Haenszel
+    ///     Variance Consistent in Both Sparse Data and Large-Strata Limiting Models. Biometrics,
+    ///     42(2), 311–323. <https://doi.org/10.2307/2531052>
+    pub fn new(strata: &[ContingencyTable], correct: bool) -> Result<MantelHaenszelTest> {
+        check_strata(strata, 2, 2)?;
+
+        let (mut deviation, mut variance) = (0.0, 0.0);
+        for table in strata {
+            let (a, n1, n2, m1, m2, n) = margins(table);
+            deviation += a - n1 * m1 / n;
+            variance += n1 * n2 * m1 * m2 / (n * n * (n - 1.0));
+        }
+        if variance <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+
+        let correction = if correct {
+            deviation.abs().min(0.5)
+        } else {
+            0.0
+        };
+        let estimate = (deviation.abs() - correction).powi(2) / variance;
+        let p_value = ChiSquared::new(1.0)?.sf(estimate);
+
+        Ok(MantelHaenszelTest {
+            df: 1.0,
+            estimate,
+            effect_size: common_odds_ratio(strata, CONFIDENCE_LEVEL),
+            p_value,
+        })
+    }
+
+    /// Run the generalized Cochran-Mantel-Haenszel test of general association on the r×c
+    /// tables of the `strata`, which all have the same dimensions (Landis et al., 1978). For
+    /// 2×2 tables, it equals the test without continuity correction.
+    ///
+    /// The effect size is Cramér's V of the table summed over the strata, with a 95%
+    /// confidence interval based on the noncentral chi-squared distribution.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no strata, if the tables differ in dimensions or have
+    /// fewer than two observations, or if the covariance matrix of the counts is singular.
+    ///
+    /// # References
+    ///
+    /// Landis, J. R., Heyman, E. R., & Koch, G. G. (1978). Average Partial Association in
+    ///     Three-way Contingency Tables: A Review and Discussion of Alternative Tests.
+    ///     International Statistical Review, 46(3), 237–254. <https://doi.org/10.2307/1402373>
+    pub fn generalized(strata: &[ContingencyTable]) -> Result<MantelHaenszelTest> {
+        let (rows, columns) = match strata.first() {
+            Some(table) => (table.rows(), table.columns()),
+            None => return Err(StatsError::BadParams),
+        };
+        check_strata(strata, rows, columns)?;
+
+        // The counts of the first r - 1 rows and c - 1 columns determine the tables given the
+        // margins.
+        let cells: Vec<(usize, usize)> = (0..rows - 1)
+            .flat_map(|i| (0..columns - 1).map(move |j| (i, j)))
+            .collect();
+        let size = cells.len();
+        let mut deviations = vec![0.0; size];
+        let mut covariance = vec![vec![0.0; size]; size];
+        for table in strata {
+            let n = table.total() as f64;
+            let row_totals: Vec<f64> = table.row_totals().iter().map(|t| *t as f64).collect();
+            let column_totals: Vec<f64> = table.column_totals().iter().map(|t| *t as f64).collect();
+            // The covariance of the totals of two categories `k` and `l`, times n².
+            let scaled = |totals: &[f64], k: usize, l: usize| {
+                let diagonal = if k == l { n * totals[k] } else { 0.0 };
+                diagonal - totals[k] * totals[l]
+            };
+
+            for (index, (i, j)) in cells.iter().enumerate() {
+                let expected = row_totals[*i] * column_totals[*j] / n;
+                deviations[index] += table.get(*i, *j) as f64 - expected;
+                for (other, (k, l)) in cells.iter().enumerate() {
+                    covariance[index][other] += scaled(&row_totals, *i, *k)
+                        * scaled(&column_totals, *j, *l)
+                        / (n * n * (n - 1.0));
+                }
+            }
+        }
+
+        let estimate =
+            inverse_quadratic_form(&deviations, &covariance).ok_or(StatsError::BadParams)?;
+        let df = size as f64;
+        let p_value = ChiSquared::new(df)?.sf(estimate);
+
+        let mut pooled = vec![vec![0; columns]; rows];
+        for table in strata {
+            for (i, row) in table.counts().iter().enumerate() {
+                for (j, count) in row.iter().enumerate() {
+                    pooled[i][j] += count;
+                }
+            }
+        }
+        let pooled = ContingencyTable::new(pooled)?;
+        let effect_size = EffectSize::cramers_v(&pooled, CONFIDENCE_LEVEL)?;
+
+        Ok(MantelHaenszelTest {
+            df,
+            estimate,
+            effect_size,
+            p_value,
+        })
+    }
+
+    /// Returns the degrees of freedom.
+    pub fn df(&self) -> f64 {
+        self.df
+    }
+}
+
+impl StatisticalTest for MantelHaenszelTest {
+    type Estimate = f64;
+    type EffectSize = EffectSize;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> EffectSize {
+        self.effect_size
+    }
+}
+
+/// Implements the Breslow-Day test of homogeneity of the odds ratios of 2×2 tables in strata
+/// (Breslow & Day, 1980), optionally with Tarone's correction (Tarone, 1985).
+///
+/// # References
+///
+/// Breslow, N. E., & Day, N. E. (1980). Statistical Methods in Cancer Research. Volume I: The
+///     Analysis of Case-Control Studies. International Agency for Research on Cancer.
+///
+/// Tarone, R. E. (1985). On heterogeneity tests based on efficient scores. Biometrika, 72(1),
+///     91–95. <https://doi.org/10.1093/biomet/72.1.91>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BreslowDayTest {
+    df: f64,
+    estimate: f64,
+    effect_size: EffectSize,
+    p_value: f64,
+}
+
+impl BreslowDayTest {
+    /// Run the Breslow-Day test on the 2×2 tables of the `strata`, comparing the top left
+    /// count of every table to its expected count under the Mantel-Haenszel estimate of the
+    /// common odds ratio.
+    ///
+    /// The effect size is the Mantel-Haenszel estimate of the common odds ratio, with a 95%
+    /// confidence interval.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are fewer than two strata, if a table is not 2×2, or if a
+    /// row or column of a table is empty.
+    pub fn new(strata: &[ContingencyTable], tarone: bool) -> Result<BreslowDayTest> {
+        if strata.len() < 2 {
+            return Err(StatsError::BadParams);
+        }
+        check_strata(strata, 2, 2)?;
+        if strata
+            .iter()
+            .any(|table| table.row_totals().contains(&0) || table.column_totals().contains(&0))
+        {
+            return Err(StatsError::BadParams);
+        }
+
+        let effect_size = common_odds_ratio(strata, CONFIDENCE_LEVEL);
+        let odds_ratio = effect_size.estimate();
+        if odds_ratio == 0.0 || odds_ratio.is_infinite() {
+            return Err(StatsError::BadParams);
+        }
+
+        let (mut estimate, mut deviation, mut variance) = (0.0, 0.0, 0.0);
+        for table in strata {
+            let (a, n1, n2, m1, _, _) = margins(table);
+            let fitted = fitted_count(n1, n2, m1, odds_ratio);
+            let stratum_variance = 1.0
+                / (1.0 / fitted
+                    + 1.0 / (n1 - fitted)
+                    + 1.0 / (m1 - fitted)
+                    + 1.0 / (n2 - m1 + fitted));
+            estimate += (a - fitted).powi(2) / stratum_variance;
+            deviation += a - fitted;
+            variance += stratum_variance;
+        }
+        if tarone {
+            estimate -= deviation.powi(2) / variance;
+        }
+
+        let df = (strata.len() - 1) as f64;
+        let p_value = ChiSquared::new(df)?.sf(estimate);
+        Ok(BreslowDayTest {
+            df,
+            estimate,
+            effect_size,
+            p_value,
+        })
+    }
+
+    /// Returns the degrees of freedom.
+    pub fn df(&self) -> f64 {
+        self.df
+    }
+}
+
+impl StatisticalTest for BreslowDayTest {
+    type Estimate = f64;
+    type EffectSize = EffectSize;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> EffectSize {
+        self.effect_size
+    }
+}
+
+/// Checks that there are strata, that every table has the given dimensions, and that every
+/// table has at least two observations.
+fn check_strata(strata: &[ContingencyTable], rows: usize, columns: usize) -> Result<()> {
+    if strata.is_empty()
+        || strata
+            .iter()
+            .any(|table| table.rows() != rows || table.columns() != columns || table.total() < 2)
+    {
+        return Err(StatsError::BadParams);
+    }
+    Ok(())
+}
+
+/// Returns the top left count, the row totals, the column totals and the total count of the
+/// 2×2 `table`.
+fn margins(table: &ContingencyTable) -> (f64, f64, f64, f64, f64, f64) {
+    let rows = table.row_totals();
+    let columns = table.column_totals();
+    (
+        table.get(0, 0) as f64,
+        rows[0] as f64,
+        rows[1] as f64,
+        columns[0] as f64,
+        columns[1] as f64,
+        table.total() as f64,
+    )
+}
+
+/// Returns the Mantel-Haenszel estimate of the common odds ratio of the 2×2 tables of the
+/// `strata`, with a confidence interval at `confidence_level` based on the variance of the
+/// logarithm of Robins, Breslow and Greenland. The interval is unbounded when the estimate is
+/// zero or infinite.
+fn common_odds_ratio(strata: &[ContingencyTable], confidence_level: f64) -> EffectSize {
+    let (mut r, mut s) = (0.0, 0.0);
+    let (mut pr, mut ps_qr, mut qs) = (0.0, 0.0, 0.0);
+    let (mut n_x, mut n_y) = (0, 0);
+    for table in strata {
+        let (a, b) = (table.get(0, 0) as f64, table.get(0, 1) as f64);
+        let (c, d) = (table.get(1, 0) as f64, table.get(1, 1) as f64);
+        let n = a + b + c + d;
+        let (p, q) = ((a + d) / n, (b + c) / n);
+        let (r_k, s_k) = (a * d / n, b * c / n);
+        r += r_k;
+        s += s_k;
+        pr += p * r_k;
+        ps_qr += p * s_k + q * r_k;
+        qs += q * s_k;
+        let rows = table.row_totals();
+        n_x += rows[0];
+        n_y += rows[1];
+    }
+
+    let estimate = r / s;
+    let confidence_interval = if r > 0.0 && s > 0.0 {
+        let variance = pr / (2.0 * r * r) + ps_qr / (2.0 * r * s) + qs / (2.0 * s * s);
+        let z = Normal::new(0.0, 1.0)
+            .unwrap()
+            .inverse_cdf(0.5 + confidence_level / 2.0);
+        let margin = z * variance.sqrt();
+        (
+            (estimate.ln() - margin).exp(),
+            (estimate.ln() + margin).exp(),
+        )
+    } else {
+        (0.0, f64::INFINITY)
+    };
+
+    EffectSize::new(
+        EffectSizeKind::OddsRatio,
+        estimate,
+        confidence_interval,
+        confidence_level,
+    )
+    .with_sample_sizes(n_x, n_y)
+}
+
+/// Returns the top left count of a 2×2 table with row totals `n1` and `n2` and first column
+/// total `m1` whose odds ratio equals `odds_ratio`.
+fn fitted_count(n1: f64, n2: f64, m1: f64, odds_ratio: f64) -> f64 {
+    let low = (m1 - n2).max(0.0);
+    let high = n1.min(m1);
+    // The count solves (1 - ψ) a² + (n2 - m1 + ψ (n1 + m1)) a - ψ n1 m1 = 0.
+    let a = 1.0 - odds_ratio;
+    let b = n2 - m1 + odds_ratio * (n1 + m1);
+    let c = -odds_ratio * n1 * m1;
+    if a.abs() < 1E-12 {
+        return -c / b;
+    }
+    let root = (b * b - 4.0 * a * c).sqrt();
+    let first = (-b + root) / (2.0 * a);
+    if first >= low && first <= high {
+        first
+    } else {
+        (-b - root) / (2.0 * a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Admissions to the six largest departments of UC Berkeley in 1973, by admission and sex.
+    fn admissions() -> Vec<ContingencyTable> {
+        vec![
+            vec![vec![512, 89], vec![313, 19]],
+            vec![vec![353, 17], vec![207, 8]],
+            vec![vec![120, 202], vec![205, 391]],
+            vec![vec![138, 131], vec![279, 244]],
+            vec![vec![53, 94], vec![138, 299]],
+            vec![vec![22, 24], vec![351, 317]],
+        ]
+        .into_iter()
+        .map(|counts| ContingencyTable::new(counts).unwrap())
+        .collect()
+    }
+
+    #[test]
+    fn mantel_haenszel() {
+        let strata = admissions();
+        let test = MantelHaenszelTest::new(&strata, true).unwrap();
+        assert_eq!(test.df(), 1.0);
+        assert_eq!(test.estimate(), 1.4269462285866883);
+        assert_eq!(test.p_value(), 0.23226346281704802);
+        assert_eq!(test.effect_size().estimate(), 0.9046968282586231);
+        assert_eq!(
+            test.effect_size().confidence_interval(),
+            (0.7719073617593506, 1.0603297644366556)
+        );
+
+        let uncorrected = MantelHaenszelTest::new(&strata, false).unwrap();
+        let generalized = MantelHaenszelTest::generalized(&strata).unwrap();
+        assert!((generalized.estimate() - uncorrected.estimate()).abs() < 1E-10);
+
+        let table = ContingencyTable::new(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert!(MantelHaenszelTest::new(&[table], true).is_err());
+    }
+
+    #[test]
+    fn generalized() {
+        let strata: Vec<ContingencyTable> = vec![
+            vec![vec![10, 5, 3], vec![4, 12, 8], vec![2, 6, 14]],
+            vec![vec![8, 6, 2], vec![5, 9, 7], vec![3, 4, 11]],
+        ]
+        .into_iter()
+        .map(|counts| ContingencyTable::new(counts).unwrap())
+        .collect();
+        let test = MantelHaenszelTest::generalized(&strata).unwrap();
+        assert_eq!(test.df(), 4.0);
+        assert_eq!(test.estimate(), 26.399518469182254);
+        assert_eq!(test.p_value(), 2.6284419062045316e-5);
+    }
+
+    #[test]
+    fn breslow_day() {
+        let strata = admissions();
+        let test = BreslowDayTest::new(&strata, false).unwrap();
+        assert_eq!(test.df(), 5.0);
+        assert_eq!(test.estimate(), 18.825513705236514);
+        assert_eq!(test.p_value(), 0.0020713903499175704);
+
+        let test = BreslowDayTest::new(&strata, true).unwrap();
+        assert_eq!(test.estimate(), 18.82550125205323);
+
+        assert!(BreslowDayTest::new(&strata[..1], false).is_err());
+    }
+}
@@ -0,0 +1,340 @@
+use crate::effect_size::{EffectSize, EffectSizeKind, CONFIDENCE_LEVEL};
+use crate::statistics::{inverse_quadratic_form, ContingencyTable};
+use statrs::distribution::{Binomial, ChiSquared, ContinuousCDF, Discrete, DiscreteCDF};
+use statrs::{Result, StatsError};
+
+use super::{ProportionInterval, StatisticalTest};
+
+/// Implements [McNemar's test](https://en.wikipedia.org/wiki/McNemar%27s_test) of marginal
+/// homogeneity of paired binary outcomes (McNemar, 1947; Fagerland et al., 2013).
+///
+/// # References
+///
+/// Fagerland, M. W., Lydersen, S., & Laake, P. (2013). The McNemar test for binary
+///     matched-pairs data: mid-p and asymptotic are better than exact conditional. BMC Medical
+///     Research Methodology, 13, 91. <https://doi.org/10.1186/1471-2288-13-91>
+///
+/// McNemar, Q. (1947). Note on the sampling error of the difference between correlated
+///     proportions or percentages. Psychometrika, 12(2), 153–157.
+///     <https://doi.org/10.1007/BF02295996>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct McNemarTest {
+    estimate: f64,
+    effect_size: EffectSize,
+    p_value: f64,
+}
+
+/// Method of computing the p-value of McNemar's test.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum McNemarMethod {
+    /// Chi-squared approximation.
+    Asymptotic,
+    /// Chi-squared approximation with Edwards's continuity correction of 1.
+    Corrected,
+    /// Exact conditional binomial test of the discordant pairs.
+    Exact,
+    /// Exact conditional binomial test with half the probability of the observed count, which
+    /// is less conservative than the exact test.
+    MidP,
+}
+
+impl McNemarTest {
+    /// Run McNemar's test on the 2×2 `table` of paired outcomes, where the rows are the
+    /// outcomes of the first and the columns are the outcomes of the second measurement, with
+    /// the given `method`. Only the discordant pairs in the off-diagonal cells are used.
+    ///
+    /// The estimate is the chi-squared statistic for the asymptotic methods, and the count in
+    /// the top right cell for the exact methods. The effect size is the conditional odds ratio
+    /// of the top right to the bottom left count, with an exact 95% confidence interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::statistics::ContingencyTable;
+    /// use stattest::test::{McNemarMethod, McNemarTest, StatisticalTest};
+    ///
+    /// let table = ContingencyTable::new(vec![vec![794, 86], vec![150, 570]]).unwrap();
+    /// let test = McNemarTest::new(&table, McNemarMethod::Corrected).unwrap();
+    /// assert!(test.p_value() < 0.001);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the table is not 2×2 or if there are no discordant pairs.
+    pub fn new(table: &ContingencyTable, method: McNemarMethod) -> Result<McNemarTest> {
+        if table.rows() != 2 || table.columns() != 2 {
+            return Err(StatsError::BadParams);
+        }
+        let (b, c) = (table.get(0, 1), table.get(1, 0));
+        let n = b + c;
+        if n == 0 {
+            return Err(StatsError::BadParams);
+        }
+
+        let (estimate, p_value) = match method {
+            McNemarMethod::Asymptotic | McNemarMethod::Corrected => {
+                let difference = (b as f64 - c as f64).abs();
+                let difference = if method == McNemarMethod::Corrected {
+                    difference - difference.min(1.0)
+                } else {
+                    difference
+                };
+                let estimate = difference.powi(2) / n as f64;
+                (estimate, ChiSquared::new(1.0)?.sf(estimate))
+            }
+            McNemarMethod::Exact | McNemarMethod::MidP => {
+                let binomial = Binomial::new(0.5, n as u64)?;
+                let smaller = b.min(c) as u64;
+                let p_value = 2.0 * binomial.cdf(smaller);
+                let p_value = if method == McNemarMethod::MidP {
+                    p_value - binomial.pmf(smaller)
+                } else {
+                    p_value
+                };
+                (b as f64, p_value.min(1.0))
+            }
+        };
+
+        Ok(McNemarTest {
+            estimate,
+            effect_size: paired_odds_ratio(b, c, CONFIDENCE_LEVEL)?,
+            p_value,
+        })
+    }
+}
+
+impl StatisticalTest for McNemarTest {
+    type Estimate = f64;
+    type EffectSize = EffectSize;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> EffectSize {
+        self.effect_size
+    }
+}
+
+/// Implements tests of symmetry and marginal homogeneity of k×k tables of paired categorical
+/// outcomes, which generalize McNemar's test to more than two categories.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SymmetryTest {
+    df: f64,
+    estimate: f64,
+    effect_size: EffectSize,
+    p_value: f64,
+}
+
+impl SymmetryTest {
+    /// Run Bowker's test of symmetry of the square `table` of paired outcomes, which compares
+    /// every pair of off-diagonal cells (Bowker, 1948). Pairs of empty cells are ignored and do
+    /// not count towards the degrees of freedom. For 2×2 tables, it equals McNemar's test
+    /// without continuity correction.
+    ///
+    /// The effect size is Cohen's w, the square root of the statistic divided by the total
+    /// count, with a 95% confidence interval based on the noncentral chi-squared distribution.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the table is not square or if there are no discordant pairs.
+    ///
+    /// # References
+    ///
+    /// Bowker, A. H. (1948). A Test for Symmetry in Contingency Tables. Journal of the American
+    ///     Statistical Association, 43(244), 572–574. <https://doi.org/10.2307/2280710>
+    pub fn bowker(table: &ContingencyTable) -> Result<SymmetryTest> {
+        let k = square(table)?;
+        let (mut estimate, mut df) = (0.0, 0);
+        for i in 0..k {
+            for j in i + 1..k {
+                let (upper, lower) = (table.get(i, j) as f64, table.get(j, i) as f64);
+                if upper + lower > 0.0 {
+                    estimate += (upper - lower).powi(2) / (upper + lower);
+                    df += 1;
+                }
+            }
+        }
+        if df == 0 {
+            return Err(StatsError::BadParams);
+        }
+        SymmetryTest::new(table, estimate, df as f64)
+    }
+
+    /// Run the Stuart-Maxwell test of marginal homogeneity of the square `table` of paired
+    /// outcomes, which compares the row totals to the column totals (Stuart, 1955; Maxwell,
+    /// 1970). For 2×2 tables, it equals McNemar's test without continuity correction.
+    ///
+    /// The effect size is Cohen's w, the square root of the statistic divided by the total
+    /// count, with a 95% confidence interval based on the noncentral chi-squared distribution.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the table is not square or if the covariance matrix of the
+    /// differences of the totals is singular, for example when a category has no discordant
+    /// pairs.
+    ///
+    /// # References
+    ///
+    /// Maxwell, A. E. (1970). Comparing the Classification of Subjects by Two Independent
+    ///     Judges. The British Journal of Psychiatry, 116(535), 651–655.
+    ///     <https://doi.org/10.1192/bjp.116.535.651>
+    ///
+    /// Stuart, A. (1955). A Test for Homogeneity of the Marginal Distributions in a Two-Way
+    ///     Classification. Biometrika, 42(3/4), 412–416. <https://doi.org/10.2307/2333387>
+    pub fn stuart_maxwell(table: &ContingencyTable) -> Result<SymmetryTest> {
+        let k = square(table)?;
+        let rows = table.row_totals();
+        let columns = table.column_totals();
+
+        // The last category is dropped, since the differences of the totals sum to zero.
+        let differences: Vec<f64> = (0..k - 1)
+            .map(|i| rows[i] as f64 - columns[i] as f64)
+            .collect();
+        let covariance: Vec<Vec<f64>> = (0..k - 1)
+            .map(|i| {
+                (0..k - 1)
+                    .map(|j| {
+                        if i == j {
+                            (rows[i] + columns[i] - 2 * table.get(i, i)) as f64
+                        } else {
+                            -((table.get(i, j) + table.get(j, i)) as f64)
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let estimate =
+            inverse_quadratic_form(&differences, &covariance).ok_or(StatsError::BadParams)?;
+        SymmetryTest::new(table, estimate, (k - 1) as f64)
+    }
+
+    fn new(table: &ContingencyTable, estimate: f64, df: f64) -> Result<SymmetryTest> {
+        let effect_size = EffectSize::from_chi_squared(
+            EffectSizeKind::CohensW,
+            estimate,
+            df,
+            table.total() as f64,
+            CONFIDENCE_LEVEL,
+        )?;
+        Ok(SymmetryTest {
+            df,
+            estimate,
+            effect_size,
+            p_value: ChiSquared::new(df)?.sf(estimate),
+        })
+    }
+
+    /// Returns the degrees of freedom.
+    pub fn df(&self) -> f64 {
+        self.df
+    }
+}
+
+impl StatisticalTest for SymmetryTest {
+    type Estimate = f64;
+    type EffectSize = EffectSize;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> EffectSize {
+        self.effect_size
+    }
+}
+
+/// Returns the number of categories of the square `table`.
+fn square(table: &ContingencyTable) -> Result<usize> {
+    if table.rows() != table.columns() || table.rows() < 2 {
+        return Err(StatsError::BadParams);
+    }
+    Ok(table.rows())
+}
+
+/// Returns the conditional odds ratio `b / c` of the discordant counts, with the exact
+/// confidence interval at `confidence_level` that follows from the Clopper-Pearson interval of
+/// the proportion `b / (b + c)`.
+fn paired_odds_ratio(b: usize, c: usize, confidence_level: f64) -> Result<EffectSize> {
+    let (lower, upper) = ProportionInterval::ClopperPearson.interval(b, b + c, confidence_level)?;
+    let odds = |p: f64| p / (1.0 - p);
+
+    Ok(EffectSize::new(
+        EffectSizeKind::OddsRatio,
+        b as f64 / c as f64,
+        (odds(lower), odds(upper)),
+        confidence_level,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mcnemar() {
+        // Presidential approval ratings in two surveys (Agresti, 2013).
+        let table = ContingencyTable::new(vec![vec![794, 150], vec![86, 570]]).unwrap();
+        let test = McNemarTest::new(&table, McNemarMethod::Asymptotic).unwrap();
+        assert_eq!(test.estimate(), 17.35593220338983);
+        assert_eq!(test.p_value(), 3.099293441045215e-5);
+        assert_eq!(test.effect_size().estimate(), 1.744186046511628);
+        assert_eq!(
+            test.effect_size().confidence_interval(),
+            (1.3292282526053003, 2.300979080421943)
+        );
+
+        let test = McNemarTest::new(&table, McNemarMethod::Corrected).unwrap();
+        assert_eq!(test.estimate(), 16.81779661016949);
+        assert_eq!(test.p_value(), 4.114562281345945e-5);
+    }
+
+    #[test]
+    fn mcnemar_exact() {
+        let table = ContingencyTable::new(vec![vec![10, 9], vec![2, 12]]).unwrap();
+        let test = McNemarTest::new(&table, McNemarMethod::Exact).unwrap();
+        assert_eq!(test.estimate(), 9.0);
+        assert_eq!(test.p_value(), 0.06542968749999895);
+
+        let test = McNemarTest::new(&table, McNemarMethod::MidP).unwrap();
+        assert_eq!(test.p_value(), 0.03857421874999887);
+
+        let table = ContingencyTable::new(vec![vec![10, 0], vec![0, 12]]).unwrap();
+        assert!(McNemarTest::new(&table, McNemarMethod::Exact).is_err());
+    }
+
+    #[test]
+    fn symmetry() {
+        let table = ContingencyTable::new(vec![vec![794, 150], vec![86, 570]]).unwrap();
+        let mcnemar = McNemarTest::new(&table, McNemarMethod::Asymptotic).unwrap();
+        let bowker = SymmetryTest::bowker(&table).unwrap();
+        let stuart_maxwell = SymmetryTest::stuart_maxwell(&table).unwrap();
+        assert!((bowker.estimate() - mcnemar.estimate()).abs() < 1E-10);
+        assert!((stuart_maxwell.estimate() - mcnemar.estimate()).abs() < 1E-10);
+
+        let table =
+            ContingencyTable::new(vec![vec![20, 10, 5], vec![3, 30, 15], vec![0, 5, 40]]).unwrap();
+        let test = SymmetryTest::bowker(&table).unwrap();
+        assert_eq!(test.df(), 3.0);
+        assert_eq!(test.estimate(), 13.76923076923077);
+        assert_eq!(test.p_value(), 0.0032367065032354405);
+
+        let test = SymmetryTest::stuart_maxwell(&table).unwrap();
+        assert_eq!(test.df(), 2.0);
+        assert_eq!(test.estimate(), 13.76470588235294);
+        assert_eq!(test.p_value(), 0.0010257277265580751);
+        assert_eq!(test.effect_size().kind(), EffectSizeKind::CohensW);
+
+        let table = ContingencyTable::new(vec![vec![1, 2, 3]]).unwrap();
+        assert!(SymmetryTest::bowker(&table).is_err());
+    }
+}
@@ -1,22 +1,63 @@
 //! Defines frequentist statistical tests.
 
+pub use self::anderson_darling::*;
+pub use self::binomial::*;
+pub use self::chi_squared::*;
+pub use self::cochran_armitage::*;
+pub use self::cochran_q::*;
+pub use self::cramer_von_mises::*;
+pub use self::dagostino_pearson::*;
+pub use self::equivalence_t::*;
 pub use self::f::*;
+pub use self::fisher_exact::*;
+pub use self::g::*;
+pub use self::jarque_bera::*;
+pub use self::kolmogorov_smirnov::*;
+pub use self::kuiper::*;
 pub use self::levenes::*;
+pub use self::lilliefors::*;
 pub use self::mann_whitney_u::*;
+pub use self::mantel_haenszel::*;
+pub use self::mcnemar::*;
+pub use self::poisson::*;
+pub use self::rank_equivalence::*;
 pub use self::shapiro_wilk::*;
 pub use self::students_t::*;
+pub use self::two_proportion::*;
+pub use self::unconditional_exact::*;
 pub use self::welchs_t::*;
 pub use self::wilcoxon_w::*;
 
+mod anderson_darling;
+mod binomial;
+mod chi_squared;
+mod cochran_armitage;
+mod cochran_q;
+mod cramer_von_mises;
+mod dagostino_pearson;
+mod equivalence_t;
 mod f;
+mod fisher_exact;
+mod g;
+mod jarque_bera;
+mod kolmogorov_smirnov;
+mod kuiper;
 mod levenes;
+mod lilliefors;
 mod mann_whitney_u;
+mod mantel_haenszel;
+mod mcnemar;
+mod poisson;
+mod rank_equivalence;
 mod shapiro_wilk;
 mod students_t;
+mod two_proportion;
+mod unconditional_exact;
 mod welchs_t;
 mod wilcoxon_w;
 
 /// Alternative hypothesis for comparing two means.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum AlternativeHypothesis {
     Greater,
     Different,
@@ -27,11 +68,13 @@ pub enum AlternativeHypothesis {
 pub trait StatisticalTest {
     /// The type of the estimate.
     type Estimate;
+    /// The type of the effect size.
+    type EffectSize;
 
     /// Returns the estimate of the test statistic.
     fn estimate(&self) -> Self::Estimate;
     /// Returns the degrees of freedom.
     fn p_value(&self) -> f64;
     /// Returns the effect size.
-    fn effect_size(&self) -> f64;
+    fn effect_size(&self) -> Self::EffectSize;
 }
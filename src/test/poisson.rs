@@ -0,0 +1,465 @@
+use crate::distribution::invert;
+use crate::effect_size::{EffectSize, EffectSizeKind, CONFIDENCE_LEVEL};
+use statrs::distribution::{ChiSquared, ContinuousCDF, Discrete, DiscreteCDF, Normal, Poisson};
+use statrs::function::gamma::gamma_lr;
+use statrs::{Result, StatsError};
+
+use super::fisher_exact::TOLERANCE;
+use super::{AlternativeHypothesis, BinomialTest, ProportionInterval, StatisticalTest};
+
+/// Method of testing the ratio of two Poisson rates, all of which condition on the total
+/// number of events, under which the events of the first sample follow a binomial
+/// distribution (Gu et al., 2008).
+///
+/// # References
+///
+/// Gu, K., Ng, H. K. T., Tang, M. L., & Schucany, W. R. (2008). Testing the Ratio of Two
+///     Poisson Rates. Biometrical Journal, 50(2), 283–298.
+///     <https://doi.org/10.1002/bimj.200710403>
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RateRatioMethod {
+    /// Exact binomial test, with the Clopper-Pearson interval of the binomial proportion.
+    Exact,
+    /// Wald test of the logarithm of the rate ratio.
+    Wald,
+    /// Score test of the binomial proportion, with the Wilson interval.
+    Score,
+}
+
+/// Implements the exact [Poisson test](https://en.wikipedia.org/wiki/Poisson_distribution#Confidence_interval)
+/// of an event rate, and tests of the ratio of two rates.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PoissonTest {
+    effect_size: EffectSize,
+    p_value: f64,
+}
+
+impl PoissonTest {
+    /// Run the exact test of whether the rate of `events` over an `exposure`, such as a time
+    /// span or a number of sessions, equals `rate`. Two-sided p-values sum the probabilities
+    /// of all counts that are at most as probable as the observed count.
+    ///
+    /// The estimate is the observed rate, and the effect size is the same rate with an exact
+    /// 95% confidence interval (Garwood, 1936), which is one-sided for one-sided alternatives.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::test::{AlternativeHypothesis, PoissonTest, StatisticalTest};
+    ///
+    /// // 37 crashes in 12 thousand sessions, against 2 crashes per thousand sessions.
+    /// let test = PoissonTest::new(37, 12.0, 2.0, AlternativeHypothesis::Greater).unwrap();
+    /// assert!(test.p_value() < 0.05);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the exposure or the rate is not positive.
+    ///
+    /// # References
+    ///
+    /// Garwood, F. (1936). Fiducial Limits for the Poisson Distribution. Biometrika, 28(3/4),
+    ///     437–442. <https://doi.org/10.2307/2333958>
+    pub fn new(
+        events: usize,
+        exposure: f64,
+        rate: f64,
+        alternative: AlternativeHypothesis,
+    ) -> Result<PoissonTest> {
+        if !(exposure > 0.0 && exposure.is_finite()) {
+            return Err(StatsError::BadParams);
+        }
+        let poisson = Poisson::new(rate * exposure)?;
+        let x = events as u64;
+        let p_value = match alternative {
+            AlternativeHypothesis::Less => poisson.cdf(x),
+            AlternativeHypothesis::Greater => poisson.sf(x) + poisson.pmf(x),
+            AlternativeHypothesis::Different => {
+                let observed = poisson.pmf(x) * (1.0 + TOLERANCE);
+                // Beyond both the count and the mean, the probabilities decrease, so the sum
+                // ends with the tail after the first count that is at most as probable.
+                let mut k = 0;
+                let mut sum = 0.0;
+                while k <= x || k as f64 <= rate * exposure || poisson.pmf(k) > observed {
+                    if poisson.pmf(k) <= observed {
+                        sum += poisson.pmf(k);
+                    }
+                    k += 1;
+                }
+                sum + poisson.sf(k - 1)
+            }
+        };
+
+        let events = events as f64;
+        let interval = |confidence_level: f64| {
+            let alpha = 1.0 - confidence_level;
+            // The probability of at least `a` events is the gamma distribution function of
+            // the mean.
+            let cdf = |a: f64, m: f64| if m > 0.0 { gamma_lr(a, m) } else { 0.0 };
+            let mean = |a: f64, p: f64| invert(|m| cdf(a, m), p, 0.0, f64::INFINITY, a);
+            let lower = if events == 0.0 {
+                0.0
+            } else {
+                mean(events, alpha / 2.0)
+            };
+            let upper = mean(events + 1.0, 1.0 - alpha / 2.0);
+            Ok((lower / exposure, upper / exposure))
+        };
+        let effect_size = EffectSize::new(
+            EffectSizeKind::Rate,
+            events / exposure,
+            one_sided(interval, alternative, (0.0, f64::INFINITY))?,
+            CONFIDENCE_LEVEL,
+        );
+
+        Ok(PoissonTest {
+            effect_size,
+            p_value: p_value.min(1.0),
+        })
+    }
+
+    /// Run a test of whether the ratio of the rate of `events_x` over `exposure_x` to the rate
+    /// of `events_y` over `exposure_y` equals `ratio`, with the given `method`.
+    ///
+    /// The estimate is the observed rate ratio, and the effect size is the same ratio with a
+    /// 95% confidence interval of the method, which is one-sided for one-sided alternatives.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::test::{AlternativeHypothesis, PoissonTest, RateRatioMethod, StatisticalTest};
+    ///
+    /// let test = PoissonTest::rate_ratio(
+    ///     41,
+    ///     28.0,
+    ///     15,
+    ///     19.0,
+    ///     1.0,
+    ///     RateRatioMethod::Exact,
+    ///     AlternativeHypothesis::Different,
+    /// )
+    /// .unwrap();
+    /// assert!(test.p_value() < 0.05);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an exposure or the ratio is not positive, if there are no events,
+    /// or if a sample has no events with the Wald method.
+    pub fn rate_ratio(
+        events_x: usize,
+        exposure_x: f64,
+        events_y: usize,
+        exposure_y: f64,
+        ratio: f64,
+        method: RateRatioMethod,
+        alternative: AlternativeHypothesis,
+    ) -> Result<PoissonTest> {
+        let positive = |value: f64| value > 0.0 && value.is_finite();
+        if !positive(exposure_x) || !positive(exposure_y) || !positive(ratio) {
+            return Err(StatsError::BadParams);
+        }
+        let events = events_x + events_y;
+        let (x, y) = (events_x as f64, events_y as f64);
+        let estimate = (x / exposure_x) / (y / exposure_y);
+        // Conditional on the total, the events of x are binomial with a proportion that
+        // determines the rate ratio.
+        let proportion = ratio * exposure_x / (ratio * exposure_x + exposure_y);
+        let to_ratio = |p: f64| p / (1.0 - p) * exposure_y / exposure_x;
+
+        let normal = Normal::new(0.0, 1.0)?;
+        let normal_p_value = |z: f64| match alternative {
+            AlternativeHypothesis::Less => normal.cdf(z),
+            AlternativeHypothesis::Greater => normal.sf(z),
+            AlternativeHypothesis::Different => 2.0 * normal.sf(z.abs()),
+        };
+
+        let (p_value, confidence_interval) = match method {
+            RateRatioMethod::Exact => {
+                let test = BinomialTest::new(events_x, events, proportion, alternative)?;
+                let (lower, upper) = test.effect_size().confidence_interval();
+                (test.p_value(), (to_ratio(lower), to_ratio(upper)))
+            }
+            RateRatioMethod::Score => {
+                if events == 0 {
+                    return Err(StatsError::BadParams);
+                }
+                let n = events as f64;
+                let z = (x - n * proportion) / (n * proportion * (1.0 - proportion)).sqrt();
+                let interval = |confidence_level| {
+                    let (lower, upper) =
+                        ProportionInterval::Wilson.interval(events_x, events, confidence_level)?;
+                    Ok((to_ratio(lower), to_ratio(upper)))
+                };
+                let confidence_interval = one_sided(interval, alternative, (0.0, f64::INFINITY))?;
+                (normal_p_value(z), confidence_interval)
+            }
+            RateRatioMethod::Wald => {
+                if events_x == 0 || events_y == 0 {
+                    return Err(StatsError::BadParams);
+                }
+                let standard_error = (1.0 / x + 1.0 / y).sqrt();
+                let z = (estimate.ln() - ratio.ln()) / standard_error;
+                let interval = |confidence_level: f64| {
+                    let margin =
+                        normal.inverse_cdf((1.0 + confidence_level) / 2.0) * standard_error;
+                    Ok((
+                        (estimate.ln() - margin).exp(),
+                        (estimate.ln() + margin).exp(),
+                    ))
+                };
+                let confidence_interval = one_sided(interval, alternative, (0.0, f64::INFINITY))?;
+                (normal_p_value(z), confidence_interval)
+            }
+        };
+
+        let effect_size = EffectSize::new(
+            EffectSizeKind::RateRatio,
+            estimate,
+            confidence_interval,
+            CONFIDENCE_LEVEL,
+        );
+
+        Ok(PoissonTest {
+            effect_size,
+            p_value,
+        })
+    }
+}
+
+impl StatisticalTest for PoissonTest {
+    type Estimate = f64;
+    type EffectSize = EffectSize;
+
+    fn estimate(&self) -> f64 {
+        self.effect_size.estimate()
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> EffectSize {
+        self.effect_size
+    }
+}
+
+/// Implements the [index of dispersion](https://en.wikipedia.org/wiki/Index_of_dispersion)
+/// test of whether counts are more or less variable than under a Poisson distribution
+/// (Fisher, 1950; Cochran, 1954).
+///
+/// # References
+///
+/// Cochran, W. G. (1954). Some Methods for Strengthening the Common χ² Tests. Biometrics,
+///     10(4), 417–451. <https://doi.org/10.2307/3001616>
+///
+/// Fisher, R. A. (1950). The Significance of Deviations from Expectation in a Poisson
+///     Series. Biometrics, 6(1), 17–24. <https://doi.org/10.2307/3001420>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DispersionTest {
+    df: f64,
+    estimate: f64,
+    p_value: f64,
+}
+
+impl DispersionTest {
+    /// Run the index of dispersion test on the `counts`, which were all observed over the
+    /// same exposure. The `alternative` hypothesis refers to the variance of the counts
+    /// compared to their mean, where `Greater` means overdispersion.
+    ///
+    /// The estimate is the chi-squared statistic, and the effect size is the index of
+    /// dispersion, the ratio of the variance of the counts to their mean.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::test::{AlternativeHypothesis, DispersionTest, StatisticalTest};
+    ///
+    /// let counts = vec![0, 7, 1, 0, 12, 2, 0, 9, 1, 0];
+    /// let test = DispersionTest::new(&counts, AlternativeHypothesis::Greater).unwrap();
+    /// assert!(test.effect_size() > 1.0 && test.p_value() < 0.05);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are fewer than two counts or if every count is zero.
+    pub fn new(counts: &[usize], alternative: AlternativeHypothesis) -> Result<DispersionTest> {
+        DispersionTest::with_exposures(counts, &vec![1.0; counts.len()], alternative)
+    }
+
+    /// Run the index of dispersion test on the `counts`, which were observed over the given
+    /// `exposures`, against a common rate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are fewer than two counts, if the counts and exposures
+    /// differ in length, if an exposure is not positive or if every count is zero.
+    pub fn with_exposures(
+        counts: &[usize],
+        exposures: &[f64],
+        alternative: AlternativeHypothesis,
+    ) -> Result<DispersionTest> {
+        if counts.len() != exposures.len() {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+        if counts.len() < 2 || exposures.iter().any(|t| !(*t > 0.0 && t.is_finite())) {
+            return Err(StatsError::BadParams);
+        }
+        let rate = counts.iter().sum::<usize>() as f64 / exposures.iter().sum::<f64>();
+        if rate == 0.0 {
+            return Err(StatsError::BadParams);
+        }
+
+        let estimate = counts
+            .iter()
+            .zip(exposures)
+            .map(|(x, t)| (*x as f64 - rate * t).powi(2) / (rate * t))
+            .sum::<f64>();
+        let df = (counts.len() - 1) as f64;
+        let chi_squared = ChiSquared::new(df)?;
+        let p_value = match alternative {
+            AlternativeHypothesis::Less => chi_squared.cdf(estimate),
+            AlternativeHypothesis::Greater => chi_squared.sf(estimate),
+            AlternativeHypothesis::Different => {
+                (2.0 * chi_squared.cdf(estimate).min(chi_squared.sf(estimate))).min(1.0)
+            }
+        };
+
+        Ok(DispersionTest {
+            df,
+            estimate,
+            p_value,
+        })
+    }
+
+    /// Returns the degrees of freedom.
+    pub fn df(&self) -> f64 {
+        self.df
+    }
+}
+
+impl StatisticalTest for DispersionTest {
+    type Estimate = f64;
+    type EffectSize = f64;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> f64 {
+        self.estimate / self.df
+    }
+}
+
+/// Returns the confidence interval at `CONFIDENCE_LEVEL` given a function that computes
+/// two-sided intervals, where one-sided alternatives replace a bound by that of the `range`.
+fn one_sided<F>(
+    interval: F,
+    alternative: AlternativeHypothesis,
+    range: (f64, f64),
+) -> Result<(f64, f64)>
+where
+    F: Fn(f64) -> Result<(f64, f64)>,
+{
+    let one_sided_level = 1.0 - 2.0 * (1.0 - CONFIDENCE_LEVEL);
+    Ok(match alternative {
+        AlternativeHypothesis::Less => (range.0, interval(one_sided_level)?.1),
+        AlternativeHypothesis::Greater => (interval(one_sided_level)?.0, range.1),
+        AlternativeHypothesis::Different => interval(CONFIDENCE_LEVEL)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poisson() {
+        let test = PoissonTest::new(137, 24.19893, 1.0, AlternativeHypothesis::Different).unwrap();
+        assert_eq!(test.estimate(), 5.661407343217241);
+        assert_eq!(test.p_value(), 2.8452272641146293e-56);
+        assert_eq!(
+            test.effect_size().confidence_interval(),
+            (4.753124804848723, 6.692709334176062)
+        );
+
+        let test = PoissonTest::new(2, 1.0, 4.5, AlternativeHypothesis::Different).unwrap();
+        assert_eq!(test.p_value(), 0.3425274921846242);
+        let test = PoissonTest::new(0, 2.0, 1.0, AlternativeHypothesis::Less).unwrap();
+        assert_eq!(test.p_value(), 0.13533528323661276);
+        assert_eq!(
+            test.effect_size().confidence_interval(),
+            (0.0, 1.497866136777017)
+        );
+
+        assert!(PoissonTest::new(3, 0.0, 1.0, AlternativeHypothesis::Less).is_err());
+    }
+
+    #[test]
+    fn rate_ratio() {
+        let test = |method| {
+            PoissonTest::rate_ratio(
+                11,
+                800.0,
+                21,
+                3011.0,
+                1.0,
+                method,
+                AlternativeHypothesis::Different,
+            )
+            .unwrap()
+        };
+        let exact = test(RateRatioMethod::Exact);
+        assert_eq!(exact.estimate(), 1.9714880952380953);
+        assert_eq!(exact.p_value(), 0.07966863303333001);
+        assert_eq!(
+            exact.effect_size().confidence_interval(),
+            (0.8584264033916225, 4.277265943654087)
+        );
+        let wald = test(RateRatioMethod::Wald);
+        assert_eq!(wald.p_value(), 0.06818971169497354);
+        assert_eq!(
+            wald.effect_size().confidence_interval(),
+            (0.9505660969473079, 4.088895366821594)
+        );
+        let score = test(RateRatioMethod::Score);
+        assert_eq!(score.p_value(), 0.06303198352373193);
+        assert_eq!(
+            score.effect_size().confidence_interval(),
+            (0.965199251443906, 4.026904604257683)
+        );
+
+        assert!(PoissonTest::rate_ratio(
+            0,
+            1.0,
+            4,
+            1.0,
+            1.0,
+            RateRatioMethod::Wald,
+            AlternativeHypothesis::Different
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn dispersion() {
+        let counts = vec![3, 5, 2, 4, 6, 3, 2, 5, 4, 4];
+        let test = DispersionTest::new(&counts, AlternativeHypothesis::Different).unwrap();
+        assert_eq!(test.df(), 9.0);
+        assert_eq!(test.estimate(), 4.105263157894736);
+        assert_eq!(test.p_value(), 0.1913026999319153);
+
+        let test = DispersionTest::with_exposures(
+            &[4, 10, 7],
+            &[1.0, 2.0, 1.5],
+            AlternativeHypothesis::Greater,
+        )
+        .unwrap();
+        assert_eq!(test.estimate(), 0.14285714285714285);
+        assert!(DispersionTest::new(&[0, 0, 0], AlternativeHypothesis::Greater).is_err());
+    }
+}
@@ -0,0 +1,508 @@
+use crate::distribution::NoncentralChiSquared;
+use crate::effect_size::{dominance, EffectSize, CONFIDENCE_LEVEL};
+use crate::statistics::*;
+use statrs::distribution::{ContinuousCDF, Normal};
+use statrs::statistics::Statistics;
+use statrs::{Result, StatsError};
+use std::f64::consts::SQRT_2;
+
+use super::{BoundScale, EquivalenceBounds, StatisticalTest};
+
+/// Implements Wellek's rank equivalence tests, the Mann-Whitney test for equivalence of the
+/// probability of superiority P(X > Y) and the signed rank test for equivalence of the
+/// probability P(D_i + D_j > 0) of positive Walsh averages of paired differences
+/// (Wellek, 2010). Ties count as half.
+///
+/// Raw bounds are offsets of these probabilities from one half, so that bounds from −0.2 to 0.2
+/// give the equivalence interval from 0.3 to 0.7, and a non-inferiority margin of 0.1 tests
+/// that the probability exceeds 0.4. Standardized bounds are on the scale of Cohen's d, and are
+/// converted to probabilities assuming normal distributions with equal variances.
+///
+/// The null hypothesis is that the probability lies outside the bounds, so a small p-value is
+/// evidence of equivalence. With bounds on both sides the p-value is that of Wellek's test
+/// based on the noncentral χ²-distribution. With a single finite bound, as for
+/// non-inferiority and superiority, it is that of the one-sided z-test against that bound.
+///
+/// # References
+///
+/// Wellek, S. (2010). Testing Statistical Hypotheses of Equivalence and Noninferiority
+///     (2nd ed.). Chapman and Hall/CRC. <https://doi.org/10.1201/EBK1439808184>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RankEquivalenceTest {
+    estimate: f64,
+    standard_error: f64,
+    bounds: (f64, f64),
+    effect_size: EffectSize,
+    p_value: f64,
+}
+
+impl RankEquivalenceTest {
+    /// Run the Mann-Whitney test for equivalence on the independent samples `x` and `y`.
+    ///
+    /// The effect size is the rank-biserial correlation, with a 95% confidence interval.
+    pub fn independent(
+        x: &[f64],
+        y: &[f64],
+        bounds: EquivalenceBounds,
+    ) -> Result<RankEquivalenceTest> {
+        if x.len() < 2 || y.len() < 2 {
+            return Err(StatsError::BadParams);
+        }
+
+        let (m, n) = (x.n(), y.n());
+        let mut estimate = 0.0;
+        let mut total_squares = 0.0;
+        let mut pairs_x = 0.0;
+        let mut pairs_y = 0.0;
+        let mut placements_x = vec![(0.0, 0.0); x.len()];
+        for y_j in y {
+            let (mut sum, mut squares) = (0.0, 0.0);
+            for (x_i, placement) in x.iter().zip(placements_x.iter_mut()) {
+                let kernel = dominance(*x_i, *y_j);
+                sum += kernel;
+                squares += kernel * kernel;
+                placement.0 += kernel;
+                placement.1 += kernel * kernel;
+            }
+            estimate += sum;
+            total_squares += squares;
+            pairs_x += sum * sum - squares;
+        }
+        for (sum, squares) in placements_x {
+            pairs_y += sum * sum - squares;
+        }
+
+        // Ties score a half, so the mean squared kernel differs from the estimate.
+        let estimate = estimate / (m * n);
+        let squares = total_squares / (m * n);
+        let pairs_x = pairs_x / (m * (m - 1.0) * n);
+        let pairs_y = pairs_y / (m * n * (n - 1.0));
+        let variance = (squares - (m + n - 1.0) * estimate * estimate
+            + (m - 1.0) * pairs_x
+            + (n - 1.0) * pairs_y)
+            / (m * n);
+
+        let bounds = bounds.probabilities(|d| d / SQRT_2)?;
+        let effect_size = EffectSize::rank_biserial(x, y, CONFIDENCE_LEVEL)?;
+        RankEquivalenceTest::new(estimate, variance.sqrt(), bounds, effect_size)
+    }
+
+    /// Run the signed rank test for equivalence on the paired samples `x` and `y`.
+    ///
+    /// The effect size is the matched-pairs rank-biserial correlation, with a 95% confidence
+    /// interval.
+    pub fn paired(x: &[f64], y: &[f64], bounds: EquivalenceBounds) -> Result<RankEquivalenceTest> {
+        if x.len() != y.len() {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+        if x.len() < 3 {
+            return Err(StatsError::BadParams);
+        }
+
+        let d: Vec<_> = x.iter().zip(y).map(|(x, y)| x - y).collect();
+        let n = (&d).n();
+        let mut estimate = 0.0;
+        let mut total_squares = 0.0;
+        let mut triples = 0.0;
+        for (i, d_i) in d.iter().enumerate() {
+            let (mut sum, mut squares) = (0.0, 0.0);
+            for (j, d_j) in d.iter().enumerate() {
+                if i != j {
+                    let kernel = dominance(d_i + d_j, 0.0);
+                    sum += kernel;
+                    squares += kernel * kernel;
+                }
+            }
+            estimate += sum;
+            total_squares += squares;
+            triples += sum * sum - squares;
+        }
+
+        let estimate = estimate / (n * (n - 1.0));
+        let squares = total_squares / (n * (n - 1.0));
+        let triples = triples / (n * (n - 1.0) * (n - 2.0));
+        let variance = 4.0 * (n - 2.0) / (n * (n - 1.0)) * (triples - estimate * estimate)
+            + 2.0 / (n * (n - 1.0)) * (squares - estimate * estimate);
+
+        let bounds = bounds.probabilities(|d| d * SQRT_2)?;
+        let effect_size = EffectSize::rank_biserial_paired(x, y, CONFIDENCE_LEVEL)?;
+        RankEquivalenceTest::new(estimate, variance.sqrt(), bounds, effect_size)
+    }
+
+    fn new(
+        estimate: f64,
+        standard_error: f64,
+        bounds: (f64, f64),
+        effect_size: EffectSize,
+    ) -> Result<RankEquivalenceTest> {
+        let p_value = match bounds {
+            (lower, f64::INFINITY) => normal_sf(estimate, lower, standard_error.powi(2)),
+            (f64::NEG_INFINITY, upper) => 1.0 - normal_sf(estimate, upper, standard_error.powi(2)),
+            (lower, upper) => {
+                let center = (lower + upper) / 2.0;
+                let half_width = (upper - lower) / 2.0;
+                let distribution =
+                    NoncentralChiSquared::new(1.0, (half_width / standard_error).powi(2))?;
+                distribution.cdf(((estimate - center) / standard_error).powi(2))
+            }
+        };
+
+        Ok(RankEquivalenceTest {
+            estimate,
+            standard_error,
+            bounds,
+            effect_size,
+            p_value,
+        })
+    }
+
+    /// Returns the standard error of the estimated probability.
+    pub fn standard_error(&self) -> f64 {
+        self.standard_error
+    }
+
+    /// Returns the bounds on the scale of the probability, with an infinite bound on the open
+    /// side of a one-sided test.
+    pub fn bounds(&self) -> (f64, f64) {
+        self.bounds
+    }
+}
+
+impl StatisticalTest for RankEquivalenceTest {
+    type Estimate = f64;
+    type EffectSize = EffectSize;
+
+    /// Returns the estimated probability.
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> EffectSize {
+        self.effect_size
+    }
+}
+
+/// Implements the two one-sided tests (TOST) procedure for equivalence of the location shift
+/// between two samples, using the Mann-Whitney U test for independent samples and the Wilcoxon
+/// signed rank test for paired samples, with the Hodges-Lehmann estimate of the shift and its
+/// distribution-free confidence interval (Hodges & Lehmann, 1963).
+///
+/// Raw bounds are in the units of the observations. Standardized bounds are relative to the
+/// pooled standard deviation, or to the standard deviation of the differences.
+///
+/// # References
+///
+/// Hodges, J. L., & Lehmann, E. L. (1963). Estimates of Location Based on Rank Tests. The Annals
+///     of Mathematical Statistics, 34(2), 598–611. <https://doi.org/10.1214/aoms/1177704172>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct HodgesLehmannEquivalenceTest {
+    estimate: f64,
+    bounds: (f64, f64),
+    confidence_interval: (f64, f64),
+    effect_size: EffectSize,
+    p_value: f64,
+}
+
+impl HodgesLehmannEquivalenceTest {
+    /// Run the equivalence test with the Mann-Whitney U test on the independent samples `x`
+    /// and `y`.
+    ///
+    /// The effect size is the rank-biserial correlation, with a 95% confidence interval.
+    pub fn independent(
+        x: &[f64],
+        y: &[f64],
+        bounds: EquivalenceBounds,
+    ) -> Result<HodgesLehmannEquivalenceTest> {
+        if x.is_empty() || y.is_empty() {
+            return Err(StatsError::BadParams);
+        }
+
+        let bounds = bounds.raw(x.pooled_std_dev(y));
+        let mut differences: Vec<_> = x
+            .iter()
+            .flat_map(|x| y.iter().map(move |y| x - y))
+            .collect();
+        differences.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let (m, n) = (x.n(), y.n());
+        let (_, tie_correction) = x.iter().chain(y).ranks();
+        let variance = rank_sum_variance(m, n, tie_correction);
+        let confidence_interval = order_interval(&differences, m * n / 2.0, variance);
+
+        let p_value = |shift: f64| {
+            let shifted: Vec<_> = x.iter().map(|x| x - shift).collect();
+            let (ranks, tie_correction) = shifted.iter().chain(y).ranks();
+            let u = ranks[0..x.len()].iter().sum::<f64>() - m * (m + 1.0) / 2.0;
+            let variance = rank_sum_variance(m, n, tie_correction);
+            normal_sf(u, m * n / 2.0, variance)
+        };
+
+        Ok(HodgesLehmannEquivalenceTest {
+            estimate: median(&differences),
+            bounds,
+            confidence_interval,
+            effect_size: EffectSize::rank_biserial(x, y, CONFIDENCE_LEVEL)?,
+            p_value: one_sided_p_values(bounds, p_value),
+        })
+    }
+
+    /// Run the equivalence test with the Wilcoxon signed rank test on the paired samples `x`
+    /// and `y`.
+    ///
+    /// The effect size is the matched-pairs rank-biserial correlation, with a 95% confidence
+    /// interval.
+    pub fn paired(
+        x: &[f64],
+        y: &[f64],
+        bounds: EquivalenceBounds,
+    ) -> Result<HodgesLehmannEquivalenceTest> {
+        if x.len() != y.len() {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+        if x.is_empty() {
+            return Err(StatsError::BadParams);
+        }
+
+        let d: Vec<_> = x.iter().zip(y).map(|(x, y)| x - y).collect();
+        let bounds = bounds.raw((&d).std_dev());
+        let mut averages: Vec<_> = d
+            .iter()
+            .enumerate()
+            .flat_map(|(i, d_i)| d[i..].iter().map(move |d_j| (d_i + d_j) / 2.0))
+            .collect();
+        averages.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let (_, tie_correction) = d.iter().map(|d| d.abs()).ranks();
+        let variance = signed_rank_variance((&d).n(), tie_correction);
+        let confidence_interval = order_interval(&averages, (&averages).n() / 2.0, variance);
+
+        let p_value = |shift: f64| {
+            let shifted: Vec<_> = d.iter().map(|d| d - shift).filter(|d| *d != 0.0).collect();
+            let (ranks, tie_correction) = shifted.iter().map(|d| d.abs()).ranks();
+            let w = shifted
+                .iter()
+                .zip(ranks)
+                .filter(|(d, _)| **d > 0.0)
+                .map(|(_, rank)| rank)
+                .sum::<f64>();
+            let n = (&shifted).n();
+            let variance = signed_rank_variance(n, tie_correction);
+            normal_sf(w, n * (n + 1.0) / 4.0, variance)
+        };
+
+        Ok(HodgesLehmannEquivalenceTest {
+            estimate: median(&averages),
+            bounds,
+            confidence_interval,
+            effect_size: EffectSize::rank_biserial_paired(x, y, CONFIDENCE_LEVEL)?,
+            p_value: one_sided_p_values(bounds, p_value),
+        })
+    }
+
+    /// Returns the 90% distribution-free confidence interval of the shift, which corresponds to
+    /// the two one-sided tests at a significance level of 5%.
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        self.confidence_interval
+    }
+
+    /// Returns the bounds in the units of the observations.
+    pub fn bounds(&self) -> (f64, f64) {
+        self.bounds
+    }
+}
+
+impl StatisticalTest for HodgesLehmannEquivalenceTest {
+    type Estimate = f64;
+    type EffectSize = EffectSize;
+
+    /// Returns the Hodges-Lehmann estimate of the shift.
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    /// Returns the larger of the p-values of the two one-sided tests.
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> EffectSize {
+        self.effect_size
+    }
+}
+
+impl EquivalenceBounds {
+    /// Returns the bounds on the scale of a probability, offsetting raw bounds from one half
+    /// and converting standardized bounds with the standard normal distribution after applying
+    /// `scale`. Infinite bounds stay infinite, so that one-sided tests remain one-sided.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if both bounds are infinite, or if a finite raw bound gives a
+    /// probability outside the unit interval.
+    fn probabilities<F: Fn(f64) -> f64>(&self, scale: F) -> Result<(f64, f64)> {
+        let normal = Normal::new(0.0, 1.0)?;
+        let probability = |bound: f64| match self.scale() {
+            _ if bound.is_infinite() => Ok(bound),
+            BoundScale::Raw if (-0.5..=0.5).contains(&bound) => Ok(0.5 + bound),
+            BoundScale::Raw => Err(StatsError::BadParams),
+            BoundScale::Standardized => Ok(normal.cdf(scale(bound))),
+        };
+        let bounds = (probability(self.lower())?, probability(self.upper())?);
+        if bounds.0.is_infinite() && bounds.1.is_infinite() {
+            return Err(StatsError::BadParams);
+        }
+        Ok(bounds)
+    }
+}
+
+fn rank_sum_variance(m: f64, n: f64, tie_correction: usize) -> f64 {
+    let total = m + n;
+    m * n * (total + 1.0 - tie_correction as f64 / (total * (total - 1.0))) / 12.0
+}
+
+fn signed_rank_variance(n: f64, tie_correction: usize) -> f64 {
+    n * (n + 1.0) * (2.0 * n + 1.0) / 24.0 - tie_correction as f64 / 48.0
+}
+
+/// Returns the probability of exceeding `statistic` under the normal approximation.
+fn normal_sf(statistic: f64, mean: f64, variance: f64) -> f64 {
+    match Normal::new(mean, variance.sqrt()) {
+        Ok(normal) => normal.sf(statistic),
+        Err(_) => f64::NAN,
+    }
+}
+
+/// Returns the larger p-value of the tests that the shift exceeds the lower bound and that it
+/// falls short of the upper bound, given the p-value of the former as a function of the bound.
+fn one_sided_p_values<F: Fn(f64) -> f64>(bounds: (f64, f64), p_value: F) -> f64 {
+    let lower = if bounds.0 == f64::NEG_INFINITY {
+        0.0
+    } else {
+        p_value(bounds.0)
+    };
+    let upper = if bounds.1 == f64::INFINITY {
+        0.0
+    } else {
+        1.0 - p_value(bounds.1)
+    };
+    lower.max(upper)
+}
+
+/// Returns the distribution-free confidence interval from the `sorted` pairwise estimates,
+/// given the mean and variance of the rank statistic.
+fn order_interval(sorted: &[f64], mean: f64, variance: f64) -> (f64, f64) {
+    let alpha = 1.0 - CONFIDENCE_LEVEL;
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let critical = (mean - normal.inverse_cdf(1.0 - alpha) * variance.sqrt()).floor();
+    let index = (critical.max(0.0) as usize).min(sorted.len() - 1);
+    (sorted[index], sorted[sorted.len() - 1 - index])
+}
+
+fn median(sorted: &[f64]) -> f64 {
+    (sorted[(sorted.len() - 1) / 2] + sorted[sorted.len() / 2]) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mann_whitney() {
+        let x = vec![
+            134.0, 146.0, 104.0, 119.0, 124.0, 161.0, 107.0, 83.0, 113.0, 129.0, 97.0, 123.0,
+        ];
+        let y = vec![70.0, 118.0, 101.0, 85.0, 107.0, 132.0, 94.0];
+        let bounds = EquivalenceBounds::new(-0.2, 0.2, BoundScale::Raw).unwrap();
+        let test = RankEquivalenceTest::independent(&x, &y, bounds).unwrap();
+        assert_eq!(test.estimate(), 0.7440476190476191);
+        assert_eq!(test.standard_error(), 0.10594330389918696);
+        assert_eq!(test.p_value(), 0.6611954999690864);
+    }
+
+    #[test]
+    fn mann_whitney_standardized() {
+        let x = vec![68.0, 68.0, 59.0, 72.0, 64.0, 67.0, 70.0, 74.0];
+        let y = vec![60.0, 67.0, 61.0, 62.0, 67.0, 63.0, 56.0, 58.0];
+        let bounds = EquivalenceBounds::new(-2.0, 2.0, BoundScale::Standardized).unwrap();
+        let test = RankEquivalenceTest::independent(&x, &y, bounds).unwrap();
+        assert_eq!(test.bounds(), (0.07864960352864254, 0.9213503964713574));
+        assert_eq!(test.estimate(), 0.859375);
+        assert_eq!(test.p_value(), 0.234539506633304);
+    }
+
+    #[test]
+    fn signed_rank() {
+        let x = vec![209.0, 200.0, 177.0, 169.0, 159.0, 169.0, 187.0, 198.0];
+        let y = vec![151.0, 168.0, 147.0, 164.0, 166.0, 163.0, 176.0, 188.0];
+        let bounds = EquivalenceBounds::new(-0.3, 0.3, BoundScale::Raw).unwrap();
+        let test = RankEquivalenceTest::paired(&x, &y, bounds).unwrap();
+        assert_eq!(test.estimate(), 0.9285714285714286);
+        assert_eq!(test.standard_error(), 0.0522803610508144);
+        assert_eq!(test.p_value(), 0.9930389712180948);
+    }
+
+    #[test]
+    fn ties() {
+        // The standard errors agree with the variance estimators evaluated by brute force over
+        // all pairs and triples of observations, with tied kernels scored as a half.
+        let x = vec![1.0, 2.0, 2.0, 3.0, 3.0, 3.0, 4.0, 5.0];
+        let y = vec![1.0, 1.0, 2.0, 2.0, 3.0, 4.0, 4.0];
+        let bounds = EquivalenceBounds::new(-0.2, 0.2, BoundScale::Raw).unwrap();
+        let test = RankEquivalenceTest::independent(&x, &y, bounds).unwrap();
+        assert_eq!(test.estimate(), 0.5982142857142857);
+        assert_eq!(test.standard_error(), 0.1320553523013308);
+
+        let x = vec![3.0, 1.0, 2.0, 4.0, 2.0, 5.0, 1.0, 3.0];
+        let y = vec![2.0, 2.0, 3.0, 2.0, 1.0, 3.0, 2.0, 3.0];
+        let bounds = EquivalenceBounds::new(-0.3, 0.3, BoundScale::Raw).unwrap();
+        let test = RankEquivalenceTest::paired(&x, &y, bounds).unwrap();
+        assert_eq!(test.estimate(), 0.6785714285714286);
+        assert_eq!(test.standard_error(), 0.14460033388539165);
+    }
+
+    #[test]
+    fn non_inferiority() {
+        let x = vec![68.0, 68.0, 59.0, 72.0, 64.0, 67.0, 70.0, 74.0];
+        let y = vec![60.0, 67.0, 61.0, 62.0, 67.0, 63.0, 56.0, 58.0];
+        let bounds = EquivalenceBounds::non_inferiority(0.1, BoundScale::Raw).unwrap();
+        let test = RankEquivalenceTest::independent(&x, &y, bounds).unwrap();
+        assert_eq!(test.bounds(), (0.4, f64::INFINITY));
+        assert_eq!(test.p_value(), 4.0187725539306365e-8);
+
+        let bounds = EquivalenceBounds::superiority(0.3, BoundScale::Raw).unwrap();
+        let test = RankEquivalenceTest::independent(&x, &y, bounds).unwrap();
+        assert_eq!(test.bounds(), (0.8, f64::INFINITY));
+        assert_eq!(test.p_value(), 0.24396613776549736);
+
+        let bounds = EquivalenceBounds::superiority(0.6, BoundScale::Raw).unwrap();
+        assert!(RankEquivalenceTest::independent(&x, &y, bounds).is_err());
+    }
+
+    #[test]
+    fn hodges_lehmann() {
+        let x = vec![
+            134.0, 146.0, 104.0, 119.0, 124.0, 161.0, 107.0, 83.0, 113.0, 129.0, 97.0, 123.0,
+        ];
+        let y = vec![70.0, 118.0, 101.0, 85.0, 107.0, 132.0, 94.0];
+        let bounds = EquivalenceBounds::new(-40.0, 40.0, BoundScale::Raw).unwrap();
+        let test = HodgesLehmannEquivalenceTest::independent(&x, &y, bounds).unwrap();
+        assert_eq!(test.estimate(), 18.5);
+        assert_eq!(test.confidence_interval(), (1.0, 37.0));
+        assert_eq!(test.p_value(), 0.023462075324500287);
+    }
+
+    #[test]
+    fn hodges_lehmann_paired() {
+        let x = vec![8.0, 6.0, 5.5, 11.0, 8.5, 5.0, 6.0, 6.0];
+        let y = vec![8.5, 9.0, 6.5, 10.5, 9.0, 7.0, 6.5, 7.0];
+        let bounds = EquivalenceBounds::non_inferiority(2.0, BoundScale::Raw).unwrap();
+        let test = HodgesLehmannEquivalenceTest::paired(&x, &y, bounds).unwrap();
+        assert_eq!(test.estimate(), -0.75);
+        assert_eq!(test.confidence_interval(), (-1.75, -0.5));
+        assert_eq!(test.p_value(), 0.019795881617466888);
+    }
+}
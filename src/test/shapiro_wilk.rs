@@ -175,6 +175,7 @@ impl ShapiroWilkTest {
 
 impl StatisticalTest for ShapiroWilkTest {
     type Estimate = f64;
+    type EffectSize = f64;
 
     fn estimate(&self) -> f64 {
         self.estimate
@@ -1,3 +1,5 @@
+use crate::effect_size::{EffectSize, CONFIDENCE_LEVEL};
+use crate::permutation::{PermutationOptions, PermutationTest};
 use crate::statistics::StatisticsExt;
 use statrs::distribution::{ContinuousCDF, StudentsT};
 use statrs::statistics::Statistics;
@@ -9,52 +11,138 @@ use super::StatisticalTest;
 pub struct StudentsTTest {
     df: f64,
     estimate: f64,
-    effect_size: f64,
+    effect_size: EffectSize,
     p_value: f64,
 }
 
 impl StudentsTTest {
     /// Run Student's two-sample t-test on samples `x` and `y`.
+    ///
+    /// The effect size is Cohen's d, with a 95% confidence interval.
     pub fn independent(x: &[f64], y: &[f64]) -> statrs::Result<StudentsTTest> {
         let n_x = x.n();
         let n_y = y.n();
         let df = n_x + n_y;
 
-        let effect_size = (x.mean() - y.mean()) / x.pooled_variance(y).sqrt();
-        let t = effect_size / (n_x.recip() + n_y.recip()).sqrt();
+        let d = (x.mean() - y.mean()) / x.pooled_variance(y).sqrt();
+        let t = d / (n_x.recip() + n_y.recip()).sqrt();
 
         let t_distribution = StudentsT::new(0.0, 1.0, df)?;
         let p_value = 2.0 * t_distribution.cdf(-t.abs());
 
+        let effect_size = EffectSize::cohens_d(x, y, CONFIDENCE_LEVEL)?;
+
         Ok(StudentsTTest {
             df,
-            effect_size: effect_size.abs(),
+            effect_size,
             estimate: t,
             p_value,
         })
     }
 
     /// Run paired Student's t-test on samples `x` and `y`.
+    ///
+    /// The effect size is Cohen's d of the differences, with a 95% confidence interval.
     pub fn paired(x: &[f64], y: &[f64]) -> statrs::Result<StudentsTTest> {
         let d: Vec<_> = x.iter().zip(y).map(|(x, y)| x - y).collect();
         let df = x.df();
-        let effect_size = (&d).mean() / (&d).std_dev();
-        let t = effect_size * x.n().sqrt();
+        let t = (&d).mean() / (&d).std_dev() * x.n().sqrt();
 
         let t_distribution = StudentsT::new(0.0, 1.0, df)?;
         let p_value = 2.0 * t_distribution.cdf(-t.abs());
 
+        let effect_size = EffectSize::cohens_d_paired(x, y, CONFIDENCE_LEVEL)?;
+
         Ok(StudentsTTest {
             df,
-            effect_size: effect_size.abs(),
+            effect_size,
             estimate: t,
             p_value,
         })
     }
+
+    /// Run Student's two-sample t-test on samples `x` and `y`, with a p-value from a
+    /// permutation test of the t-statistic instead of the t-distribution.
+    pub fn independent_permutation(
+        x: &[f64],
+        y: &[f64],
+        options: PermutationOptions,
+    ) -> statrs::Result<StudentsTTest> {
+        let test = StudentsTTest::independent(x, y)?;
+        let t = |x: &[f64], y: &[f64]| MeanDifference::pooled(x, y).t();
+        let permutation = PermutationTest::two_sample(x, y, t, options)?;
+
+        Ok(StudentsTTest {
+            p_value: permutation.p_value(),
+            ..test
+        })
+    }
+
+    /// Run paired Student's t-test on samples `x` and `y`, with a p-value from a permutation
+    /// test of the t-statistic that swaps observations within pairs.
+    pub fn paired_permutation(
+        x: &[f64],
+        y: &[f64],
+        options: PermutationOptions,
+    ) -> statrs::Result<StudentsTTest> {
+        let test = StudentsTTest::paired(x, y)?;
+        let t = |x: &[f64], y: &[f64]| MeanDifference::paired(x, y).t();
+        let permutation = PermutationTest::paired(x, y, t, options)?;
+
+        Ok(StudentsTTest {
+            p_value: permutation.p_value(),
+            ..test
+        })
+    }
+}
+
+/// Difference between the means of two samples, with the quantities the t-tests derive from it.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct MeanDifference {
+    /// The difference between the means.
+    pub(crate) estimate: f64,
+    /// The standard error of the difference.
+    pub(crate) standard_error: f64,
+    /// The standard deviation by which the difference is standardized.
+    pub(crate) standardizer: f64,
+    /// The degrees of freedom of the t-distribution of the difference.
+    pub(crate) df: f64,
+}
+
+impl MeanDifference {
+    /// Returns the difference between the means of the independent samples `x` and `y`,
+    /// assuming equal variances.
+    pub(crate) fn pooled(x: &[f64], y: &[f64]) -> MeanDifference {
+        let standardizer = x.pooled_std_dev(y);
+        MeanDifference {
+            estimate: x.mean() - y.mean(),
+            standard_error: standardizer * (x.n().recip() + y.n().recip()).sqrt(),
+            standardizer,
+            df: x.n() + y.n() - 2.0,
+        }
+    }
+
+    /// Returns the mean of the differences between the paired samples `x` and `y`.
+    pub(crate) fn paired(x: &[f64], y: &[f64]) -> MeanDifference {
+        let d: Vec<_> = x.iter().zip(y).map(|(x, y)| x - y).collect();
+        let standardizer = (&d).std_dev();
+        MeanDifference {
+            estimate: (&d).mean(),
+            standard_error: standardizer / (&d).n().sqrt(),
+            standardizer,
+            df: (&d).df(),
+        }
+    }
+
+    /// Returns the t-statistic of the difference.
+    pub(crate) fn t(&self) -> f64 {
+        self.estimate / self.standard_error
+    }
 }
 
 impl StatisticalTest for StudentsTTest {
     type Estimate = f64;
+    type EffectSize = EffectSize;
 
     fn estimate(&self) -> f64 {
         self.estimate
@@ -64,7 +152,7 @@ impl StatisticalTest for StudentsTTest {
         self.p_value
     }
 
-    fn effect_size(&self) -> f64 {
+    fn effect_size(&self) -> EffectSize {
         self.effect_size
     }
 }
@@ -72,6 +160,7 @@ impl StatisticalTest for StudentsTTest {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::effect_size::EffectSizeKind;
 
     #[test]
     fn students_t() {
@@ -81,7 +170,12 @@ mod tests {
         let y = vec![70.0, 118.0, 101.0, 85.0, 107.0, 132.0, 94.0];
         let test = StudentsTTest::independent(&x, &y).unwrap();
         assert_eq!(test.estimate(), 1.8914363974423305);
-        assert_eq!(test.effect_size(), 0.8995574392432595);
+        assert_eq!(test.effect_size().kind(), EffectSizeKind::CohensD);
+        assert_eq!(test.effect_size().estimate(), 0.8995574392432595);
+        assert_eq!(
+            test.effect_size().confidence_interval(),
+            (-0.09161262194797916, 1.866901131792742)
+        );
         assert_eq!(test.p_value(), 0.073911127032672);
     }
 
@@ -93,7 +187,7 @@ mod tests {
         let y = vec![70.0, 118.0, 101.0, 85.0, 107.0, 132.0, 94.0];
         let test = StudentsTTest::independent(&y, &x).unwrap();
         assert_eq!(test.estimate(), -1.8914363974423305);
-        assert_eq!(test.effect_size(), 0.8995574392432595);
+        assert_eq!(test.effect_size().estimate(), -0.8995574392432595);
         assert_eq!(test.p_value(), 0.073911127032672);
     }
 
@@ -103,7 +197,29 @@ mod tests {
         let y = vec![8.5, 9.0, 6.5, 10.5, 9.0, 7.0, 6.5, 7.0];
         let test = StudentsTTest::paired(&x, &y).unwrap();
         assert_eq!(test.estimate(), -2.645751311064591);
-        assert_eq!(test.effect_size(), 0.9354143466934856);
+        assert_eq!(test.effect_size().estimate(), -0.9354143466934856);
+        assert_eq!(
+            test.effect_size().confidence_interval(),
+            (-1.7569102929603473, -0.0705694354453236)
+        );
         assert_eq!(test.p_value(), 0.03314550026377362);
     }
+
+    #[test]
+    fn permutation() {
+        let x = vec![68.0, 68.0, 59.0, 72.0, 64.0, 67.0, 70.0, 74.0];
+        let y = vec![60.0, 67.0, 61.0, 62.0, 67.0, 63.0, 56.0, 58.0];
+        let options = PermutationOptions::default();
+        let test = StudentsTTest::independent_permutation(&x, &y, options).unwrap();
+        assert_eq!(
+            test.estimate(),
+            StudentsTTest::independent(&x, &y).unwrap().estimate()
+        );
+        assert_eq!(test.p_value(), 0.019580419580419582);
+
+        let x = vec![8.0, 6.0, 5.5, 11.0, 8.5, 5.0, 6.0, 6.0];
+        let y = vec![8.5, 9.0, 6.5, 10.5, 9.0, 7.0, 6.5, 7.0];
+        let test = StudentsTTest::paired_permutation(&x, &y, options).unwrap();
+        assert_eq!(test.p_value(), 0.0390625);
+    }
 }
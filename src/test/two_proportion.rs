@@ -0,0 +1,435 @@
+use crate::distribution::invert;
+use crate::effect_size::{EffectSize, EffectSizeKind, CONFIDENCE_LEVEL};
+use statrs::distribution::{ContinuousCDF, Normal};
+use statrs::{Result, StatsError};
+
+use super::fisher_exact::NoncentralHypergeometric;
+use super::{AlternativeHypothesis, ProportionInterval, StatisticalTest};
+
+/// Method of computing a confidence interval of the difference between two proportions
+/// (Newcombe, 1998).
+///
+/// # References
+///
+/// Newcombe, R. G. (1998). Interval Estimation for the Difference between Independent
+///     Proportions: Comparison of Eleven Methods. Statistics in Medicine, 17(8), 873–890.
+///     <https://doi.org/10.1002/(SICI)1097-0258(19980430)17:8%3C873::AID-SIM779%3E3.0.CO;2-I>
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DifferenceInterval {
+    /// Hybrid interval that combines the Wilson score intervals of both proportions
+    /// (Newcombe, 1998).
+    Newcombe,
+    /// Wald interval of the proportions with one success and one failure added to each
+    /// group (Agresti & Caffo, 2000).
+    ///
+    /// Agresti, A., & Caffo, B. (2000). Simple and Effective Confidence Intervals for
+    ///     Proportions and Differences of Proportions Result from Adding Two Successes and Two
+    ///     Failures. The American Statistician, 54(4), 280–288.
+    ///     <https://doi.org/10.1080/00031305.2000.10474560>
+    AgrestiCaffo,
+}
+
+/// Method of computing a confidence interval of the ratio of two proportions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RiskRatioInterval {
+    /// Interval that inverts the score test of the ratio (Koopman, 1984).
+    ///
+    /// Koopman, P. A. R. (1984). Confidence Intervals for the Ratio of Two Binomial
+    ///     Proportions. Biometrics, 40(2), 513–517. <https://doi.org/10.2307/2531405>
+    Koopman,
+    /// Wald interval of the logarithm of the ratio (Katz et al., 1978), with 0.5 added to
+    /// every cell if a group has no successes.
+    ///
+    /// Katz, D., Baptista, J., Azen, S. P., & Pike, M. C. (1978). Obtaining Confidence
+    ///     Intervals for the Risk Ratio in Cohort Studies. Biometrics, 34(3), 469–474.
+    ///     <https://doi.org/10.2307/2530610>
+    Katz,
+}
+
+/// Method of computing a confidence interval of the odds ratio of two proportions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OddsRatioInterval {
+    /// Wald interval of the logarithm of the odds ratio (Woolf, 1955), with 0.5 added to
+    /// every cell if a cell is empty.
+    ///
+    /// Woolf, B. (1955). On Estimating the Relation between Blood Group and Disease. Annals of
+    ///     Human Genetics, 19(4), 251–253. <https://doi.org/10.1111/j.1469-1809.1955.tb01348.x>
+    Woolf,
+    /// Exact interval that inverts the two-sided conditional test that orders tables by their
+    /// probability (Baptista & Pike, 1977), which is shorter than the interval of Fisher's
+    /// exact test.
+    ///
+    /// Baptista, J., & Pike, M. C. (1977). Exact Two-Sided Confidence Limits for the Odds
+    ///     Ratio in a 2 × 2 Table. Journal of the Royal Statistical Society. Series C (Applied
+    ///     Statistics), 26(2), 214–220. <https://doi.org/10.2307/2347041>
+    BaptistaPike,
+}
+
+/// Implements the [z-test of two proportions](https://en.wikipedia.org/wiki/Two-proportion_Z-test),
+/// with confidence intervals of their difference, ratio and odds ratio.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TwoProportionTest {
+    successes: (usize, usize),
+    trials: (usize, usize),
+    estimate: f64,
+    p_value: f64,
+}
+
+impl TwoProportionTest {
+    /// Run the z-test of whether the proportion of `successes_x` out of `trials_x` equals the
+    /// proportion of `successes_y` out of `trials_y`, with the variance of the difference
+    /// estimated from the pooled proportion. The `alternative` hypothesis refers to the
+    /// proportion of `x` compared to that of `y`.
+    ///
+    /// The estimate is the z statistic, and the effect size is the difference between the
+    /// proportions with a 95% Newcombe confidence interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::test::{AlternativeHypothesis, StatisticalTest, TwoProportionTest};
+    ///
+    /// let test = TwoProportionTest::new(120, 1000, 90, 1000, AlternativeHypothesis::Greater)
+    ///     .unwrap();
+    /// assert!(test.p_value() < 0.05);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a group has no trials, if a group has more successes than trials,
+    /// or if every trial is a success or every trial is a failure.
+    pub fn new(
+        successes_x: usize,
+        trials_x: usize,
+        successes_y: usize,
+        trials_y: usize,
+        alternative: AlternativeHypothesis,
+    ) -> Result<TwoProportionTest> {
+        if trials_x == 0 || trials_y == 0 || successes_x > trials_x || successes_y > trials_y {
+            return Err(StatsError::BadParams);
+        }
+        let (n_x, n_y) = (trials_x as f64, trials_y as f64);
+        let p_x = successes_x as f64 / n_x;
+        let p_y = successes_y as f64 / n_y;
+        let pooled = (successes_x + successes_y) as f64 / (n_x + n_y);
+        let estimate = (p_x - p_y) / (pooled * (1.0 - pooled) * (1.0 / n_x + 1.0 / n_y)).sqrt();
+        if !estimate.is_finite() {
+            return Err(StatsError::BadParams);
+        }
+
+        let normal = Normal::new(0.0, 1.0)?;
+        let p_value = match alternative {
+            AlternativeHypothesis::Less => normal.cdf(estimate),
+            AlternativeHypothesis::Greater => normal.sf(estimate),
+            AlternativeHypothesis::Different => 2.0 * normal.sf(estimate.abs()),
+        };
+
+        Ok(TwoProportionTest {
+            successes: (successes_x, successes_y),
+            trials: (trials_x, trials_y),
+            estimate,
+            p_value,
+        })
+    }
+
+    /// Returns the proportions of successes of both groups.
+    pub fn proportions(&self) -> (f64, f64) {
+        (
+            self.successes.0 as f64 / self.trials.0 as f64,
+            self.successes.1 as f64 / self.trials.1 as f64,
+        )
+    }
+
+    /// Returns the difference between the proportions of `x` and `y` with a two-sided
+    /// confidence interval at `confidence_level`, computed with the given `method`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the confidence level is not between 0 and 1.
+    pub fn difference(
+        &self,
+        method: DifferenceInterval,
+        confidence_level: f64,
+    ) -> Result<EffectSize> {
+        let z = quantile(confidence_level)?;
+        let (p_x, p_y) = self.proportions();
+        let difference = p_x - p_y;
+
+        let confidence_interval = match method {
+            DifferenceInterval::Newcombe => {
+                let wilson = |successes, trials| {
+                    ProportionInterval::Wilson.interval(successes, trials, confidence_level)
+                };
+                let (lower_x, upper_x) = wilson(self.successes.0, self.trials.0)?;
+                let (lower_y, upper_y) = wilson(self.successes.1, self.trials.1)?;
+                (
+                    difference - (p_x - lower_x).hypot(upper_y - p_y),
+                    difference + (upper_x - p_x).hypot(p_y - lower_y),
+                )
+            }
+            DifferenceInterval::AgrestiCaffo => {
+                let adjusted = |successes: usize, trials: usize| {
+                    let n = trials as f64 + 2.0;
+                    let p = (successes as f64 + 1.0) / n;
+                    (p, p * (1.0 - p) / n)
+                };
+                let (p_x, variance_x) = adjusted(self.successes.0, self.trials.0);
+                let (p_y, variance_y) = adjusted(self.successes.1, self.trials.1);
+                let margin = z * (variance_x + variance_y).sqrt();
+                (
+                    (p_x - p_y - margin).max(-1.0),
+                    (p_x - p_y + margin).min(1.0),
+                )
+            }
+        };
+
+        Ok(EffectSize::new(
+            EffectSizeKind::RiskDifference,
+            difference,
+            confidence_interval,
+            confidence_level,
+        )
+        .with_sample_sizes(self.trials.0, self.trials.1))
+    }
+
+    /// Returns the ratio of the proportion of `x` to the proportion of `y` with a two-sided
+    /// confidence interval at `confidence_level`, computed with the given `method`. The
+    /// estimate of the Katz method includes its correction for groups without successes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the confidence level is not between 0 and 1.
+    pub fn risk_ratio(
+        &self,
+        method: RiskRatioInterval,
+        confidence_level: f64,
+    ) -> Result<EffectSize> {
+        let z = quantile(confidence_level)?;
+        let (x_x, x_y) = (self.successes.0 as f64, self.successes.1 as f64);
+        let (n_x, n_y) = (self.trials.0 as f64, self.trials.1 as f64);
+
+        let (estimate, confidence_interval) = match method {
+            RiskRatioInterval::Koopman => {
+                let statistic = |ln_ratio: f64| -koopman(x_x, n_x, x_y, n_y, ln_ratio.exp());
+                let ratio = (x_x / n_x) / (x_y / n_y);
+                let start = if x_x == 0.0 || x_y == 0.0 {
+                    ((x_x + 0.5) / (n_x + 0.5) / ((x_y + 0.5) / (n_y + 0.5))).ln()
+                } else {
+                    ratio.ln()
+                };
+                let bound = |target| {
+                    invert(statistic, target, f64::NEG_INFINITY, f64::INFINITY, start).exp()
+                };
+                let lower = if x_x == 0.0 { 0.0 } else { bound(-z) };
+                let upper = if x_y == 0.0 { f64::INFINITY } else { bound(z) };
+                (ratio, (lower, upper))
+            }
+            RiskRatioInterval::Katz => {
+                let (x_x, n_x, x_y, n_y) = if x_x == 0.0 || x_y == 0.0 {
+                    (x_x + 0.5, n_x + 1.0, x_y + 0.5, n_y + 1.0)
+                } else {
+                    (x_x, n_x, x_y, n_y)
+                };
+                let ln_ratio = ((x_x / n_x) / (x_y / n_y)).ln();
+                let margin = z * (1.0 / x_x - 1.0 / n_x + 1.0 / x_y - 1.0 / n_y).sqrt();
+                (
+                    ln_ratio.exp(),
+                    ((ln_ratio - margin).exp(), (ln_ratio + margin).exp()),
+                )
+            }
+        };
+
+        Ok(EffectSize::new(
+            EffectSizeKind::RiskRatio,
+            estimate,
+            confidence_interval,
+            confidence_level,
+        )
+        .with_sample_sizes(self.trials.0, self.trials.1))
+    }
+
+    /// Returns the ratio of the odds of success of `x` to the odds of success of `y` with a
+    /// two-sided confidence interval at `confidence_level`, computed with the given `method`.
+    /// The estimate is the sample odds ratio, which includes the correction for empty cells
+    /// of the Woolf method.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the confidence level is not between 0 and 1.
+    pub fn odds_ratio(
+        &self,
+        method: OddsRatioInterval,
+        confidence_level: f64,
+    ) -> Result<EffectSize> {
+        let z = quantile(confidence_level)?;
+        let cells = [
+            self.successes.0,
+            self.trials.0 - self.successes.0,
+            self.successes.1,
+            self.trials.1 - self.successes.1,
+        ];
+        let [a, b, c, d] = cells.map(|cell| cell as f64);
+
+        let (estimate, confidence_interval) = match method {
+            OddsRatioInterval::Woolf => {
+                let [a, b, c, d] = if cells.contains(&0) {
+                    [a + 0.5, b + 0.5, c + 0.5, d + 0.5]
+                } else {
+                    [a, b, c, d]
+                };
+                let ln_odds_ratio = (a * d / (b * c)).ln();
+                let margin = z * (1.0 / a + 1.0 / b + 1.0 / c + 1.0 / d).sqrt();
+                (
+                    ln_odds_ratio.exp(),
+                    (
+                        (ln_odds_ratio - margin).exp(),
+                        (ln_odds_ratio + margin).exp(),
+                    ),
+                )
+            }
+            OddsRatioInterval::BaptistaPike => {
+                let hypergeometric = NoncentralHypergeometric::new(
+                    self.trials.0,
+                    self.trials.1,
+                    self.successes.0 + self.successes.1,
+                );
+                let interval =
+                    hypergeometric.baptista_pike(self.successes.0, 1.0 - confidence_level);
+                (a * d / (b * c), interval)
+            }
+        };
+
+        Ok(EffectSize::new(
+            EffectSizeKind::OddsRatio,
+            estimate,
+            confidence_interval,
+            confidence_level,
+        )
+        .with_sample_sizes(self.trials.0, self.trials.1))
+    }
+}
+
+impl StatisticalTest for TwoProportionTest {
+    type Estimate = f64;
+    type EffectSize = EffectSize;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> EffectSize {
+        // The confidence level is valid, so the interval cannot fail.
+        self.difference(DifferenceInterval::Newcombe, CONFIDENCE_LEVEL)
+            .unwrap()
+    }
+}
+
+/// Returns the quantile of the standard normal distribution for a two-sided interval at
+/// `confidence_level`.
+fn quantile(confidence_level: f64) -> Result<f64> {
+    if !(0.0..1.0).contains(&confidence_level) {
+        return Err(StatsError::BadParams);
+    }
+    Ok(Normal::new(0.0, 1.0)?.inverse_cdf((1.0 + confidence_level) / 2.0))
+}
+
+/// Returns the signed root of the score statistic of Koopman (1984) for the hypothesis that
+/// the ratio of the proportions of `x_x` out of `n_x` and `x_y` out of `n_y` equals `ratio`,
+/// which decreases with the ratio.
+fn koopman(x_x: f64, n_x: f64, x_y: f64, n_y: f64, ratio: f64) -> f64 {
+    // The constrained maximum likelihood estimate of the proportion of y is the smaller root
+    // of a quadratic equation, written in a form that does not suffer from cancellation.
+    let a = (n_x + n_y) * ratio;
+    let b = -(ratio * (n_x + x_y) + x_x + n_y);
+    let c = x_x + x_y;
+    let p_y = 2.0 * c / (-b + (b * b - 4.0 * a * c).max(0.0).sqrt());
+    let p_x = ratio * p_y;
+
+    let chi_squared = (x_x - n_x * p_x).powi(2) / (n_x * p_x * (1.0 - p_x))
+        + (x_y - n_y * p_y).powi(2) / (n_y * p_y * (1.0 - p_y));
+    chi_squared.sqrt().copysign(x_x - n_x * p_x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_proportion() {
+        let test = TwoProportionTest::new(15, 40, 6, 42, AlternativeHypothesis::Different).unwrap();
+        assert_eq!(test.estimate(), 2.4073671359530198);
+        assert_eq!(test.p_value(), 0.016068007673605483);
+        assert_eq!(test.effect_size().estimate(), 0.23214285714285715);
+        assert_eq!(
+            test.effect_size().confidence_interval(),
+            (0.04239895639741237, 0.404348116287056)
+        );
+
+        let test = TwoProportionTest::new(15, 40, 6, 42, AlternativeHypothesis::Greater).unwrap();
+        assert_eq!(test.p_value(), 0.008034003836802741);
+
+        assert!(TwoProportionTest::new(0, 10, 0, 12, AlternativeHypothesis::Less).is_err());
+        assert!(TwoProportionTest::new(11, 10, 0, 12, AlternativeHypothesis::Less).is_err());
+    }
+
+    #[test]
+    fn difference() {
+        let test =
+            TwoProportionTest::new(56, 70, 48, 80, AlternativeHypothesis::Different).unwrap();
+        let difference = test.difference(DifferenceInterval::Newcombe, 0.95).unwrap();
+        assert_eq!(
+            difference.confidence_interval(),
+            (0.0524314724023649, 0.3338726540369059)
+        );
+        let difference = test
+            .difference(DifferenceInterval::AgrestiCaffo, 0.95)
+            .unwrap();
+        assert_eq!(
+            difference.confidence_interval(),
+            (0.052452926638053704, 0.33575845547576744)
+        );
+    }
+
+    #[test]
+    fn risk_ratio() {
+        let test =
+            TwoProportionTest::new(36, 40, 16, 80, AlternativeHypothesis::Different).unwrap();
+        let ratio = test.risk_ratio(RiskRatioInterval::Koopman, 0.95).unwrap();
+        assert_eq!(ratio.estimate(), 4.5);
+        assert_eq!(
+            ratio.confidence_interval(),
+            (2.939569192215013, 7.1522234348660305)
+        );
+        let ratio = test.risk_ratio(RiskRatioInterval::Katz, 0.95).unwrap();
+        assert_eq!(
+            ratio.confidence_interval(),
+            (2.8685501986034847, 7.059315193388788)
+        );
+
+        let test = TwoProportionTest::new(0, 10, 4, 10, AlternativeHypothesis::Different).unwrap();
+        let ratio = test.risk_ratio(RiskRatioInterval::Koopman, 0.95).unwrap();
+        assert_eq!(ratio.confidence_interval(), (0.0, 0.7866471244417521));
+    }
+
+    #[test]
+    fn odds_ratio() {
+        let test = TwoProportionTest::new(6, 8, 2, 9, AlternativeHypothesis::Different).unwrap();
+        let odds_ratio = test.odds_ratio(OddsRatioInterval::Woolf, 0.95).unwrap();
+        assert_eq!(odds_ratio.estimate(), 10.5);
+        assert_eq!(
+            odds_ratio.confidence_interval(),
+            (1.1146068044663036, 98.91380490251885)
+        );
+        let odds_ratio = test
+            .odds_ratio(OddsRatioInterval::BaptistaPike, 0.95)
+            .unwrap();
+        assert_eq!(
+            odds_ratio.confidence_interval(),
+            (0.8761234829993939, 109.86130681130486)
+        );
+    }
+}
@@ -0,0 +1,352 @@
+use crate::effect_size::EffectSize;
+use crate::statistics::ContingencyTable;
+use statrs::function::factorial::ln_binomial;
+use statrs::{Result, StatsError};
+
+use super::fisher_exact::{NoncentralHypergeometric, TOLERANCE};
+use super::{AlternativeHypothesis, FisherExactTest, StatisticalTest};
+
+/// Default number of values of the nuisance parameter at which the p-value is evaluated.
+static POINTS: usize = 1000;
+
+/// Options of an unconditional exact test.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct UnconditionalOptions {
+    alternative: AlternativeHypothesis,
+    points: usize,
+    pooled: bool,
+}
+
+impl Default for UnconditionalOptions {
+    fn default() -> UnconditionalOptions {
+        UnconditionalOptions {
+            alternative: AlternativeHypothesis::Different,
+            points: POINTS,
+            pooled: true,
+        }
+    }
+}
+
+impl UnconditionalOptions {
+    /// Sets the alternative hypothesis, which refers to the proportion of the first row
+    /// compared to the proportion of the second row.
+    pub fn with_alternative(self, alternative: AlternativeHypothesis) -> UnconditionalOptions {
+        UnconditionalOptions {
+            alternative,
+            ..self
+        }
+    }
+
+    /// Sets the number of equally spaced values of the common proportion, from 0 to 1, at
+    /// which the p-value is evaluated.
+    pub fn with_points(self, points: usize) -> UnconditionalOptions {
+        UnconditionalOptions { points, ..self }
+    }
+
+    /// Sets whether Barnard's test uses the pooled variance of the difference of proportions,
+    /// as in the score test, or the unpooled variance, as in the Wald test.
+    pub fn with_pooled(self, pooled: bool) -> UnconditionalOptions {
+        UnconditionalOptions { pooled, ..self }
+    }
+
+    /// Returns the alternative hypothesis.
+    pub fn alternative(&self) -> AlternativeHypothesis {
+        self.alternative
+    }
+
+    /// Returns the number of values of the common proportion.
+    pub fn points(&self) -> usize {
+        self.points
+    }
+
+    /// Returns whether Barnard's test uses the pooled variance.
+    pub fn pooled(&self) -> bool {
+        self.pooled
+    }
+}
+
+/// Implements unconditional exact tests of the equality of two binomial proportions in a 2×2
+/// contingency table, whose rows are independent samples with fixed sizes and whose first
+/// column counts the successes (Barnard, 1945; Boschloo, 1970).
+///
+/// Unlike Fisher's exact test, the column totals are not conditioned on. The p-value is the
+/// largest probability, over the common proportion of successes under the null hypothesis, of
+/// the tables that are at least as extreme as the observed table, evaluated on a grid.
+///
+/// # References
+///
+/// Barnard, G. A. (1945). A New Test for 2 × 2 Tables. Nature, 156(3954), 177.
+///     <https://doi.org/10.1038/156177a0>
+///
+/// Boschloo, R. D. (1970). Raised conditional level of significance for the 2 × 2-table when
+///     testing the equality of two probabilities. Statistica Neerlandica, 24(1), 1–9.
+///     <https://doi.org/10.1111/j.1467-9574.1970.tb00104.x>
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct UnconditionalExactTest {
+    estimate: f64,
+    effect_size: EffectSize,
+    p_value: f64,
+    nuisance: f64,
+}
+
+impl UnconditionalExactTest {
+    /// Run Barnard's test on the 2×2 `table`, which orders the tables by the z statistic of
+    /// the difference of the proportions of the first and second row. The estimate is the
+    /// observed z statistic.
+    ///
+    /// The effect size is the conditional maximum likelihood estimate of the odds ratio with
+    /// an exact 95% confidence interval, as in [`FisherExactTest`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stattest::statistics::ContingencyTable;
+    /// use stattest::test::{StatisticalTest, UnconditionalExactTest, UnconditionalOptions};
+    ///
+    /// let table = ContingencyTable::new(vec![vec![7, 1], vec![2, 6]]).unwrap();
+    /// let test = UnconditionalExactTest::barnard(&table, UnconditionalOptions::default()).unwrap();
+    /// assert!(test.p_value() < 0.05);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the table is not 2×2, if a row or column is empty, or if fewer than
+    /// two points are requested.
+    pub fn barnard(
+        table: &ContingencyTable,
+        options: UnconditionalOptions,
+    ) -> Result<UnconditionalExactTest> {
+        let (n1, n2) = sample_sizes(table, options)?;
+        let pooled = options.pooled();
+        let statistic = |x1: usize, x2: usize| z_statistic(x1, n1, x2, n2, pooled);
+        let observed = statistic(table.get(0, 0), table.get(1, 0));
+
+        // Larger statistics are more extreme, up to rounding errors.
+        let tolerance = TOLERANCE * observed.abs().max(1.0);
+        let extreme = |x1: usize, x2: usize| {
+            let z = statistic(x1, x2);
+            match options.alternative() {
+                AlternativeHypothesis::Greater => z >= observed - tolerance,
+                AlternativeHypothesis::Less => z <= observed + tolerance,
+                AlternativeHypothesis::Different => z.abs() >= observed.abs() - tolerance,
+            }
+        };
+        UnconditionalExactTest::new(table, options, observed, extreme)
+    }
+
+    /// Run Boschloo's test on the 2×2 `table`, which orders the tables by the p-value of
+    /// Fisher's exact test with the same alternative hypothesis. The estimate is the observed
+    /// p-value of Fisher's exact test. Boschloo's test is uniformly more powerful than
+    /// Fisher's exact test.
+    ///
+    /// The effect size is the conditional maximum likelihood estimate of the odds ratio with
+    /// an exact 95% confidence interval, as in [`FisherExactTest`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the table is not 2×2, if a row or column is empty, or if fewer than
+    /// two points are requested.
+    pub fn boschloo(
+        table: &ContingencyTable,
+        options: UnconditionalOptions,
+    ) -> Result<UnconditionalExactTest> {
+        let (n1, n2) = sample_sizes(table, options)?;
+        let statistic = |x1: usize, x2: usize| {
+            NoncentralHypergeometric::new(n1, n2, x1 + x2).p_value(x1, options.alternative())
+        };
+        let observed = statistic(table.get(0, 0), table.get(1, 0));
+
+        // Smaller p-values are more extreme, up to rounding errors.
+        let threshold = observed * (1.0 + TOLERANCE);
+        let extreme = |x1: usize, x2: usize| statistic(x1, x2) <= threshold;
+        UnconditionalExactTest::new(table, options, observed, extreme)
+    }
+
+    fn new<F>(
+        table: &ContingencyTable,
+        options: UnconditionalOptions,
+        estimate: f64,
+        extreme: F,
+    ) -> Result<UnconditionalExactTest>
+    where
+        F: Fn(usize, usize) -> bool,
+    {
+        let rows = table.row_totals();
+        let (n1, n2) = (rows[0], rows[1]);
+        let n = n1 + n2;
+
+        // The probability of a table only depends on the proportion through the total number
+        // of successes, so sum the binomial coefficients of the extreme tables per total.
+        let mut ln_coefficients = vec![f64::NEG_INFINITY; n + 1];
+        for x1 in 0..=n1 {
+            for x2 in 0..=n2 {
+                if extreme(x1, x2) {
+                    let ln_coefficient =
+                        ln_binomial(n1 as u64, x1 as u64) + ln_binomial(n2 as u64, x2 as u64);
+                    ln_coefficients[x1 + x2] = ln_add(ln_coefficients[x1 + x2], ln_coefficient);
+                }
+            }
+        }
+
+        let (nuisance, p_value) = (0..options.points())
+            .map(|i| {
+                let proportion = i as f64 / (options.points() - 1) as f64;
+                (proportion, probability(&ln_coefficients, proportion))
+            })
+            .fold((0.0, f64::NEG_INFINITY), |max, point| {
+                if point.1 > max.1 {
+                    point
+                } else {
+                    max
+                }
+            });
+
+        let fisher = FisherExactTest::new(table, options.alternative())?;
+        Ok(UnconditionalExactTest {
+            estimate,
+            effect_size: fisher.effect_size(),
+            p_value: p_value.min(1.0),
+            nuisance,
+        })
+    }
+
+    /// Returns the common proportion of successes at which the p-value is attained.
+    pub fn nuisance(&self) -> f64 {
+        self.nuisance
+    }
+}
+
+impl StatisticalTest for UnconditionalExactTest {
+    type Estimate = f64;
+    type EffectSize = EffectSize;
+
+    fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    fn effect_size(&self) -> EffectSize {
+        self.effect_size
+    }
+}
+
+/// Returns the sizes of the samples in the rows of the 2×2 `table`.
+fn sample_sizes(table: &ContingencyTable, options: UnconditionalOptions) -> Result<(usize, usize)> {
+    if table.rows() != 2 || table.columns() != 2 || options.points() < 2 {
+        return Err(StatsError::BadParams);
+    }
+    let rows = table.row_totals();
+    if rows.contains(&0) || table.column_totals().contains(&0) {
+        return Err(StatsError::BadParams);
+    }
+    Ok((rows[0], rows[1]))
+}
+
+/// Returns the z statistic of the difference of the proportions `x1 / n1` and `x2 / n2`, or
+/// zero if its variance is zero.
+fn z_statistic(x1: usize, n1: usize, x2: usize, n2: usize, pooled: bool) -> f64 {
+    let (n1, n2) = (n1 as f64, n2 as f64);
+    let p1 = x1 as f64 / n1;
+    let p2 = x2 as f64 / n2;
+    let variance = if pooled {
+        let p = (x1 + x2) as f64 / (n1 + n2);
+        p * (1.0 - p) * (1.0 / n1 + 1.0 / n2)
+    } else {
+        p1 * (1.0 - p1) / n1 + p2 * (1.0 - p2) / n2
+    };
+    if variance > 0.0 {
+        (p1 - p2) / variance.sqrt()
+    } else {
+        0.0
+    }
+}
+
+/// Returns the probability of the extreme tables given the common `proportion`, from the
+/// logarithms of the summed binomial coefficients of every total number of successes.
+fn probability(ln_coefficients: &[f64], proportion: f64) -> f64 {
+    let n = ln_coefficients.len() - 1;
+    ln_coefficients
+        .iter()
+        .enumerate()
+        .filter(|(_, ln_coefficient)| ln_coefficient.is_finite())
+        .map(|(s, ln_coefficient)| {
+            let successes = if s == 0 {
+                0.0
+            } else {
+                s as f64 * proportion.ln()
+            };
+            let failures = if s == n {
+                0.0
+            } else {
+                (n - s) as f64 * (-proportion).ln_1p()
+            };
+            (ln_coefficient + successes + failures).exp()
+        })
+        .sum()
+}
+
+/// Returns the logarithm of the sum of two numbers given their logarithms.
+fn ln_add(a: f64, b: f64) -> f64 {
+    let max = a.max(b);
+    if max == f64::NEG_INFINITY {
+        return max;
+    }
+    max + ((a - max).exp() + (b - max).exp()).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn barnard() {
+        let table = ContingencyTable::new(vec![vec![7, 1], vec![2, 6]]).unwrap();
+        let options = UnconditionalOptions::default();
+        let test = UnconditionalExactTest::barnard(&table, options).unwrap();
+        assert_eq!(test.estimate(), 2.519763153394848);
+        assert_eq!(test.p_value(), 0.021270685169282284);
+        assert_eq!(test.nuisance(), 0.5005005005005005);
+
+        let test = UnconditionalExactTest::barnard(
+            &table,
+            options.with_alternative(AlternativeHypothesis::Greater),
+        )
+        .unwrap();
+        assert_eq!(test.p_value(), 0.010635342584641147);
+
+        let test = UnconditionalExactTest::barnard(&table, options.with_pooled(false)).unwrap();
+        assert_eq!(test.p_value(), 0.021240167835786066);
+    }
+
+    #[test]
+    fn boschloo() {
+        let table = ContingencyTable::new(vec![vec![74, 43], vec![31, 32]]).unwrap();
+        let options =
+            UnconditionalOptions::default().with_alternative(AlternativeHypothesis::Greater);
+        let test = UnconditionalExactTest::boschloo(&table, options).unwrap();
+        assert_eq!(test.estimate(), 0.04831221008691202);
+        assert_eq!(test.p_value(), 0.0355640520943518);
+        assert_eq!(test.nuisance(), 0.5685685685685685);
+
+        let fisher = FisherExactTest::new(&table, AlternativeHypothesis::Greater).unwrap();
+        assert!(test.p_value() <= fisher.p_value());
+
+        let table = ContingencyTable::new(vec![vec![7, 1], vec![2, 6]]).unwrap();
+        let test =
+            UnconditionalExactTest::boschloo(&table, UnconditionalOptions::default()).unwrap();
+        assert_eq!(test.p_value(), 0.021270685169282284);
+    }
+
+    #[test]
+    fn unconditional_exact_invalid() {
+        let table = ContingencyTable::new(vec![vec![5, 0], vec![3, 0]]).unwrap();
+        let options = UnconditionalOptions::default();
+        assert!(UnconditionalExactTest::barnard(&table, options).is_err());
+
+        let table = ContingencyTable::new(vec![vec![5, 1], vec![3, 2]]).unwrap();
+        assert!(UnconditionalExactTest::boschloo(&table, options.with_points(1)).is_err());
+    }
+}
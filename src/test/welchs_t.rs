@@ -1,8 +1,9 @@
+use crate::effect_size::{noncentral_t_interval, EffectSize, EffectSizeKind, CONFIDENCE_LEVEL};
 use crate::statistics::StatisticsExt;
 use statrs::distribution::{ContinuousCDF, StudentsT};
 use statrs::statistics::Statistics;
 
-use super::StatisticalTest;
+use super::{MeanDifference, StatisticalTest};
 
 /// Implements [Welch's t-test](https://en.wikipedia.org/wiki/Welch's_t-test) (Welch, 1947).
 ///
@@ -14,28 +15,33 @@ use super::StatisticalTest;
 pub struct WelchsTTest {
     df: f64,
     estimate: f64,
-    effect_size: f64,
+    effect_size: EffectSize,
     p_value: f64,
 }
 
 impl WelchsTTest {
     /// Run Welch's two-sample t-test on samples `x` and `y`.
+    ///
+    /// The effect size is Cohen's d, standardized by the root mean square of the two standard
+    /// deviations, with a 95% confidence interval based on the noncentral t-distribution.
     pub fn independent(x: &[f64], y: &[f64]) -> statrs::Result<WelchsTTest> {
-        let var_x = x.variance();
-        let var_y = y.variance();
-        let var_x_n = var_x / x.n();
-        let var_y_n = var_y / y.n();
-        let linear_combination = var_x_n + var_y_n;
-
-        let df = linear_combination.powi(2) / (var_x_n.powi(2) / x.df() + var_y_n.powi(2) / y.df());
-
-        let mean_difference = x.mean() - y.mean();
-        let effect_size = mean_difference.abs() / ((var_x + var_y) / 2.0).sqrt();
-        let t = mean_difference / linear_combination.sqrt();
+        let difference = MeanDifference::welch(x, y);
+        let df = difference.df;
+        let t = difference.t();
 
         let t_distribution = StudentsT::new(0.0, 1.0, df)?;
         let p_value = 2.0 * t_distribution.cdf(-t.abs());
 
+        let scale = difference.standard_error / difference.standardizer;
+        let (lower, upper) = noncentral_t_interval(t, df, CONFIDENCE_LEVEL)?;
+        let effect_size = EffectSize::new(
+            EffectSizeKind::CohensD,
+            difference.estimate / difference.standardizer,
+            (lower * scale, upper * scale),
+            CONFIDENCE_LEVEL,
+        )
+        .with_sample_sizes(x.len(), y.len());
+
         Ok(WelchsTTest {
             df,
             effect_size,
@@ -45,8 +51,30 @@ impl WelchsTTest {
     }
 }
 
+impl MeanDifference {
+    /// Returns the difference between the means of the independent samples `x` and `y`,
+    /// with the Welch-Satterthwaite degrees of freedom.
+    ///
+    /// The standardizer is the root mean square of the two standard deviations.
+    pub(crate) fn welch(x: &[f64], y: &[f64]) -> MeanDifference {
+        let var_x = x.variance();
+        let var_y = y.variance();
+        let var_x_n = var_x / x.n();
+        let var_y_n = var_y / y.n();
+        let linear_combination = var_x_n + var_y_n;
+
+        MeanDifference {
+            estimate: x.mean() - y.mean(),
+            standard_error: linear_combination.sqrt(),
+            standardizer: ((var_x + var_y) / 2.0).sqrt(),
+            df: linear_combination.powi(2) / (var_x_n.powi(2) / x.df() + var_y_n.powi(2) / y.df()),
+        }
+    }
+}
+
 impl StatisticalTest for WelchsTTest {
     type Estimate = f64;
+    type EffectSize = EffectSize;
 
     fn estimate(&self) -> f64 {
         self.estimate
@@ -56,7 +84,7 @@ impl StatisticalTest for WelchsTTest {
         self.p_value
     }
 
-    fn effect_size(&self) -> f64 {
+    fn effect_size(&self) -> EffectSize {
         self.effect_size
     }
 }
@@ -74,7 +102,11 @@ mod tests {
         let test = WelchsTTest::independent(&x, &y).unwrap();
         assert_eq!(test.df, 13.081702113268564);
         assert_eq!(test.estimate(), 1.9107001042454415);
-        assert_eq!(test.effect_size(), 0.904358069450997);
+        assert_eq!(test.effect_size().estimate(), 0.904358069450997);
+        assert_eq!(
+            test.effect_size().confidence_interval(),
+            (-0.09978008064426153, 1.8783819071792602)
+        );
         assert_eq!(test.p_value(), 0.0782070409214568);
     }
 
@@ -87,7 +119,7 @@ mod tests {
         let test = WelchsTTest::independent(&y, &x).unwrap();
         assert_eq!(test.df, 13.081702113268564);
         assert_eq!(test.estimate(), -1.9107001042454415);
-        assert_eq!(test.effect_size(), 0.904358069450997);
+        assert_eq!(test.effect_size().estimate(), -0.904358069450997);
         assert_eq!(test.p_value(), 0.0782070409214568);
     }
 }
@@ -1,4 +1,5 @@
 use crate::distribution::SignedRank;
+use crate::effect_size::{EffectSize, CONFIDENCE_LEVEL};
 use crate::statistics::*;
 use statrs::distribution::ContinuousCDF;
 
@@ -8,12 +9,16 @@ use super::StatisticalTest;
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct WilcoxonWTest {
     estimate: (f64, f64),
-    effect_size: f64,
+    effect_size: EffectSize,
     p_value: f64,
 }
 
 impl WilcoxonWTest {
     /// Run Wilcoxon signed rank test on samples `x` and `y`.
+    ///
+    /// The effect size is the matched-pairs rank-biserial correlation, with a 95% confidence
+    /// interval from the Fisher z-transformation. Use [`EffectSize::rank_biserial_paired`] for
+    /// a bootstrap interval.
     pub fn paired(x: &[f64], y: &[f64]) -> statrs::Result<WilcoxonWTest> {
         let d: Vec<_> = x.iter().zip(y).map(|(x, y)| (x - y).abs()).collect();
         let (ranks, tie_correction) = (&d).ranks();
@@ -37,10 +42,7 @@ impl WilcoxonWTest {
         };
         let distribution = SignedRank::new(d.len(), zeroes, tie_correction)?;
         let p_value = distribution.cdf(estimate_small);
-
-        let n = (&d).n();
-        let rank_sum = n * (n + 1.0) / 2.0;
-        let effect_size = estimate_small / rank_sum;
+        let effect_size = EffectSize::rank_biserial_paired_fisher(x, y, CONFIDENCE_LEVEL)?;
 
         Ok(WilcoxonWTest {
             effect_size,
@@ -52,6 +54,7 @@ impl WilcoxonWTest {
 
 impl StatisticalTest for WilcoxonWTest {
     type Estimate = (f64, f64);
+    type EffectSize = EffectSize;
 
     fn estimate(&self) -> (f64, f64) {
         self.estimate
@@ -61,7 +64,7 @@ impl StatisticalTest for WilcoxonWTest {
         self.p_value
     }
 
-    fn effect_size(&self) -> f64 {
+    fn effect_size(&self) -> EffectSize {
         self.effect_size
     }
 }
@@ -77,7 +80,11 @@ mod tests {
         let test = WilcoxonWTest::paired(&x, &y).unwrap();
         assert_eq!(test.estimate(), (33.5, 2.5));
         assert_eq!(test.p_value(), 0.027785782704095215);
-        assert_eq!(test.effect_size(), 0.06944444444444445);
+        assert_eq!(test.effect_size().estimate(), -0.8611111111111112);
+        assert_eq!(
+            test.effect_size().confidence_interval(),
+            (-0.9689748622974543, -0.47771501263628297)
+        );
     }
 
     #[test]
@@ -87,6 +94,6 @@ mod tests {
         let test = WilcoxonWTest::paired(&x, &y).unwrap();
         assert_eq!(test.estimate(), (3.0, 33.0));
         assert_eq!(test.p_value(), 0.0390625);
-        assert_eq!(test.effect_size(), 0.08333333333333333);
+        assert_eq!(test.effect_size().estimate(), 0.8333333333333334);
     }
 }